@@ -0,0 +1,15 @@
+-- Migración: Revocación de sesiones
+-- Descripción: Permite invalidar los JWT emitidos a un usuario antes de un instante dado
+-- (logout forzado por un admin o desactivación de la cuenta)
+
+CREATE TABLE IF NOT EXISTS public.session_revocations (
+    id_usuario UUID PRIMARY KEY REFERENCES public.users(id) ON DELETE CASCADE,
+    revocado_antes TIMESTAMPTZ NOT NULL,
+    motivo VARCHAR(255),
+    revocado_por UUID REFERENCES public.users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE public.session_revocations IS 'Tokens con iat anterior al segundo de revocado_antes se rechazan para el usuario';
+COMMENT ON COLUMN public.session_revocations.revocado_antes IS 'Instante de la última revocación; solo son válidos tokens emitidos después';
+COMMENT ON COLUMN public.session_revocations.revocado_por IS 'Admin que revocó las sesiones (NULL si fue automático)';
@@ -0,0 +1,23 @@
+-- Migración: API keys de servicio
+-- Descripción: Credenciales para clientes máquina (escáneres de almacén, integraciones)
+-- que no pueden usar el login de Supabase. Solo se guarda el hash SHA-256 del secreto.
+
+CREATE TABLE IF NOT EXISTS public.api_keys (
+    id UUID PRIMARY KEY,
+    nombre VARCHAR(100) NOT NULL,
+    prefijo VARCHAR(32) NOT NULL UNIQUE,
+    hash CHAR(64) NOT NULL,
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    expira_en TIMESTAMPTZ,
+    ultimo_uso TIMESTAMPTZ,
+    revocada_en TIMESTAMPTZ,
+    creada_por UUID REFERENCES public.users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_created_at ON public.api_keys(created_at DESC);
+
+COMMENT ON TABLE public.api_keys IS 'API keys de servicio; el secreto completo solo se muestra al crearla';
+COMMENT ON COLUMN public.api_keys.prefijo IS 'Parte pública de la key (ck_<prefijo>_<secreto>), usada para buscarla';
+COMMENT ON COLUMN public.api_keys.hash IS 'SHA-256 (hex) de la key completa';
+COMMENT ON COLUMN public.api_keys.scopes IS 'Permisos otorgados: pedidos:read, pedidos:estado, productos:write';
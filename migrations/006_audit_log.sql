@@ -0,0 +1,23 @@
+-- Migración: Registro de auditoría
+-- Descripción: Bitácora genérica de acciones sensibles (suplantación de usuarios por
+-- soporte, exportación/anonimización de datos, fusiones, etc.)
+
+CREATE TABLE IF NOT EXISTS public.audit_log (
+    id UUID PRIMARY KEY,
+    id_actor UUID,
+    id_usuario UUID,
+    accion VARCHAR(100) NOT NULL,
+    recurso VARCHAR(255),
+    detalle JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_actor ON public.audit_log(id_actor, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_usuario ON public.audit_log(id_usuario, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_accion ON public.audit_log(accion, created_at DESC);
+
+COMMENT ON TABLE public.audit_log IS 'Bitácora de acciones sensibles; no se modifica ni se borra';
+COMMENT ON COLUMN public.audit_log.id_actor IS 'Usuario que realmente ejecutó la acción (ej: admin que suplanta)';
+COMMENT ON COLUMN public.audit_log.id_usuario IS 'Usuario en cuyo nombre se actuó o que fue afectado';
+COMMENT ON COLUMN public.audit_log.accion IS 'Tipo de acción, ej: impersonation.request';
+COMMENT ON COLUMN public.audit_log.recurso IS 'Recurso afectado, ej: "GET /api/pedidos"';
@@ -0,0 +1,20 @@
+-- Migración: Tipo de documento de identidad en perfiles de cliente
+-- Descripción: Guarda el tipo detectado al validar documento_identidad (cédula,
+-- RUC o pasaporte); facturación lo necesita para identificar al comprador.
+-- Los perfiles existentes quedan con NULL hasta que se vuelva a guardar su documento.
+
+ALTER TABLE public.perfiles_cliente
+    ADD COLUMN IF NOT EXISTS tipo_documento VARCHAR(30);
+
+ALTER TABLE public.perfiles_cliente
+    DROP CONSTRAINT IF EXISTS perfiles_cliente_tipo_documento_check;
+
+ALTER TABLE public.perfiles_cliente
+    ADD CONSTRAINT perfiles_cliente_tipo_documento_check CHECK (
+        tipo_documento IN (
+            'cedula', 'ruc_persona_natural', 'ruc_sociedad_privada',
+            'ruc_entidad_publica', 'pasaporte'
+        )
+    );
+
+COMMENT ON COLUMN public.perfiles_cliente.tipo_documento IS 'Tipo detectado de documento_identidad: cedula, ruc_persona_natural, ruc_sociedad_privada, ruc_entidad_publica o pasaporte';
@@ -0,0 +1,23 @@
+-- Migración: Tipo de línea del teléfono en perfiles de cliente
+-- Descripción: El teléfono se guarda normalizado a E.164 (+593991234567) y se
+-- registra si es fijo o móvil; las notificaciones SMS solo usan móviles.
+-- Los perfiles existentes conservan el valor anterior, con tipo NULL: la
+-- normalización depende del plan de numeración (libphonenumber) y no se puede
+-- replicar en SQL. Después de aplicar la migración ejecutar
+-- `integrador normalize-telefonos` (admite --dry-run).
+
+ALTER TABLE public.perfiles_cliente
+    ADD COLUMN IF NOT EXISTS tipo_telefono VARCHAR(20);
+
+ALTER TABLE public.perfiles_cliente
+    DROP CONSTRAINT IF EXISTS perfiles_cliente_tipo_telefono_check;
+
+ALTER TABLE public.perfiles_cliente
+    ADD CONSTRAINT perfiles_cliente_tipo_telefono_check CHECK (
+        tipo_telefono IN ('movil', 'fijo', 'fijo_o_movil')
+    );
+
+CREATE INDEX IF NOT EXISTS idx_perfiles_cliente_telefono ON public.perfiles_cliente(telefono);
+
+COMMENT ON COLUMN public.perfiles_cliente.telefono IS 'Teléfono en formato E.164, ej: +593991234567';
+COMMENT ON COLUMN public.perfiles_cliente.tipo_telefono IS 'Tipo de línea: movil, fijo o fijo_o_movil';
@@ -0,0 +1,20 @@
+-- Migración: Miembros de perfiles de cliente (cuentas de empresa)
+-- Descripción: Un perfil con RUC puede compartirse con otros usuarios; sus
+-- direcciones y pedidos quedan accesibles según el rol del miembro.
+-- El titular (perfiles_cliente.id_usuario) es siempre owner y no aparece aquí.
+
+CREATE TABLE IF NOT EXISTS public.perfil_miembros (
+    id_perfil UUID NOT NULL REFERENCES public.perfiles_cliente(id_perfil) ON DELETE CASCADE,
+    id_usuario UUID NOT NULL REFERENCES public.users(id) ON DELETE CASCADE,
+    rol VARCHAR(20) NOT NULL CHECK (rol IN ('owner', 'shipper', 'viewer')),
+    invitado_por UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (id_perfil, id_usuario)
+);
+
+CREATE INDEX IF NOT EXISTS idx_perfil_miembros_usuario ON public.perfil_miembros(id_usuario);
+
+COMMENT ON TABLE public.perfil_miembros IS 'Usuarios que comparten un perfil de cliente de empresa';
+COMMENT ON COLUMN public.perfil_miembros.rol IS 'owner: todo, incluida la gestión de miembros; shipper: direcciones y pedidos; viewer: solo lectura';
+COMMENT ON COLUMN public.perfil_miembros.invitado_por IS 'Usuario que agregó al miembro';
@@ -0,0 +1,32 @@
+-- Migración: Crédito de clientes postpago
+-- Descripción: Un perfil puede comprar a crédito hasta limite_credito y pagar
+-- sus facturas dias_credito días después de la emisión. Sin límite (NULL) el
+-- perfil no tiene control de crédito. El saldo no se guarda: se calcula con
+-- las facturas pendientes o vencidas menos sus pagos completados.
+
+-- El límite va en centavos exactos: con DOUBLE PRECISION un pedido que llega
+-- justo al límite podía excederlo por redondeo (1.1 + 2.2 > 3.3)
+ALTER TABLE public.perfiles_cliente
+    ADD COLUMN IF NOT EXISTS limite_credito NUMERIC(12, 2),
+    ADD COLUMN IF NOT EXISTS dias_credito INTEGER;
+
+ALTER TABLE public.perfiles_cliente
+    ALTER COLUMN limite_credito TYPE NUMERIC(12, 2) USING round(limite_credito::numeric, 2);
+
+ALTER TABLE public.perfiles_cliente
+    DROP CONSTRAINT IF EXISTS perfiles_cliente_limite_credito_check,
+    DROP CONSTRAINT IF EXISTS perfiles_cliente_dias_credito_check;
+
+ALTER TABLE public.perfiles_cliente
+    ADD CONSTRAINT perfiles_cliente_limite_credito_check CHECK (limite_credito >= 0),
+    ADD CONSTRAINT perfiles_cliente_dias_credito_check CHECK (
+        dias_credito IS NULL OR (dias_credito BETWEEN 0 AND 365 AND limite_credito IS NOT NULL)
+    );
+
+-- El saldo se calcula por perfil: facturas de sus pedidos y pagos de cada factura
+CREATE INDEX IF NOT EXISTS idx_facturas_pedido ON public.facturas(id_pedido);
+CREATE INDEX IF NOT EXISTS idx_pagos_factura ON public.pagos(id_factura);
+CREATE INDEX IF NOT EXISTS idx_pedidos_perfil ON public.pedidos(id_perfil);
+
+COMMENT ON COLUMN public.perfiles_cliente.limite_credito IS 'Máximo adeudado (facturas impagas + pedidos sin facturar); NULL = sin control de crédito';
+COMMENT ON COLUMN public.perfiles_cliente.dias_credito IS 'Plazo de pago de las facturas, en días desde la emisión';
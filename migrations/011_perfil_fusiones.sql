@@ -0,0 +1,26 @@
+-- Migración: Fusión de perfiles de cliente duplicados
+-- Descripción: Al fusionar, direcciones, pedidos y miembros del perfil origen
+-- pasan al perfil destino y el origen se elimina. Cada fusión deja aquí un
+-- registro que redirige el ID eliminado al perfil que lo absorbió.
+
+CREATE TABLE IF NOT EXISTS public.perfil_fusiones (
+    -- Sin FK: el perfil origen ya no existe
+    id_perfil_origen UUID PRIMARY KEY,
+    -- Sin FK para conservar el registro aunque el destino se elimine después
+    id_perfil_destino UUID NOT NULL,
+    id_usuario_origen UUID,
+    documento_origen VARCHAR(20),
+    telefono_origen VARCHAR(20),
+    direcciones_movidas INTEGER NOT NULL DEFAULT 0,
+    pedidos_movidos INTEGER NOT NULL DEFAULT 0,
+    miembros_movidos INTEGER NOT NULL DEFAULT 0,
+    fusionado_por UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_perfil_fusiones_destino ON public.perfil_fusiones(id_perfil_destino);
+CREATE INDEX IF NOT EXISTS idx_perfil_fusiones_created_at ON public.perfil_fusiones(created_at DESC);
+
+COMMENT ON TABLE public.perfil_fusiones IS 'Perfiles eliminados al fusionarse con un duplicado y el perfil que los absorbió';
+COMMENT ON COLUMN public.perfil_fusiones.id_perfil_destino IS 'Perfil vigente; si este se fusiona después, se actualiza al nuevo destino';
+COMMENT ON COLUMN public.perfil_fusiones.fusionado_por IS 'Admin que ejecutó la fusión';
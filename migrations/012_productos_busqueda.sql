@@ -0,0 +1,57 @@
+-- Migración: Búsqueda de productos por texto completo
+-- Descripción: Columna tsvector generada con la configuración española sin
+-- acentos ("telefono" encuentra "Teléfonos"), índice trigram sobre el nombre
+-- para tolerar errores de tipeo e índice de prefijo sobre el SKU.
+
+-- Sin forzar el esquema: en Supabase las extensiones viven en `extensions` y
+-- pueden estar ya instaladas ahí. Lo que sigue resuelve el esquema real.
+CREATE EXTENSION IF NOT EXISTS unaccent;
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+DO $do$
+DECLARE
+    esquema TEXT := (
+        SELECT n.nspname FROM pg_extension e
+        JOIN pg_namespace n ON n.oid = e.extnamespace
+        WHERE e.extname = 'unaccent'
+    );
+BEGIN
+    -- Configuración española que quita acentos antes de aplicar el stemming
+    IF NOT EXISTS (SELECT 1 FROM pg_ts_config WHERE cfgname = 'spanish_unaccent') THEN
+        CREATE TEXT SEARCH CONFIGURATION public.spanish_unaccent (COPY = pg_catalog.spanish);
+        EXECUTE format(
+            'ALTER TEXT SEARCH CONFIGURATION public.spanish_unaccent
+                ALTER MAPPING FOR hword, hword_part, word WITH %I.unaccent, pg_catalog.spanish_stem',
+            esquema
+        );
+    END IF;
+
+    -- unaccent() es STABLE; este envoltorio fija el diccionario para poder indexarlo
+    EXECUTE format(
+        $f$CREATE OR REPLACE FUNCTION public.f_unaccent(text)
+        RETURNS text
+        LANGUAGE sql IMMUTABLE PARALLEL SAFE STRICT
+        AS $body$ SELECT %1$I.unaccent('%1$I.unaccent'::regdictionary, $1) $body$$f$,
+        esquema
+    );
+END $do$;
+
+-- Pesos: A = nombre y SKU, B = categoría, C = descripción
+ALTER TABLE public.productos
+    ADD COLUMN IF NOT EXISTS busqueda tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('public.spanish_unaccent', coalesce(nombre_producto, '')), 'A') ||
+        setweight(to_tsvector('public.spanish_unaccent', coalesce(sku, '')), 'A') ||
+        setweight(to_tsvector('public.spanish_unaccent', coalesce(categoria, '')), 'B') ||
+        setweight(to_tsvector('public.spanish_unaccent', coalesce(descripcion, '')), 'C')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_productos_busqueda
+    ON public.productos USING gin (busqueda);
+
+CREATE INDEX IF NOT EXISTS idx_productos_nombre_trgm
+    ON public.productos USING gin (public.f_unaccent(lower(nombre_producto)) gin_trgm_ops);
+
+CREATE INDEX IF NOT EXISTS idx_productos_sku_prefijo
+    ON public.productos (lower(sku) text_pattern_ops);
+
+COMMENT ON COLUMN public.productos.busqueda IS 'Vector de búsqueda (spanish_unaccent) generado a partir de nombre, SKU, categoría y descripción';
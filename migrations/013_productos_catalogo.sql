@@ -0,0 +1,15 @@
+-- Migración: Índices del catálogo paginado
+-- Descripción: El catálogo público solo lista productos activos, ordenados por
+-- (columna, id_producto) para la paginación por cursor.
+
+CREATE INDEX IF NOT EXISTS idx_productos_catalogo_nombre
+    ON public.productos (nombre_producto, id_producto) WHERE estado;
+
+CREATE INDEX IF NOT EXISTS idx_productos_catalogo_precio
+    ON public.productos (precio, id_producto) WHERE estado;
+
+CREATE INDEX IF NOT EXISTS idx_productos_catalogo_created
+    ON public.productos (created_at, id_producto) WHERE estado;
+
+CREATE INDEX IF NOT EXISTS idx_productos_categoria
+    ON public.productos (categoria) WHERE estado;
@@ -0,0 +1,102 @@
+-- Migración: Categorías de productos jerárquicas
+-- Descripción: Las categorías pasan a ser una tabla con padre/hijos, slug, orden
+-- y estado. productos.id_categoria es la referencia; productos.categoria queda
+-- como copia del nombre (la usa la búsqueda de texto) y la mantiene la app.
+-- Los textos libres existentes se agrupan por su raíz en español sin acentos
+-- ("Electrónicos", "electronicos" y "Electronica" → una categoría) y cada texto
+-- original queda registrado en categorias_legado.
+
+CREATE TABLE IF NOT EXISTS public.categorias (
+    id_categoria UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    nombre VARCHAR(100) NOT NULL,
+    slug VARCHAR(120) NOT NULL,
+    id_padre UUID REFERENCES public.categorias(id_categoria) ON DELETE RESTRICT,
+    orden INTEGER NOT NULL DEFAULT 0,
+    activo BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT categorias_slug_key UNIQUE (slug),
+    CONSTRAINT categorias_slug_check CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+    CONSTRAINT categorias_padre_check CHECK (id_padre <> id_categoria)
+);
+
+CREATE INDEX IF NOT EXISTS idx_categorias_padre ON public.categorias(id_padre);
+
+-- Texto libre original → categoría asignada (trazabilidad y compatibilidad con
+-- clientes que todavía envían `categoria` como texto)
+CREATE TABLE IF NOT EXISTS public.categorias_legado (
+    texto VARCHAR PRIMARY KEY,
+    id_categoria UUID NOT NULL REFERENCES public.categorias(id_categoria) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE public.productos
+    ADD COLUMN IF NOT EXISTS id_categoria UUID REFERENCES public.categorias(id_categoria) ON DELETE RESTRICT;
+
+CREATE INDEX IF NOT EXISTS idx_productos_id_categoria ON public.productos(id_categoria);
+
+-- ----------------------------------------------------------------------------
+-- Migración de los textos libres
+-- ----------------------------------------------------------------------------
+
+CREATE TEMP TABLE categorias_migracion AS
+WITH textos AS (
+    SELECT trim(categoria) AS texto,
+           COUNT(*) AS usos,
+           COALESCE(
+               NULLIF(array_to_string(tsvector_to_array(
+                   to_tsvector('public.spanish_unaccent', trim(categoria))), ' '), ''),
+               lower(public.f_unaccent(trim(categoria)))
+           ) AS clave
+    FROM public.productos
+    WHERE id_categoria IS NULL AND trim(coalesce(categoria, '')) <> ''
+    GROUP BY trim(categoria)
+),
+-- El nombre del grupo es su texto más usado
+grupos AS (
+    SELECT clave, (array_agg(texto ORDER BY usos DESC, texto))[1] AS nombre
+    FROM textos
+    GROUP BY clave
+),
+bases AS (
+    SELECT clave, nombre,
+           COALESCE(NULLIF(trim(BOTH '-' FROM regexp_replace(
+               lower(public.f_unaccent(nombre)), '[^a-z0-9]+', '-', 'g')), ''), 'categoria') AS base
+    FROM grupos
+),
+-- Grupos distintos con el mismo slug base reciben sufijo -2, -3, ...
+slugs AS (
+    SELECT clave, nombre, base,
+           row_number() OVER (PARTITION BY base ORDER BY nombre, clave) AS n
+    FROM bases
+)
+SELECT t.texto, s.nombre,
+       CASE WHEN s.n = 1 THEN s.base ELSE s.base || '-' || s.n END AS slug
+FROM textos t
+JOIN slugs s USING (clave);
+
+-- Un slug que ya exista (p. ej. creado por un admin) absorbe el grupo
+INSERT INTO public.categorias (nombre, slug)
+SELECT DISTINCT nombre, slug FROM categorias_migracion
+ON CONFLICT (slug) DO NOTHING;
+
+INSERT INTO public.categorias_legado (texto, id_categoria)
+SELECT m.texto, c.id_categoria
+FROM categorias_migracion m
+JOIN public.categorias c ON c.slug = m.slug
+ON CONFLICT (texto) DO NOTHING;
+
+UPDATE public.productos p
+SET id_categoria = c.id_categoria,
+    categoria = c.nombre
+FROM public.categorias_legado l
+JOIN public.categorias c ON c.id_categoria = l.id_categoria
+WHERE p.id_categoria IS NULL
+  AND trim(p.categoria) = l.texto;
+
+DROP TABLE categorias_migracion;
+
+COMMENT ON TABLE public.categorias IS 'Categorías de productos (árbol por id_padre)';
+COMMENT ON COLUMN public.categorias.orden IS 'Posición entre hermanas (ascendente; empate por nombre)';
+COMMENT ON COLUMN public.productos.categoria IS 'Nombre de la categoría (copia de categorias.nombre mantenida por la app)';
+COMMENT ON TABLE public.categorias_legado IS 'Textos libres de productos.categoria anteriores a 014 y la categoría a la que se asignaron';
@@ -0,0 +1,59 @@
+-- Migración: Kardex de movimientos de stock
+-- Descripción: Cada cambio de productos.stock deja un movimiento con la cantidad,
+-- el saldo resultante, el motivo, quién lo hizo y el pedido relacionado. Se
+-- escribe en la misma transacción que el cambio de stock, así que la suma de los
+-- movimientos de un producto debe coincidir con su stock.
+
+CREATE TABLE IF NOT EXISTS public.movimientos_stock (
+    id_movimiento UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- El kardex se conserva: un producto con movimientos no se borra, se desactiva
+    id_producto UUID NOT NULL REFERENCES public.productos(id_producto) ON DELETE RESTRICT,
+    cantidad INTEGER NOT NULL,
+    saldo INTEGER NOT NULL,
+    motivo VARCHAR(500) NOT NULL,
+    id_actor UUID,
+    -- Las API keys se revocan, no se eliminan
+    id_api_key UUID REFERENCES public.api_keys(id),
+    id_pedido UUID REFERENCES public.pedidos(id_pedido) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    CONSTRAINT movimientos_stock_cantidad_check CHECK (cantidad <> 0),
+    CONSTRAINT movimientos_stock_saldo_check CHECK (saldo >= 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_movimientos_stock_producto
+    ON public.movimientos_stock(id_producto, created_at DESC, id_movimiento DESC);
+CREATE INDEX IF NOT EXISTS idx_movimientos_stock_pedido
+    ON public.movimientos_stock(id_pedido) WHERE id_pedido IS NOT NULL;
+
+-- Solo inserción: se rechazan UPDATE y DELETE directos. Los que dispara una FK
+-- (SET NULL al borrar el pedido) sí se permiten.
+CREATE OR REPLACE FUNCTION public.movimientos_stock_solo_insercion()
+RETURNS trigger
+LANGUAGE plpgsql
+AS $$
+BEGIN
+    IF pg_trigger_depth() > 1 THEN
+        RETURN COALESCE(NEW, OLD);
+    END IF;
+    RAISE EXCEPTION 'movimientos_stock no admite %', TG_OP
+        USING HINT = 'Registre un movimiento compensatorio';
+END $$;
+
+DROP TRIGGER IF EXISTS trg_movimientos_stock_solo_insercion ON public.movimientos_stock;
+CREATE TRIGGER trg_movimientos_stock_solo_insercion
+    BEFORE UPDATE OR DELETE ON public.movimientos_stock
+    FOR EACH ROW EXECUTE FUNCTION public.movimientos_stock_solo_insercion();
+
+-- Saldo de apertura: el stock actual de los productos sin movimientos
+INSERT INTO public.movimientos_stock (id_producto, cantidad, saldo, motivo)
+SELECT p.id_producto, p.stock, p.stock, 'Saldo inicial (apertura del kardex)'
+FROM public.productos p
+WHERE p.stock <> 0
+  AND NOT EXISTS (SELECT 1 FROM public.movimientos_stock m WHERE m.id_producto = p.id_producto);
+
+COMMENT ON TABLE public.movimientos_stock IS 'Kardex: movimientos de stock de productos (solo inserción)';
+COMMENT ON COLUMN public.movimientos_stock.cantidad IS 'Positiva = entrada, negativa = salida';
+COMMENT ON COLUMN public.movimientos_stock.saldo IS 'Stock del producto después del movimiento';
+COMMENT ON COLUMN public.movimientos_stock.id_actor IS 'Usuario que registró el movimiento (el admin real si hay suplantación)';
+COMMENT ON COLUMN public.movimientos_stock.id_api_key IS 'API key que registró el movimiento (id_actor queda null)';
+COMMENT ON COLUMN public.movimientos_stock.created_at IS 'clock_timestamp(): respeta el orden en que se tomó el bloqueo del producto';
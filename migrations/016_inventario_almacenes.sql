@@ -0,0 +1,87 @@
+-- Migración: Inventario por almacén
+-- Descripción: El stock de cada producto se lleva por almacén (direcciones de
+-- tipo almacen). productos.stock queda como el total de todos los almacenes y lo
+-- mantiene la app en la misma transacción. Los movimientos del kardex indican el
+-- almacén afectado; una transferencia son dos movimientos con el mismo
+-- id_transferencia.
+
+CREATE TABLE IF NOT EXISTS public.inventario_almacen (
+    id_producto UUID NOT NULL REFERENCES public.productos(id_producto) ON DELETE CASCADE,
+    id_almacen UUID NOT NULL REFERENCES public.direcciones(id_direccion) ON DELETE RESTRICT,
+    stock INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (id_producto, id_almacen),
+    CONSTRAINT inventario_almacen_stock_check CHECK (stock >= 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_inventario_almacen_almacen ON public.inventario_almacen(id_almacen);
+
+ALTER TABLE public.movimientos_stock
+    ADD COLUMN IF NOT EXISTS id_almacen UUID REFERENCES public.direcciones(id_direccion) ON DELETE RESTRICT,
+    ADD COLUMN IF NOT EXISTS saldo_almacen INTEGER,
+    ADD COLUMN IF NOT EXISTS id_transferencia UUID;
+
+CREATE INDEX IF NOT EXISTS idx_movimientos_stock_almacen
+    ON public.movimientos_stock(id_almacen, id_producto) WHERE id_almacen IS NOT NULL;
+
+-- ----------------------------------------------------------------------------
+-- Asignación del stock existente
+-- ----------------------------------------------------------------------------
+-- Todo el stock actual pasa al almacén más antiguo (prefiriendo uno activo),
+-- registrado en el kardex como una transferencia desde "sin almacén". Si hay
+-- stock y ningún almacén, el stock queda sin asignar (la conciliación lo reporta
+-- como descuadre): cree un almacén y ejecute `integrador asignar-stock`, que
+-- registra la misma transferencia.
+
+DO $$
+DECLARE
+    v_almacen UUID;
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM public.productos p
+        WHERE p.stock > 0
+          AND NOT EXISTS (SELECT 1 FROM public.inventario_almacen i WHERE i.id_producto = p.id_producto)
+    ) THEN
+        RETURN;
+    END IF;
+
+    SELECT id_direccion INTO v_almacen
+    FROM public.direcciones
+    WHERE tipo = 'almacen'
+    ORDER BY activo DESC, created_at ASC, id_direccion ASC
+    LIMIT 1;
+
+    IF v_almacen IS NULL THEN
+        RAISE NOTICE 'Hay productos con stock pero ningún almacén: el stock queda sin asignar'
+            USING HINT = 'Cree un almacén (POST /api/admin/almacenes) y ejecute: integrador asignar-stock --almacen <id_almacen>';
+        RETURN;
+    END IF;
+
+    RAISE NOTICE 'Stock existente asignado al almacén %', v_almacen;
+
+    CREATE TEMP TABLE inventario_asignacion AS
+    SELECT p.id_producto, p.stock, gen_random_uuid() AS id_transferencia
+    FROM public.productos p
+    WHERE p.stock > 0
+      AND NOT EXISTS (SELECT 1 FROM public.inventario_almacen i WHERE i.id_producto = p.id_producto);
+
+    INSERT INTO public.inventario_almacen (id_producto, id_almacen, stock)
+    SELECT id_producto, v_almacen, stock FROM inventario_asignacion;
+
+    INSERT INTO public.movimientos_stock (id_producto, cantidad, saldo, motivo, id_transferencia)
+    SELECT id_producto, -stock, stock, 'Asignación del stock existente a un almacén', id_transferencia
+    FROM inventario_asignacion;
+
+    INSERT INTO public.movimientos_stock
+        (id_producto, id_almacen, cantidad, saldo, saldo_almacen, motivo, id_transferencia)
+    SELECT id_producto, v_almacen, stock, stock, stock, 'Asignación del stock existente a un almacén', id_transferencia
+    FROM inventario_asignacion;
+
+    DROP TABLE inventario_asignacion;
+END $$;
+
+COMMENT ON TABLE public.inventario_almacen IS 'Stock de cada producto por almacén';
+COMMENT ON COLUMN public.productos.stock IS 'Total de inventario_almacen.stock del producto (lo mantiene la app)';
+COMMENT ON COLUMN public.movimientos_stock.id_almacen IS 'Almacén afectado (null = movimientos anteriores al inventario por almacén)';
+COMMENT ON COLUMN public.movimientos_stock.saldo_almacen IS 'Stock del producto en el almacén después del movimiento';
+COMMENT ON COLUMN public.movimientos_stock.id_transferencia IS 'Agrupa la salida y la entrada de una transferencia entre almacenes';
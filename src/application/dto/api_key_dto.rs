@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+use chrono::{DateTime, Utc};
+
+// ============================================================================
+// CREATE API KEY DTO
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateApiKeyDTO {
+    /// Nombre descriptivo (ej: "Escáner almacén Quito")
+    pub nombre: String,
+
+    /// Scopes otorgados (pedidos:read, pedidos:estado, productos:write)
+    pub scopes: Vec<String>,
+
+    /// Fecha de expiración (opcional, sin expiración si se omite)
+    pub expira_en: Option<DateTime<Utc>>,
+}
+
+// ============================================================================
+// API KEY RESPONSE DTO
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyResponseDTO {
+    /// ID de la API key
+    pub id: Uuid,
+
+    /// Nombre descriptivo
+    pub nombre: String,
+
+    /// Prefijo público, permite identificar la key sin exponer el secreto
+    pub prefijo: String,
+
+    /// Scopes otorgados
+    pub scopes: Vec<String>,
+
+    /// Fecha de expiración
+    pub expira_en: Option<DateTime<Utc>>,
+
+    /// Último uso registrado
+    pub ultimo_uso: Option<DateTime<Utc>>,
+
+    /// Fecha de revocación (null si sigue activa)
+    pub revocada_en: Option<DateTime<Utc>>,
+
+    /// Admin que la creó
+    pub creada_por: Option<Uuid>,
+
+    /// Fecha de creación
+    pub created_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// API KEY CREATED RESPONSE DTO
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyCreatedResponseDTO {
+    /// Key completa para el header `X-API-Key`. Solo se muestra en esta respuesta.
+    pub secret: String,
+
+    /// Datos de la API key creada
+    pub api_key: ApiKeyResponseDTO,
+}
+
+// ============================================================================
+// API KEYS LIST RESPONSE DTO
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeysListResponseDTO {
+    /// Total de API keys
+    pub total: i64,
+
+    /// Lista de API keys (sin secretos)
+    pub api_keys: Vec<ApiKeyResponseDTO>,
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::entities::Categoria;
+
+// ============================================================================
+// REQUEST DTOs
+// ============================================================================
+
+/// DTO para crear una categoría (admin)
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateCategoriaDTO {
+    #[schema(example = "Teléfonos Móviles")]
+    pub nombre: String,
+
+    /// Si no se envía se genera a partir del nombre
+    #[schema(example = "telefonos-moviles")]
+    pub slug: Option<String>,
+
+    /// Categoría padre (null = raíz)
+    pub id_padre: Option<Uuid>,
+
+    /// Posición entre hermanas (default: 0)
+    pub orden: Option<i32>,
+
+    /// Default: true
+    pub activo: Option<bool>,
+}
+
+/// DTO para actualizar una categoría (admin); los campos ausentes no cambian
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateCategoriaDTO {
+    pub nombre: Option<String>,
+
+    pub slug: Option<String>,
+
+    /// Nuevo padre; `null` explícito la convierte en raíz
+    #[serde(default, deserialize_with = "presente")]
+    #[schema(value_type = Option<Uuid>)]
+    pub id_padre: Option<Option<Uuid>>,
+
+    pub orden: Option<i32>,
+
+    pub activo: Option<bool>,
+}
+
+/// Distingue un campo ausente (`None`) de un `null` explícito (`Some(None)`)
+pub(crate) fn presente<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+// ============================================================================
+// RESPONSE DTOs
+// ============================================================================
+
+/// DTO de respuesta para una categoría (admin)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CategoriaResponseDTO {
+    pub id_categoria: Uuid,
+    pub nombre: String,
+    pub slug: String,
+    pub id_padre: Option<Uuid>,
+    pub orden: i32,
+    pub activo: bool,
+
+    /// Productos asignados directamente (activos e inactivos)
+    pub productos: i64,
+
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Lista plana de categorías (admin)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CategoriasListResponseDTO {
+    pub categorias: Vec<CategoriaResponseDTO>,
+    pub total: usize,
+}
+
+/// Nodo del árbol público de categorías
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CategoriaArbolDTO {
+    pub id_categoria: Uuid,
+    pub nombre: String,
+    pub slug: String,
+    pub orden: i32,
+
+    /// Productos activos de la categoría y sus subcategorías
+    pub productos: i64,
+
+    /// Subcategorías activas, por orden y nombre
+    pub hijos: Vec<CategoriaArbolDTO>,
+}
+
+/// Árbol público de categorías activas
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CategoriasArbolResponseDTO {
+    /// Categorías raíz
+    pub categorias: Vec<CategoriaArbolDTO>,
+}
+
+// ============================================================================
+// CONVERSIONES (From impls)
+// ============================================================================
+
+/// Categoría con la cantidad de productos asignados directamente
+impl From<(Categoria, i64)> for CategoriaResponseDTO {
+    fn from((c, productos): (Categoria, i64)) -> Self {
+        Self {
+            id_categoria: c.id_categoria,
+            nombre: c.nombre,
+            slug: c.slug,
+            id_padre: c.id_padre,
+            orden: c.orden,
+            activo: c.activo,
+            productos,
+            created_at: c.created_at,
+            updated_at: c.updated_at,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::entities::EstadoCredito;
+
+// ============================================================================
+// REQUEST DTOs
+// ============================================================================
+
+/// DTO para configurar el crédito de un perfil (admin)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ConfigurarCreditoDTO {
+    /// Monto máximo adeudado; null quita el control de crédito
+    #[schema(example = 5000.0)]
+    pub limite_credito: Option<f64>,
+
+    /// Plazo de pago en días (0 a 365); requiere límite
+    #[schema(example = 30)]
+    pub dias_credito: Option<i32>,
+}
+
+/// Parámetros de `GET /api/admin/perfiles/credito`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CreditoAlertasQueryDTO {
+    /// Fracción del límite a partir de la cual se lista (default: 0.8)
+    pub umbral: Option<f64>,
+}
+
+// ============================================================================
+// RESPONSE DTOs
+// ============================================================================
+
+/// DTO de respuesta con la situación de crédito de un perfil
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EstadoCreditoResponseDTO {
+    pub id_perfil: Uuid,
+    pub documento_identidad: Option<String>,
+
+    /// null: el perfil no tiene control de crédito
+    pub limite_credito: Option<f64>,
+    pub dias_credito: Option<i32>,
+
+    /// Facturas pendientes o vencidas menos sus pagos completados
+    pub saldo_facturas: f64,
+
+    /// Parte del saldo con el plazo de pago cumplido
+    pub saldo_vencido: f64,
+
+    /// Pedidos no cancelados que aún no tienen factura
+    pub pedidos_por_facturar: f64,
+
+    /// `saldo_facturas + pedidos_por_facturar`: lo que cuenta para el límite
+    pub comprometido: f64,
+
+    /// Crédito restante (null sin límite)
+    pub disponible: Option<f64>,
+
+    /// Fracción del límite usada (1.0 = en el límite; null sin límite o con límite 0)
+    #[schema(example = 0.85)]
+    pub utilizacion: Option<f64>,
+}
+
+/// DTO de respuesta para los perfiles cerca o sobre su límite
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreditoAlertasResponseDTO {
+    pub umbral: f64,
+    pub perfiles: Vec<EstadoCreditoResponseDTO>,
+    pub total: usize,
+}
+
+// ============================================================================
+// CONVERSIONES
+// ============================================================================
+
+impl From<EstadoCredito> for EstadoCreditoResponseDTO {
+    fn from(estado: EstadoCredito) -> Self {
+        Self {
+            comprometido: estado.comprometido().to_f64().unwrap_or(0.0),
+            disponible: estado.disponible().and_then(|d| d.to_f64()),
+            utilizacion: estado.utilizacion(),
+            id_perfil: estado.id_perfil,
+            documento_identidad: estado.documento_identidad,
+            limite_credito: estado.limite_credito.and_then(|l| l.to_f64()),
+            dias_credito: estado.dias_credito,
+            saldo_facturas: estado.saldo_facturas.to_f64().unwrap_or(0.0),
+            saldo_vencido: estado.saldo_vencido.to_f64().unwrap_or(0.0),
+            pedidos_por_facturar: estado.pedidos_por_facturar.to_f64().unwrap_or(0.0),
+        }
+    }
+}
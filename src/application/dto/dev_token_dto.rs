@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+use crate::domain::auth::ROL_CLIENTE;
+
+// ============================================================================
+// DEV TOKEN DTOs (solo ENVIRONMENT=development)
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DevTokenRequestDTO {
+    /// UUID del usuario (si se omite se genera uno nuevo)
+    pub user_id: Option<Uuid>,
+
+    /// Email incluido en los claims
+    pub email: Option<String>,
+
+    /// Rol en el sistema (cliente, transportista, admin)
+    #[serde(default = "default_role")]
+    pub rol: String,
+
+    /// Duración del token en segundos (por defecto 3600)
+    pub expires_in: Option<i64>,
+}
+
+fn default_role() -> String {
+    ROL_CLIENTE.to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DevTokenResponseDTO {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub user_id: Uuid,
+    pub rol: String,
+}
@@ -0,0 +1,36 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+use super::{DireccionResponseDTO, PerfilAccesibleDTO, PerfilClienteResponseDTO, UserResponseDTO};
+
+// ============================================================================
+// RESPONSE DTOs
+// ============================================================================
+
+/// Estado inicial del usuario autenticado para el frontend (`GET /api/me`)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MeResponseDTO {
+    /// Fila de `public.users`, con el rol real de la aplicación
+    pub usuario: UserResponseDTO,
+
+    /// Perfil de cliente (null si todavía no lo creó)
+    pub perfil: Option<PerfilClienteResponseDTO>,
+
+    /// Dirección predeterminada del perfil, si tiene
+    pub direccion_predeterminada: Option<DireccionResponseDTO>,
+
+    /// Cantidad de pedidos por estado (todos los estados, 0 si no hay)
+    #[schema(example = json!({"pendiente": 1, "confirmado": 0, "en_transito": 2, "entregado": 5, "cancelado": 0}))]
+    pub pedidos_por_estado: BTreeMap<String, i64>,
+
+    /// Total de pedidos del perfil
+    pub total_pedidos: i64,
+
+    /// Perfiles de empresa de los que es miembro (se eligen con `X-Perfil-Id`)
+    pub organizaciones: Vec<PerfilAccesibleDTO>,
+
+    /// true si el usuario es cliente, aún no creó su perfil (`POST /api/perfil`)
+    /// y no es miembro de ningún perfil de empresa
+    pub requiere_onboarding: bool,
+}
@@ -3,9 +3,27 @@ pub mod producto_dto;
 pub mod pedido_dto;
 pub mod perfil_cliente_dto;
 pub mod direccion_dto;
+pub mod dev_token_dto;
+pub mod api_key_dto;
+pub mod privacy_dto;
+pub mod me_dto;
+pub mod perfil_miembro_dto;
+pub mod credito_dto;
+pub mod perfil_fusion_dto;
+pub mod categoria_dto;
+pub mod movimiento_stock_dto;
 
 pub use user_dto::*;
-pub use producto_dto::{CreateProductoDTO, UpdateProductoDTO, UpdateStockDTO, UpdateEstadoProductoDTO, ProductoResponseDTO, ProductosListResponseDTO};
+pub use producto_dto::{CreateProductoDTO, UpdateProductoDTO, UpdateStockDTO, UpdateEstadoProductoDTO, ProductoResponseDTO, ProductosListResponseDTO, AlmacenCercanoDTO, PaginacionDTO, CategoriaFacetaDTO, ProductosQueryDTO, ModoBusqueda, ProductoBusquedaResponseDTO, ProductosBusquedaResponseDTO};
 pub use pedido_dto::*;
 pub use perfil_cliente_dto::*;
 pub use direccion_dto::{CreateDireccionDTO, UpdateDireccionDTO, CreateAlmacenDTO, DireccionResponseDTO, DireccionesListResponseDTO};
+pub use dev_token_dto::{DevTokenRequestDTO, DevTokenResponseDTO};
+pub use api_key_dto::*;
+pub use privacy_dto::{PersonalDataExportDTO, AnonymizationResponseDTO};
+pub use me_dto::MeResponseDTO;
+pub use perfil_miembro_dto::*;
+pub use credito_dto::*;
+pub use perfil_fusion_dto::*;
+pub use categoria_dto::*;
+pub use movimiento_stock_dto::*;
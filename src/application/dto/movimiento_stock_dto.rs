@@ -0,0 +1,225 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::entities::{DescuadreStock, InventarioAlmacen, MovimientoStock};
+
+use super::ProductoResponseDTO;
+
+// ============================================================================
+// QUERY DTOs
+// ============================================================================
+
+/// Parámetros de `GET /api/admin/productos/{id}/movimientos`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MovimientosStockQueryDTO {
+    /// Movimientos desde (RFC 3339, inclusive)
+    pub desde: Option<DateTime<Utc>>,
+
+    /// Movimientos hasta (RFC 3339, inclusive)
+    pub hasta: Option<DateTime<Utc>>,
+
+    /// Tamaño de página (default: 50, máximo: 200)
+    pub limit: Option<i64>,
+
+    /// Cursor opaco devuelto en `next_cursor`
+    pub cursor: Option<String>,
+
+    /// Solo los movimientos de este almacén
+    pub id_almacen: Option<Uuid>,
+}
+
+// ============================================================================
+// REQUEST DTOs
+// ============================================================================
+
+/// Request para mover stock de un producto entre dos almacenes
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct TransferenciaStockDTO {
+    pub id_almacen_origen: Uuid,
+
+    /// Debe ser un almacén activo
+    pub id_almacen_destino: Uuid,
+
+    /// Unidades a mover (mayor que 0)
+    #[schema(example = 20)]
+    pub cantidad: i32,
+
+    #[schema(example = "Reposición de la bodega de Guayaquil")]
+    pub motivo: String,
+}
+
+// ============================================================================
+// RESPONSE DTOs
+// ============================================================================
+
+/// Movimiento del kardex de un producto
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MovimientoStockResponseDTO {
+    pub id_movimiento: Uuid,
+
+    /// Positiva = entrada, negativa = salida
+    #[schema(example = -30)]
+    pub cantidad: i32,
+
+    /// Stock total después del movimiento
+    #[schema(example = 120)]
+    pub saldo: i32,
+
+    /// Almacén afectado (null = movimientos anteriores al inventario por almacén)
+    pub id_almacen: Option<Uuid>,
+
+    /// Stock en el almacén después del movimiento
+    #[schema(example = 45)]
+    pub saldo_almacen: Option<i32>,
+
+    /// Agrupa la salida y la entrada de una transferencia
+    pub id_transferencia: Option<Uuid>,
+
+    #[schema(example = "Merma por rotura en bodega")]
+    pub motivo: String,
+
+    /// Usuario que registró el movimiento (null = API key, migración o proceso interno)
+    pub id_actor: Option<Uuid>,
+
+    /// API key que registró el movimiento
+    pub id_api_key: Option<Uuid>,
+
+    /// Pedido relacionado
+    pub id_pedido: Option<Uuid>,
+
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<MovimientoStock> for MovimientoStockResponseDTO {
+    fn from(m: MovimientoStock) -> Self {
+        Self {
+            id_movimiento: m.id_movimiento,
+            cantidad: m.cantidad,
+            saldo: m.saldo,
+            id_almacen: m.id_almacen,
+            saldo_almacen: m.saldo_almacen,
+            id_transferencia: m.id_transferencia,
+            motivo: m.motivo,
+            id_actor: m.id_actor,
+            id_api_key: m.id_api_key,
+            id_pedido: m.id_pedido,
+            created_at: m.created_at,
+        }
+    }
+}
+
+/// Kardex de un producto, del movimiento más reciente al más antiguo
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MovimientosStockResponseDTO {
+    pub id_producto: Uuid,
+
+    /// Stock actual del producto
+    pub stock: i32,
+
+    /// Movimientos en el rango de fechas pedido
+    pub total: i64,
+
+    pub movimientos: Vec<MovimientoStockResponseDTO>,
+
+    /// Cursor para pedir la página siguiente (null si es la última)
+    pub next_cursor: Option<String>,
+}
+
+/// Stock de un producto en un almacén
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct InventarioAlmacenDTO {
+    pub id_almacen: Uuid,
+
+    #[schema(example = "Bodega Quito")]
+    pub almacen: String,
+
+    pub ciudad: String,
+
+    /// El stock de almacenes inactivos no se ofrece en el catálogo
+    pub activo: bool,
+
+    #[schema(example = 45)]
+    pub stock: i32,
+
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<InventarioAlmacen> for InventarioAlmacenDTO {
+    fn from(i: InventarioAlmacen) -> Self {
+        Self {
+            id_almacen: i.id_almacen,
+            almacen: i.almacen,
+            ciudad: i.ciudad,
+            activo: i.activo,
+            stock: i.stock,
+            updated_at: i.updated_at,
+        }
+    }
+}
+
+/// Resultado de una transferencia entre almacenes
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransferenciaStockResponseDTO {
+    pub id_transferencia: Uuid,
+
+    /// Producto con el desglose por almacén actualizado
+    pub producto: ProductoResponseDTO,
+
+    /// Salida del origen y entrada en el destino
+    pub movimientos: Vec<MovimientoStockResponseDTO>,
+}
+
+/// Producto cuyo stock no coincide con su kardex o con sus almacenes
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DescuadreStockDTO {
+    pub id_producto: Uuid,
+    pub nombre_producto: String,
+    pub sku: Option<String>,
+
+    /// Stock registrado en el producto
+    pub stock: i32,
+
+    /// Suma de las cantidades del kardex
+    pub suma_movimientos: i64,
+
+    /// stock - suma_movimientos
+    pub diferencia: i64,
+
+    /// Cantidad de movimientos del producto
+    pub movimientos: i64,
+
+    /// Suma del stock de sus almacenes
+    pub suma_almacenes: i64,
+
+    /// Almacenes cuyo stock no coincide con sus movimientos
+    pub almacenes_descuadrados: i64,
+}
+
+impl From<DescuadreStock> for DescuadreStockDTO {
+    fn from(d: DescuadreStock) -> Self {
+        Self {
+            id_producto: d.id_producto,
+            nombre_producto: d.nombre_producto,
+            sku: d.sku,
+            stock: d.stock,
+            suma_movimientos: d.suma_movimientos,
+            diferencia: i64::from(d.stock) - d.suma_movimientos,
+            movimientos: d.movimientos,
+            suma_almacenes: d.suma_almacenes,
+            almacenes_descuadrados: d.almacenes_descuadrados,
+        }
+    }
+}
+
+/// Resultado de conciliar el stock de todos los productos con su kardex y sus almacenes
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConciliacionStockResponseDTO {
+    /// true si ningún producto tiene descuadre
+    pub conciliado: bool,
+
+    pub productos_revisados: i64,
+
+    pub descuadres: Vec<DescuadreStockDTO>,
+}
@@ -11,6 +11,9 @@ pub struct CreatePedidoDTO {
     pub id_direccion_origen: Uuid,
     pub id_direccion_destino: Uuid,
     pub monto_total: f64,
+    /// Crea el pedido aunque supere el límite de crédito (solo un admin con X-Act-As)
+    #[serde(default)]
+    pub omitir_limite_credito: bool,
 }
 
 /// DTO para actualizar estado del pedido
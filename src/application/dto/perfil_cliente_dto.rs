@@ -1,3 +1,4 @@
+use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
@@ -11,24 +12,24 @@ use crate::domain::entities::PerfilCliente;
 /// DTO para crear un perfil de cliente
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreatePerfilClienteDTO {
-    /// Documento de identidad (cédula, RUC, pasaporte)
-    #[schema(example = "1234567890")]
+    /// Documento de identidad: cédula (10 dígitos), RUC (13 dígitos) o pasaporte
+    #[schema(example = "1710034065")]
     pub documento_identidad: Option<String>,
     
-    /// Número de teléfono
-    #[schema(example = "+593999123456")]
+    /// Número de teléfono, local ecuatoriano o internacional; se guarda en E.164
+    #[schema(example = "099 912 3456")]
     pub telefono: Option<String>,
 }
 
 /// DTO para actualizar un perfil de cliente
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdatePerfilClienteDTO {
-    /// Documento de identidad (cédula, RUC, pasaporte)
-    #[schema(example = "1234567890")]
+    /// Documento de identidad: cédula (10 dígitos), RUC (13 dígitos) o pasaporte
+    #[schema(example = "1710034065")]
     pub documento_identidad: Option<String>,
     
-    /// Número de teléfono
-    #[schema(example = "+593999123456")]
+    /// Número de teléfono, local ecuatoriano o internacional; se guarda en E.164
+    #[schema(example = "099 912 3456")]
     pub telefono: Option<String>,
 }
 
@@ -48,9 +49,27 @@ pub struct PerfilClienteResponseDTO {
     /// Documento de identidad
     pub documento_identidad: Option<String>,
     
-    /// Número de teléfono
+    /// Tipo de documento detectado: cedula, ruc_persona_natural, ruc_sociedad_privada,
+    /// ruc_entidad_publica o pasaporte
+    #[schema(example = "cedula")]
+    pub tipo_documento: Option<String>,
+    
+    /// Número de teléfono en formato E.164
+    #[schema(example = "+593999123456")]
     pub telefono: Option<String>,
     
+    /// Tipo de línea: movil, fijo o fijo_o_movil
+    #[schema(example = "movil")]
+    pub tipo_telefono: Option<String>,
+    
+    /// Límite de crédito (null = sin crédito); lo configura un admin
+    #[schema(example = 5000.0)]
+    pub limite_credito: Option<f64>,
+    
+    /// Plazo de pago de las facturas, en días
+    #[schema(example = 30)]
+    pub dias_credito: Option<i32>,
+    
     /// Fecha de creación
     pub created_at: chrono::DateTime<chrono::Utc>,
     
@@ -78,7 +97,11 @@ impl From<PerfilCliente> for PerfilClienteResponseDTO {
             id_perfil: perfil.id_perfil,
             id_usuario: perfil.id_usuario,
             documento_identidad: perfil.documento_identidad,
+            tipo_documento: perfil.tipo_documento,
             telefono: perfil.telefono,
+            tipo_telefono: perfil.tipo_telefono,
+            limite_credito: perfil.limite_credito.and_then(|l| l.to_f64()),
+            dias_credito: perfil.dias_credito,
             created_at: perfil.created_at,
             updated_at: perfil.updated_at,
         }
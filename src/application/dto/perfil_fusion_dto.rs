@@ -0,0 +1,127 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::PerfilClienteResponseDTO;
+use crate::domain::entities::{PerfilDuplicado, PerfilFusion};
+
+// ============================================================================
+// REQUEST DTOs
+// ============================================================================
+
+/// DTO para fusionar un perfil duplicado en el perfil de la ruta (admin)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FusionarPerfilDTO {
+    /// Perfil que se absorbe y elimina
+    pub id_perfil_origen: Uuid,
+
+    /// Rol (owner, shipper, viewer) con el que el usuario del origen queda como
+    /// miembro del perfil resultante; null = pierde el acceso
+    #[schema(example = "owner")]
+    pub rol_miembro_origen: Option<String>,
+}
+
+// ============================================================================
+// RESPONSE DTOs
+// ============================================================================
+
+/// Perfil dentro de un grupo de posibles duplicados
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PerfilDuplicadoDTO {
+    pub id_perfil: Uuid,
+    pub id_usuario: Uuid,
+    pub email: Option<String>,
+    pub documento_identidad: Option<String>,
+    pub telefono: Option<String>,
+    pub direcciones: i64,
+    pub pedidos: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Perfiles que comparten un valor normalizado
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DuplicadosGrupoDTO {
+    /// documento, telefono o email
+    #[schema(example = "documento")]
+    pub criterio: String,
+
+    /// Valor normalizado en común
+    #[schema(example = "1710034065")]
+    pub clave: String,
+
+    /// Perfiles del grupo, del más antiguo al más nuevo
+    pub perfiles: Vec<PerfilDuplicadoDTO>,
+}
+
+/// DTO de respuesta para la búsqueda de duplicados
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DuplicadosResponseDTO {
+    pub grupos: Vec<DuplicadosGrupoDTO>,
+    pub total: usize,
+}
+
+/// Registro de una fusión de perfiles
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PerfilFusionResponseDTO {
+    pub id_perfil_origen: Uuid,
+    pub id_perfil_destino: Uuid,
+    pub id_usuario_origen: Option<Uuid>,
+    pub documento_origen: Option<String>,
+    pub telefono_origen: Option<String>,
+    pub direcciones_movidas: i32,
+    pub pedidos_movidos: i32,
+    pub miembros_movidos: i32,
+    pub fusionado_por: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO de respuesta de una fusión: perfil resultante y registro
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FusionResponseDTO {
+    pub perfil: PerfilClienteResponseDTO,
+    pub fusion: PerfilFusionResponseDTO,
+}
+
+/// DTO de respuesta para la lista de fusiones
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FusionesListResponseDTO {
+    pub fusiones: Vec<PerfilFusionResponseDTO>,
+    pub total: usize,
+}
+
+// ============================================================================
+// CONVERSIONES
+// ============================================================================
+
+impl From<PerfilDuplicado> for PerfilDuplicadoDTO {
+    fn from(duplicado: PerfilDuplicado) -> Self {
+        Self {
+            id_perfil: duplicado.id_perfil,
+            id_usuario: duplicado.id_usuario,
+            email: duplicado.email,
+            documento_identidad: duplicado.documento_identidad,
+            telefono: duplicado.telefono,
+            direcciones: duplicado.direcciones,
+            pedidos: duplicado.pedidos,
+            created_at: duplicado.created_at,
+        }
+    }
+}
+
+impl From<PerfilFusion> for PerfilFusionResponseDTO {
+    fn from(fusion: PerfilFusion) -> Self {
+        Self {
+            id_perfil_origen: fusion.id_perfil_origen,
+            id_perfil_destino: fusion.id_perfil_destino,
+            id_usuario_origen: fusion.id_usuario_origen,
+            documento_origen: fusion.documento_origen,
+            telefono_origen: fusion.telefono_origen,
+            direcciones_movidas: fusion.direcciones_movidas,
+            pedidos_movidos: fusion.pedidos_movidos,
+            miembros_movidos: fusion.miembros_movidos,
+            fusionado_por: fusion.fusionado_por,
+            created_at: fusion.created_at,
+        }
+    }
+}
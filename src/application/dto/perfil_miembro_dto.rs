@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::PerfilClienteResponseDTO;
+use crate::domain::entities::PerfilMiembro;
+
+// ============================================================================
+// REQUEST DTOs
+// ============================================================================
+
+/// DTO para agregar un miembro a un perfil de empresa
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddMiembroDTO {
+    /// Email del usuario registrado que se agrega
+    #[schema(example = "logistica@empresa.com")]
+    pub email: String,
+
+    /// Rol en el perfil: owner, shipper o viewer
+    #[schema(example = "shipper")]
+    pub rol: String,
+}
+
+/// DTO para cambiar el rol de un miembro
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateMiembroRolDTO {
+    /// Nuevo rol: owner, shipper o viewer
+    #[schema(example = "viewer")]
+    pub rol: String,
+}
+
+// ============================================================================
+// RESPONSE DTOs
+// ============================================================================
+
+/// DTO de respuesta para un miembro de un perfil
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MiembroResponseDTO {
+    pub id_usuario: Uuid,
+    pub email: Option<String>,
+    pub nombre: Option<String>,
+    pub apellido: Option<String>,
+
+    /// owner, shipper o viewer
+    pub rol: String,
+
+    /// true para el titular del perfil (no se puede quitar ni cambiar de rol)
+    pub titular: bool,
+
+    /// Fecha en que se agregó (creación del perfil para el titular)
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO de respuesta para la lista de miembros de un perfil
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MiembrosListResponseDTO {
+    pub id_perfil: Uuid,
+
+    /// Titular primero, luego los miembros por fecha de alta
+    pub miembros: Vec<MiembroResponseDTO>,
+
+    pub total: usize,
+}
+
+/// Perfil con el que el usuario puede operar y su rol en él
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PerfilAccesibleDTO {
+    pub perfil: PerfilClienteResponseDTO,
+
+    /// owner, shipper o viewer
+    pub rol: String,
+
+    /// true si es el perfil propio del usuario
+    pub titular: bool,
+}
+
+/// DTO de respuesta para los perfiles accesibles del usuario
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PerfilesAccesiblesResponseDTO {
+    pub perfiles: Vec<PerfilAccesibleDTO>,
+    pub total: usize,
+}
+
+// ============================================================================
+// CONVERSIONES (From impls)
+// ============================================================================
+
+impl From<PerfilMiembro> for MiembroResponseDTO {
+    fn from(miembro: PerfilMiembro) -> Self {
+        Self {
+            id_usuario: miembro.id_usuario,
+            email: miembro.email,
+            nombre: miembro.nombre,
+            apellido: miembro.apellido,
+            rol: miembro.rol,
+            titular: false,
+            created_at: miembro.created_at,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{DireccionResponseDTO, PedidoResponseDTO, PerfilClienteResponseDTO, UserResponseDTO};
+
+// ============================================================================
+// RESPONSE DTOs
+// ============================================================================
+
+/// Archivo con todos los datos personales de un usuario
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PersonalDataExportDTO {
+    /// Fecha de generación del archivo
+    pub generado_en: DateTime<Utc>,
+
+    pub usuario: UserResponseDTO,
+
+    /// Perfil de cliente (null si el usuario no tiene)
+    pub perfil: Option<PerfilClienteResponseDTO>,
+
+    /// Direcciones del perfil, incluidas las inactivas
+    pub direcciones: Vec<DireccionResponseDTO>,
+
+    /// Pedidos del perfil
+    pub pedidos: Vec<PedidoResponseDTO>,
+}
+
+/// Resultado de anonimizar un usuario
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnonymizationResponseDTO {
+    pub id_usuario: Uuid,
+
+    /// Perfiles de cliente anonimizados
+    pub perfiles_anonimizados: u64,
+
+    /// Direcciones anonimizadas
+    pub direcciones_anonimizadas: u64,
+
+    pub anonimizado_en: DateTime<Utc>,
+}
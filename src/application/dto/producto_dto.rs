@@ -1,8 +1,12 @@
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::domain::entities::Producto;
+use crate::domain::entities::{AlmacenCercano, Producto, ProductoBusqueda, FIN_RESALTADO, INICIO_RESALTADO};
+
+use super::InventarioAlmacenDTO;
 
 // ============================================================================
 // REQUEST DTOs
@@ -26,9 +30,15 @@ pub struct CreateProductoDTO {
     /// Stock inicial (debe ser >= 0)
     #[schema(example = 50)]
     pub stock: Option<i32>,
+
+    /// Almacén que recibe el stock inicial (requerido si `stock` > 0)
+    pub id_almacen: Option<Uuid>,
     
     /// Categoría del producto
-    #[schema(example = "Electrónicos")]
+    pub id_categoria: Option<Uuid>,
+
+    /// Slug, nombre o texto libre anterior de la categoría (si no se envía `id_categoria`)
+    #[schema(example = "electronicos")]
     pub categoria: Option<String>,
     
     /// Código SKU único
@@ -50,7 +60,12 @@ pub struct UpdateProductoDTO {
     #[schema(example = 1499.99)]
     pub precio: Option<f64>,
     
-    /// Categoría
+    /// Categoría; `null` explícito deja el producto sin categoría
+    #[serde(default, deserialize_with = "super::categoria_dto::presente")]
+    #[schema(value_type = Option<Uuid>)]
+    pub id_categoria: Option<Option<Uuid>>,
+
+    /// Slug, nombre o texto libre anterior de la categoría (si no se envía `id_categoria`)
     pub categoria: Option<String>,
     
     /// Código SKU
@@ -60,13 +75,19 @@ pub struct UpdateProductoDTO {
 /// DTO para actualizar stock
 #[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct UpdateStockDTO {
+    /// Almacén donde entra o sale el stock (requerido)
+    pub id_almacen: Uuid,
+
     /// Cantidad a agregar (positivo) o restar (negativo)
     #[schema(example = 10)]
     pub cantidad: i32,
     
-    /// Motivo del ajuste
+    /// Motivo del ajuste (requerido; queda en el kardex)
     #[schema(example = "Recepción de inventario")]
     pub motivo: Option<String>,
+
+    /// Pedido relacionado con el movimiento
+    pub id_pedido: Option<Uuid>,
 }
 
 /// DTO para cambiar estado del producto
@@ -97,11 +118,21 @@ pub struct ProductoResponseDTO {
     #[schema(example = 1299.99)]
     pub precio: f64,
     
-    /// Stock disponible
+    /// Stock total (suma de todos los almacenes)
     pub stock: i32,
+
+    /// Unidades disponibles para el cliente: suma de los almacenes activos o,
+    /// con `disponibilidad=cercano`, las del almacén más cercano (solo catálogo)
+    pub disponible: Option<i64>,
+
+    /// Stock por almacén (solo en respuestas de administración)
+    pub inventario: Option<Vec<InventarioAlmacenDTO>>,
     
-    /// Categoría
+    /// Nombre de la categoría
     pub categoria: Option<String>,
+
+    /// Categoría
+    pub id_categoria: Option<Uuid>,
     
     /// Código SKU
     pub sku: Option<String>,
@@ -122,7 +153,144 @@ pub struct ProductosListResponseDTO {
     /// Lista de productos
     pub productos: Vec<ProductoResponseDTO>,
     
-    /// Total de productos
+    /// Total de productos que cumplen los filtros (no solo los de esta página)
+    pub total: i64,
+
+    /// Datos de paginación (solo en el catálogo paginado)
+    pub paginacion: Option<PaginacionDTO>,
+
+    /// Productos por categoría con los mismos filtros, excepto `categoria`
+    pub categorias: Vec<CategoriaFacetaDTO>,
+
+    /// Almacén usado para la disponibilidad (solo con `disponibilidad=cercano`)
+    pub almacen_cercano: Option<AlmacenCercanoDTO>,
+}
+
+/// Almacén activo más cercano a las coordenadas del cliente
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AlmacenCercanoDTO {
+    pub id_almacen: Uuid,
+
+    #[schema(example = "Bodega Guayaquil")]
+    pub almacen: String,
+
+    pub ciudad: String,
+
+    /// Distancia en línea recta
+    #[schema(example = 3.2)]
+    pub distancia_km: f64,
+}
+
+/// Paginación del catálogo
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginacionDTO {
+    /// Tamaño de página aplicado
+    pub limit: i64,
+
+    /// Página actual (solo si se pidió por número de página)
+    pub page: Option<i64>,
+
+    /// Cantidad de páginas con el `limit` aplicado
+    pub total_pages: i64,
+
+    /// Cursor para pedir la página siguiente (null si es la última)
+    pub next_cursor: Option<String>,
+}
+
+/// Conteo de productos de una categoría (sin contar sus subcategorías)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CategoriaFacetaDTO {
+    /// null = productos sin categoría
+    pub id_categoria: Option<Uuid>,
+
+    /// Valor para el filtro `categoria`
+    pub slug: Option<String>,
+
+    /// Nombre de la categoría
+    pub categoria: Option<String>,
+
+    pub total: i64,
+}
+
+/// Parámetros de `GET /api/productos` y `GET /api/productos/categoria/{categoria}`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProductosQueryDTO {
+    /// Slugs de categorías separados por coma (incluye sus subcategorías)
+    pub categoria: Option<String>,
+
+    /// Precio mínimo (inclusive)
+    pub precio_min: Option<Decimal>,
+
+    /// Precio máximo (inclusive)
+    pub precio_max: Option<Decimal>,
+
+    /// Solo productos con stock disponible
+    pub en_stock: Option<bool>,
+
+    /// precio | nombre | created_at (default: nombre)
+    pub sort: Option<String>,
+
+    /// asc | desc (default: asc)
+    pub order: Option<String>,
+
+    /// Tamaño de página (default: 20, máximo: 100)
+    pub limit: Option<i64>,
+
+    /// Número de página desde 1 (alternativa a `cursor`)
+    pub page: Option<i64>,
+
+    /// Cursor opaco devuelto en `paginacion.next_cursor`
+    pub cursor: Option<String>,
+
+    /// total | cercano (default: total). Con `cercano` se requieren `lat` y `lng`
+    pub disponibilidad: Option<String>,
+
+    /// Latitud del cliente
+    pub lat: Option<f64>,
+
+    /// Longitud del cliente
+    pub lng: Option<f64>,
+}
+
+/// Estrategia que produjo los resultados de búsqueda
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ModoBusqueda {
+    /// Texto completo y prefijo de SKU
+    Texto,
+    /// Similitud de trigramas (sin coincidencias de texto)
+    Similitud,
+}
+
+/// DTO de un resultado de búsqueda
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ProductoBusquedaResponseDTO {
+    pub producto: ProductoResponseDTO,
+
+    /// Relevancia (mayor es mejor)
+    #[schema(example = 0.61)]
+    pub score: f64,
+
+    /// Nombre en HTML escapado con los términos entre `<mark>`
+    #[schema(example = "<mark>Teléfono</mark> Samsung Galaxy A54")]
+    pub nombre_resaltado: String,
+
+    /// Fragmentos de la descripción en HTML escapado con los términos entre `<mark>`
+    pub descripcion_resaltada: Option<String>,
+}
+
+/// DTO de respuesta de `GET /api/productos/buscar`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ProductosBusquedaResponseDTO {
+    /// Término buscado
+    pub q: String,
+
+    pub modo: ModoBusqueda,
+
+    /// Resultados ordenados por relevancia
+    pub productos: Vec<ProductoBusquedaResponseDTO>,
+
+    /// Cantidad de resultados devueltos
     pub total: usize,
 }
 
@@ -140,20 +308,128 @@ impl From<Producto> for ProductoResponseDTO {
             precio: p.precio.to_f64().unwrap_or(0.0),
             stock: p.stock,
             categoria: p.categoria,
+            id_categoria: p.id_categoria,
             sku: p.sku,
             estado: p.estado,
             created_at: p.created_at,
             updated_at: p.updated_at,
+            disponible: None,
+            inventario: None,
         }
     }
 }
 
 impl From<Vec<Producto>> for ProductosListResponseDTO {
     fn from(productos: Vec<Producto>) -> Self {
-        let total = productos.len();
+        let mut conteo: BTreeMap<(Option<String>, Option<Uuid>), i64> = BTreeMap::new();
+        for p in &productos {
+            *conteo.entry((p.categoria.clone(), p.id_categoria)).or_default() += 1;
+        }
+        // Igual que en el catálogo paginado: por nombre y "sin categoría" al final
+        let sin_categoria = conteo.remove(&(None, None));
+        let categorias = conteo
+            .into_iter()
+            .chain(sin_categoria.map(|total| ((None, None), total)))
+            .map(|((categoria, id_categoria), total)| CategoriaFacetaDTO {
+                id_categoria,
+                slug: None,
+                categoria,
+                total,
+            })
+            .collect();
+
         Self {
+            total: productos.len() as i64,
             productos: productos.into_iter().map(Into::into).collect(),
-            total,
+            paginacion: None,
+            categorias,
+            almacen_cercano: None,
+        }
+    }
+}
+
+impl From<AlmacenCercano> for AlmacenCercanoDTO {
+    fn from(a: AlmacenCercano) -> Self {
+        Self {
+            id_almacen: a.id_almacen,
+            almacen: a.calle,
+            ciudad: a.ciudad,
+            distancia_km: a.distancia_km,
         }
     }
 }
+
+impl From<ProductoBusqueda> for ProductoBusquedaResponseDTO {
+    fn from(b: ProductoBusqueda) -> Self {
+        Self {
+            producto: b.producto.into(),
+            score: b.score,
+            nombre_resaltado: resaltado_html(&b.nombre_resaltado),
+            descripcion_resaltada: b.descripcion_resaltada.as_deref().map(resaltado_html),
+        }
+    }
+}
+
+/// Escapa el texto como HTML y convierte las marcas de resaltado en `<mark>`
+fn resaltado_html(texto: &str) -> String {
+    let mut html = String::with_capacity(texto.len() + 16);
+    for c in texto.chars() {
+        match c {
+            INICIO_RESALTADO => html.push_str("<mark>"),
+            FIN_RESALTADO => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marcado(texto: &str) -> String {
+        texto.replace('[', &INICIO_RESALTADO.to_string()).replace(']', &FIN_RESALTADO.to_string())
+    }
+
+    #[test]
+    fn convierte_las_marcas_en_mark() {
+        assert_eq!(resaltado_html(&marcado("Cable [USB] tipo [C]")), "Cable <mark>USB</mark> tipo <mark>C</mark>");
+    }
+
+    #[test]
+    fn escapa_el_html_del_texto() {
+        assert_eq!(
+            resaltado_html(&marcado("<script>alert(\"x\")</script> & [O'Brien]")),
+            "&lt;script&gt;alert(&quot;x&quot;)&lt;/script&gt; &amp; <mark>O&#39;Brien</mark>"
+        );
+    }
+
+    #[test]
+    fn no_interpreta_etiquetas_mark_del_texto() {
+        assert_eq!(resaltado_html("<mark>falso</mark>"), "&lt;mark&gt;falso&lt;/mark&gt;");
+    }
+
+    #[test]
+    fn conserva_acentos_y_texto_sin_marcas() {
+        assert_eq!(resaltado_html("Teléfono ñandú"), "Teléfono ñandú");
+        assert_eq!(resaltado_html(""), "");
+    }
+
+    #[test]
+    fn id_categoria_distingue_ausente_null_y_valor() {
+        let ausente: UpdateProductoDTO = serde_json::from_str(r#"{"nombre_producto": "Cable"}"#).unwrap();
+        assert_eq!(ausente.id_categoria, None);
+
+        let null: UpdateProductoDTO = serde_json::from_str(r#"{"id_categoria": null}"#).unwrap();
+        assert_eq!(null.id_categoria, Some(None));
+
+        let id = Uuid::new_v4();
+        let valor: UpdateProductoDTO = serde_json::from_str(&format!(r#"{{"id_categoria": "{}"}}"#, id)).unwrap();
+        assert_eq!(valor.id_categoria, Some(Some(id)));
+    }
+}
@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 use utoipa::ToSchema;
 use chrono::{DateTime, Utc};
 
+use crate::domain::auth::ROL_CLIENTE;
+
 // ============================================================================
 // CREATE USER DTO
 // ============================================================================
@@ -24,7 +27,7 @@ pub struct CreateUserDTO {
 }
 
 fn default_role() -> String {
-    "cliente".to_string()
+    ROL_CLIENTE.to_string()
 }
 
 // ============================================================================
@@ -109,9 +112,107 @@ pub struct UserResponseDTO {
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UsersListResponseDTO {
-    /// Total de usuarios
+    /// Total de usuarios que cumplen los filtros
     pub total: i64,
     
     /// Lista de usuarios
     pub users: Vec<UserResponseDTO>,
+
+    /// Cursor para pedir la página siguiente (null si es la última)
+    pub next_cursor: Option<String>,
+
+    /// Usuarios por rol con los mismos filtros, excepto `rol` (pestañas del dashboard)
+    pub counts_por_rol: BTreeMap<String, i64>,
+}
+
+// ============================================================================
+// USERS QUERY DTO
+// ============================================================================
+
+/// Parámetros de `GET /api/admin/users`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UsersQueryDTO {
+    /// Filtrar por rol (cliente, transportista, admin)
+    pub rol: Option<String>,
+
+    /// Filtrar por estado
+    pub activo: Option<bool>,
+
+    /// Creados desde (RFC 3339, inclusive)
+    pub created_desde: Option<DateTime<Utc>>,
+
+    /// Creados hasta (RFC 3339, inclusive)
+    pub created_hasta: Option<DateTime<Utc>>,
+
+    /// Subcadena de email, nombre o apellido
+    pub q: Option<String>,
+
+    /// Columna de orden (default: created_at)
+    pub sort: Option<String>,
+
+    /// asc | desc (default: desc)
+    pub order: Option<String>,
+
+    /// Tamaño de página (default: 20, máximo: 100)
+    pub limit: Option<i64>,
+
+    /// Cursor opaco devuelto en `next_cursor`
+    pub cursor: Option<String>,
+}
+
+// ============================================================================
+// IMPORTACIÓN CSV
+// ============================================================================
+
+/// Parámetros de `POST /api/admin/users/import`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UserImportQueryDTO {
+    /// `dry_run` (default) solo valida; `commit` crea los usuarios válidos
+    pub mode: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UserImportEstado {
+    /// Fila válida (dry run: se crearía)
+    Valido,
+    /// Usuario creado (commit)
+    Creado,
+    /// Fila rechazada; ver `error`
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserImportRowDTO {
+    /// Número de línea en el archivo (la cabecera es la línea 1)
+    pub fila: u64,
+
+    /// Email leído de la fila
+    pub email: Option<String>,
+
+    pub estado: UserImportEstado,
+
+    /// ID del usuario creado (solo en commit)
+    pub id: Option<Uuid>,
+
+    /// Motivo del rechazo
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserImportReportDTO {
+    /// true si no se creó ningún usuario (modo dry_run)
+    pub dry_run: bool,
+
+    /// Filas de datos procesadas
+    pub total: usize,
+
+    /// Filas válidas (en commit, las que se crearon)
+    pub validos: usize,
+
+    /// Filas rechazadas
+    pub errores: usize,
+
+    /// Resultado por fila
+    pub filas: Vec<UserImportRowDTO>,
 }
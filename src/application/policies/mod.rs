@@ -0,0 +1,5 @@
+pub mod pedido_policy;
+pub mod perfil_policy;
+
+pub use pedido_policy::{PedidoAccion, PedidoPolicy, Solicitante};
+pub use perfil_policy::{PerfilAccion, PerfilPolicy, RolMiembro};
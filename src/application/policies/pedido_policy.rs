@@ -0,0 +1,341 @@
+use uuid::Uuid;
+
+use crate::application::policies::{PerfilAccion, PerfilPolicy, RolMiembro};
+use crate::domain::auth::{ApiKeyPrincipal, ROL_ADMIN, SCOPE_PEDIDOS_ESTADO, SCOPE_PEDIDOS_READ};
+use crate::domain::entities::Pedido;
+use crate::shared::{AppError, AppResult};
+
+/// Acciones sobre un pedido sujetas a autorización
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PedidoAccion {
+    Ver,
+    ActualizarEstado,
+    AsignarTransportista,
+    Cancelar,
+}
+
+impl PedidoAccion {
+    /// Las lecturas responden 404 ante falta de acceso para no revelar qué IDs existen
+    fn es_lectura(self) -> bool {
+        matches!(self, PedidoAccion::Ver)
+    }
+}
+
+/// Rol asignado a las API keys de servicio dentro de `Solicitante`
+const ROL_API_KEY: &str = "api_key";
+
+/// Identidad de quien realiza la operación, resuelta desde public.users
+#[derive(Debug, Clone)]
+pub struct Solicitante {
+    /// ID del usuario (o de la API key)
+    pub id_usuario: Uuid,
+    /// Rol de aplicación (cliente, transportista, admin)
+    pub rol: String,
+    /// Perfil de cliente del usuario, si tiene uno
+    pub id_perfil: Option<Uuid>,
+    /// Perfiles de empresa de los que es miembro, con su rol
+    pub membresias: Vec<(Uuid, RolMiembro)>,
+    /// Registro de transportista del usuario, si tiene uno
+    pub id_transportista: Option<Uuid>,
+    /// Scopes de la API key; `None` para usuarios
+    pub scopes: Option<Vec<String>>,
+}
+
+impl Solicitante {
+    /// Solicitante para una API key de servicio
+    pub fn api_key(api_key: &ApiKeyPrincipal) -> Self {
+        Self {
+            id_usuario: api_key.id,
+            rol: ROL_API_KEY.to_string(),
+            id_perfil: None,
+            membresias: Vec::new(),
+            id_transportista: None,
+            scopes: Some(api_key.scopes.clone()),
+        }
+    }
+
+    pub fn es_admin(&self) -> bool {
+        self.rol == ROL_ADMIN
+    }
+
+    /// Rol del solicitante en un perfil: owner si es el titular, o el de su membresía
+    pub fn rol_en_perfil(&self, id_perfil: Uuid) -> Option<RolMiembro> {
+        if self.id_perfil == Some(id_perfil) {
+            return Some(RolMiembro::Owner);
+        }
+        self.membresias
+            .iter()
+            .find(|(id, _)| *id == id_perfil)
+            .map(|(_, rol)| *rol)
+    }
+}
+
+/// Relación del solicitante con un pedido concreto
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Relacion {
+    Admin,
+    /// Titular o miembro del perfil dueño del pedido
+    Cliente(RolMiembro),
+    TransportistaAsignado,
+    ApiKey,
+    Ninguna,
+}
+
+/// Política de propiedad y visibilidad de pedidos
+///
+/// - Admin: todas las acciones
+/// - Cliente titular del perfil: ver y cancelar
+/// - Miembro del perfil: ver; cancelar si su rol gestiona pedidos (owner, shipper)
+/// - Transportista asignado: ver y actualizar estado
+/// - API key: ver con `pedidos:read`, actualizar estado con `pedidos:estado`
+/// - Cualquier otro: 404 en lecturas, 403 en escrituras
+pub struct PedidoPolicy;
+
+impl PedidoPolicy {
+    /// Verifica que el solicitante pueda ejecutar la acción sobre el pedido
+    pub fn autorizar(solicitante: &Solicitante, pedido: &Pedido, accion: PedidoAccion) -> AppResult<()> {
+        let relacion = Self::relacion(solicitante, pedido);
+
+        let permitido = match relacion {
+            Relacion::Admin => true,
+            Relacion::Cliente(rol) => match accion {
+                PedidoAccion::Ver => PerfilPolicy::permite(rol, PerfilAccion::Ver),
+                PedidoAccion::Cancelar => PerfilPolicy::permite(rol, PerfilAccion::GestionarPedidos),
+                _ => false,
+            },
+            Relacion::TransportistaAsignado => {
+                matches!(accion, PedidoAccion::Ver | PedidoAccion::ActualizarEstado)
+            }
+            Relacion::ApiKey => {
+                let scope = match accion {
+                    PedidoAccion::Ver => Some(SCOPE_PEDIDOS_READ),
+                    PedidoAccion::ActualizarEstado => Some(SCOPE_PEDIDOS_ESTADO),
+                    _ => None,
+                };
+                match (scope, &solicitante.scopes) {
+                    (Some(scope), Some(scopes)) => scopes.iter().any(|s| s == scope),
+                    _ => false,
+                }
+            }
+            Relacion::Ninguna => false,
+        };
+
+        if permitido {
+            return Ok(());
+        }
+
+        if accion.es_lectura() {
+            Err(AppError::NotFound(format!("Pedido {} no encontrado", pedido.id_pedido)))
+        } else {
+            Err(AppError::Forbidden("No tiene permisos para esta acción sobre el pedido".to_string()))
+        }
+    }
+
+    fn relacion(solicitante: &Solicitante, pedido: &Pedido) -> Relacion {
+        if solicitante.scopes.is_some() {
+            Relacion::ApiKey
+        } else if solicitante.es_admin() {
+            Relacion::Admin
+        } else if let Some(rol) = solicitante.rol_en_perfil(pedido.id_perfil) {
+            Relacion::Cliente(rol)
+        } else if solicitante.id_transportista.is_some()
+            && solicitante.id_transportista == pedido.id_transportista
+        {
+            Relacion::TransportistaAsignado
+        } else {
+            Relacion::Ninguna
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::auth::{ROL_CLIENTE, ROL_TRANSPORTISTA};
+
+    const TODAS: [PedidoAccion; 4] = [
+        PedidoAccion::Ver,
+        PedidoAccion::ActualizarEstado,
+        PedidoAccion::AsignarTransportista,
+        PedidoAccion::Cancelar,
+    ];
+
+    fn pedido(id_perfil: Uuid, id_transportista: Option<Uuid>) -> Pedido {
+        Pedido {
+            id_pedido: Uuid::new_v4(),
+            numero_tracking: "TRK-00000001".to_string(),
+            id_perfil,
+            id_transportista,
+            id_direccion_origen: Uuid::new_v4(),
+            id_direccion_destino: Uuid::new_v4(),
+            estado: "pendiente".to_string(),
+            fecha_entrega_estimada: None,
+            fecha_entrega_real: None,
+            monto_total: 10.0,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn solicitante(rol: &str, id_perfil: Option<Uuid>, id_transportista: Option<Uuid>) -> Solicitante {
+        Solicitante {
+            id_usuario: Uuid::new_v4(),
+            rol: rol.to_string(),
+            id_perfil,
+            membresias: Vec::new(),
+            id_transportista,
+            scopes: None,
+        }
+    }
+
+    #[test]
+    fn admin_puede_todo() {
+        let admin = solicitante(ROL_ADMIN, None, None);
+        let p = pedido(Uuid::new_v4(), Some(Uuid::new_v4()));
+
+        for accion in TODAS {
+            assert!(PedidoPolicy::autorizar(&admin, &p, accion).is_ok(), "{:?}", accion);
+        }
+    }
+
+    #[test]
+    fn cliente_propietario_ve_y_cancela() {
+        let id_perfil = Uuid::new_v4();
+        let cliente = solicitante(ROL_CLIENTE, Some(id_perfil), None);
+        let p = pedido(id_perfil, None);
+
+        assert!(PedidoPolicy::autorizar(&cliente, &p, PedidoAccion::Ver).is_ok());
+        assert!(PedidoPolicy::autorizar(&cliente, &p, PedidoAccion::Cancelar).is_ok());
+        assert!(matches!(
+            PedidoPolicy::autorizar(&cliente, &p, PedidoAccion::ActualizarEstado),
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(matches!(
+            PedidoPolicy::autorizar(&cliente, &p, PedidoAccion::AsignarTransportista),
+            Err(AppError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn cliente_ajeno_recibe_404_en_lectura_y_403_en_escritura() {
+        let cliente = solicitante(ROL_CLIENTE, Some(Uuid::new_v4()), None);
+        let p = pedido(Uuid::new_v4(), None);
+
+        assert!(matches!(
+            PedidoPolicy::autorizar(&cliente, &p, PedidoAccion::Ver),
+            Err(AppError::NotFound(_))
+        ));
+        for accion in [PedidoAccion::ActualizarEstado, PedidoAccion::AsignarTransportista, PedidoAccion::Cancelar] {
+            assert!(matches!(
+                PedidoPolicy::autorizar(&cliente, &p, accion),
+                Err(AppError::Forbidden(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn cliente_sin_perfil_no_accede() {
+        let cliente = solicitante(ROL_CLIENTE, None, None);
+        let p = pedido(Uuid::new_v4(), None);
+
+        assert!(matches!(
+            PedidoPolicy::autorizar(&cliente, &p, PedidoAccion::Ver),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn miembro_del_perfil_accede_segun_su_rol() {
+        let id_perfil = Uuid::new_v4();
+        let p = pedido(id_perfil, None);
+
+        let mut shipper = solicitante(ROL_CLIENTE, Some(Uuid::new_v4()), None);
+        shipper.membresias.push((id_perfil, RolMiembro::Shipper));
+        assert!(PedidoPolicy::autorizar(&shipper, &p, PedidoAccion::Ver).is_ok());
+        assert!(PedidoPolicy::autorizar(&shipper, &p, PedidoAccion::Cancelar).is_ok());
+        assert!(matches!(
+            PedidoPolicy::autorizar(&shipper, &p, PedidoAccion::ActualizarEstado),
+            Err(AppError::Forbidden(_))
+        ));
+
+        let mut viewer = solicitante(ROL_CLIENTE, None, None);
+        viewer.membresias.push((id_perfil, RolMiembro::Viewer));
+        assert!(PedidoPolicy::autorizar(&viewer, &p, PedidoAccion::Ver).is_ok());
+        assert!(matches!(
+            PedidoPolicy::autorizar(&viewer, &p, PedidoAccion::Cancelar),
+            Err(AppError::Forbidden(_))
+        ));
+
+        let mut de_otro_perfil = solicitante(ROL_CLIENTE, None, None);
+        de_otro_perfil.membresias.push((Uuid::new_v4(), RolMiembro::Owner));
+        assert!(matches!(
+            PedidoPolicy::autorizar(&de_otro_perfil, &p, PedidoAccion::Ver),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn transportista_asignado_ve_y_actualiza_estado() {
+        let id_transportista = Uuid::new_v4();
+        let transportista = solicitante(ROL_TRANSPORTISTA, None, Some(id_transportista));
+        let p = pedido(Uuid::new_v4(), Some(id_transportista));
+
+        assert!(PedidoPolicy::autorizar(&transportista, &p, PedidoAccion::Ver).is_ok());
+        assert!(PedidoPolicy::autorizar(&transportista, &p, PedidoAccion::ActualizarEstado).is_ok());
+        assert!(matches!(
+            PedidoPolicy::autorizar(&transportista, &p, PedidoAccion::Cancelar),
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(matches!(
+            PedidoPolicy::autorizar(&transportista, &p, PedidoAccion::AsignarTransportista),
+            Err(AppError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn transportista_no_asignado_no_accede() {
+        let transportista = solicitante(ROL_TRANSPORTISTA, None, Some(Uuid::new_v4()));
+        let sin_asignar = pedido(Uuid::new_v4(), None);
+        let de_otro = pedido(Uuid::new_v4(), Some(Uuid::new_v4()));
+
+        for p in [&sin_asignar, &de_otro] {
+            assert!(matches!(
+                PedidoPolicy::autorizar(&transportista, p, PedidoAccion::Ver),
+                Err(AppError::NotFound(_))
+            ));
+            assert!(matches!(
+                PedidoPolicy::autorizar(&transportista, p, PedidoAccion::ActualizarEstado),
+                Err(AppError::Forbidden(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn api_key_accede_segun_scopes() {
+        let p = pedido(Uuid::new_v4(), Some(Uuid::new_v4()));
+        let lector = Solicitante::api_key(&ApiKeyPrincipal {
+            id: Uuid::new_v4(),
+            nombre: "escaner".to_string(),
+            scopes: vec![SCOPE_PEDIDOS_READ.to_string()],
+        });
+
+        assert!(PedidoPolicy::autorizar(&lector, &p, PedidoAccion::Ver).is_ok());
+        assert!(matches!(
+            PedidoPolicy::autorizar(&lector, &p, PedidoAccion::ActualizarEstado),
+            Err(AppError::Forbidden(_))
+        ));
+
+        let sin_scopes = Solicitante::api_key(&ApiKeyPrincipal {
+            id: Uuid::new_v4(),
+            nombre: "integracion".to_string(),
+            scopes: vec![],
+        });
+        assert!(matches!(
+            PedidoPolicy::autorizar(&sin_scopes, &p, PedidoAccion::Ver),
+            Err(AppError::NotFound(_))
+        ));
+        for accion in [PedidoAccion::AsignarTransportista, PedidoAccion::Cancelar] {
+            assert!(PedidoPolicy::autorizar(&lector, &p, accion).is_err());
+        }
+    }
+}
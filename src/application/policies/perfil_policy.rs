@@ -0,0 +1,126 @@
+use crate::shared::{AppError, AppResult};
+
+/// Rol de un usuario dentro de un perfil de cliente compartido
+///
+/// El titular del perfil (`perfiles_cliente.id_usuario`) siempre es `Owner`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RolMiembro {
+    Owner,
+    Shipper,
+    Viewer,
+}
+
+impl RolMiembro {
+    pub const TODOS: [RolMiembro; 3] = [RolMiembro::Owner, RolMiembro::Shipper, RolMiembro::Viewer];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::TODOS.into_iter().find(|rol| rol.as_str() == value)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RolMiembro::Owner => "owner",
+            RolMiembro::Shipper => "shipper",
+            RolMiembro::Viewer => "viewer",
+        }
+    }
+}
+
+/// Acciones sobre un perfil compartido sujetas a autorización
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerfilAccion {
+    /// Ver perfil, direcciones y pedidos
+    Ver,
+    /// Crear, editar, activar/desactivar direcciones y elegir la predeterminada
+    EditarDirecciones,
+    /// Borrar direcciones definitivamente
+    EliminarDirecciones,
+    /// Crear y cancelar pedidos
+    GestionarPedidos,
+    /// Agregar, cambiar de rol y quitar miembros
+    GestionarMiembros,
+}
+
+/// Política de acceso de los miembros a un perfil de cliente
+///
+/// - owner: todas las acciones
+/// - shipper: ver, editar direcciones y gestionar pedidos
+/// - viewer: solo ver
+pub struct PerfilPolicy;
+
+impl PerfilPolicy {
+    pub fn permite(rol: RolMiembro, accion: PerfilAccion) -> bool {
+        match rol {
+            RolMiembro::Owner => true,
+            RolMiembro::Shipper => matches!(
+                accion,
+                PerfilAccion::Ver | PerfilAccion::EditarDirecciones | PerfilAccion::GestionarPedidos
+            ),
+            RolMiembro::Viewer => accion == PerfilAccion::Ver,
+        }
+    }
+
+    /// Verifica que el rol permita la acción (403 si no)
+    pub fn autorizar(rol: RolMiembro, accion: PerfilAccion) -> AppResult<()> {
+        if Self::permite(rol, accion) {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden(format!(
+                "El rol {} no permite esta acción sobre el perfil",
+                rol.as_str()
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TODAS: [PerfilAccion; 5] = [
+        PerfilAccion::Ver,
+        PerfilAccion::EditarDirecciones,
+        PerfilAccion::EliminarDirecciones,
+        PerfilAccion::GestionarPedidos,
+        PerfilAccion::GestionarMiembros,
+    ];
+
+    #[test]
+    fn owner_puede_todo() {
+        for accion in TODAS {
+            assert!(PerfilPolicy::autorizar(RolMiembro::Owner, accion).is_ok(), "{:?}", accion);
+        }
+    }
+
+    #[test]
+    fn shipper_gestiona_direcciones_y_pedidos_pero_no_miembros() {
+        for accion in [PerfilAccion::Ver, PerfilAccion::EditarDirecciones, PerfilAccion::GestionarPedidos] {
+            assert!(PerfilPolicy::autorizar(RolMiembro::Shipper, accion).is_ok(), "{:?}", accion);
+        }
+        for accion in [PerfilAccion::EliminarDirecciones, PerfilAccion::GestionarMiembros] {
+            assert!(matches!(
+                PerfilPolicy::autorizar(RolMiembro::Shipper, accion),
+                Err(AppError::Forbidden(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn viewer_solo_ve() {
+        assert!(PerfilPolicy::autorizar(RolMiembro::Viewer, PerfilAccion::Ver).is_ok());
+        for accion in &TODAS[1..] {
+            assert!(matches!(
+                PerfilPolicy::autorizar(RolMiembro::Viewer, *accion),
+                Err(AppError::Forbidden(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn parse_roles() {
+        for rol in RolMiembro::TODOS {
+            assert_eq!(RolMiembro::parse(rol.as_str()), Some(rol));
+        }
+        assert_eq!(RolMiembro::parse("admin"), None);
+    }
+}
@@ -0,0 +1,173 @@
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    ApiKeyCreatedResponseDTO, ApiKeyResponseDTO, ApiKeysListResponseDTO, CreateApiKeyDTO,
+};
+use crate::domain::auth::{ApiKeyPrincipal, SCOPES_VALIDOS};
+use crate::domain::entities::ApiKey;
+use crate::domain::repositories::ApiKeyRepository;
+use crate::shared::error::{AppError, AppResult};
+
+/// Prefijo fijo de todas las keys, facilita detectarlas en logs o repositorios
+const API_KEY_PREFIX: &str = "ck";
+
+/// Service de API keys de servicio
+///
+/// Formato de la key: `ck_<prefijo>_<secreto>`. Se busca por `prefijo` y se
+/// compara el SHA-256 de la key completa; el secreto no se guarda.
+pub struct ApiKeyService {
+    repository: Arc<dyn ApiKeyRepository>,
+}
+
+impl ApiKeyService {
+    pub fn new(repository: Arc<dyn ApiKeyRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Lista todas las API keys (solo admin)
+    pub async fn list(&self) -> AppResult<ApiKeysListResponseDTO> {
+        let api_keys = self.repository.find_all().await?;
+
+        Ok(ApiKeysListResponseDTO {
+            total: api_keys.len() as i64,
+            api_keys: api_keys.into_iter().map(ApiKeyResponseDTO::from).collect(),
+        })
+    }
+
+    /// Crea una API key (solo admin). El secreto solo se devuelve aquí.
+    pub async fn create(&self, dto: CreateApiKeyDTO, admin_id: Uuid) -> AppResult<ApiKeyCreatedResponseDTO> {
+        let nombre = dto.nombre.trim().to_string();
+        if nombre.is_empty() {
+            return Err(AppError::BadRequest("El nombre es requerido".to_string()));
+        }
+        if dto.scopes.is_empty() {
+            return Err(AppError::BadRequest("Debe indicar al menos un scope".to_string()));
+        }
+        if let Some(scope) = dto.scopes.iter().find(|s| !SCOPES_VALIDOS.contains(&s.as_str())) {
+            return Err(AppError::BadRequest(format!(
+                "Scope inválido: {}. Scopes válidos: {:?}",
+                scope, SCOPES_VALIDOS
+            )));
+        }
+        if dto.expira_en.is_some_and(|expira| expira <= chrono::Utc::now()) {
+            return Err(AppError::BadRequest("La fecha de expiración debe ser futura".to_string()));
+        }
+
+        let prefijo = random_hex(6);
+        let secret = format!("{}_{}_{}", API_KEY_PREFIX, prefijo, random_hex(32));
+
+        let mut scopes = dto.scopes;
+        scopes.sort();
+        scopes.dedup();
+
+        let api_key = ApiKey {
+            id: Uuid::new_v4(),
+            nombre,
+            prefijo,
+            hash: hash_key(&secret),
+            scopes,
+            expira_en: dto.expira_en,
+            ultimo_uso: None,
+            revocada_en: None,
+            creada_por: Some(admin_id),
+            created_at: chrono::Utc::now(),
+        };
+
+        let created = self.repository.create(&api_key).await?;
+        tracing::info!("API key {} ({}) creada por {}", created.id, created.nombre, admin_id);
+
+        Ok(ApiKeyCreatedResponseDTO {
+            secret,
+            api_key: created.into(),
+        })
+    }
+
+    /// Revoca una API key (solo admin)
+    pub async fn revoke(&self, id: Uuid) -> AppResult<()> {
+        self.repository
+            .revoke(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("API key no encontrada".to_string()))?;
+
+        tracing::info!("API key {} revocada", id);
+        Ok(())
+    }
+
+    /// Valida una key recibida en un request; `None` si no existe, no coincide,
+    /// está revocada o expiró
+    pub async fn authenticate(&self, raw_key: &str) -> AppResult<Option<ApiKeyPrincipal>> {
+        let prefijo = match parse_prefijo(raw_key) {
+            Some(prefijo) => prefijo,
+            None => return Ok(None),
+        };
+
+        let api_key = match self.repository.find_by_prefijo(prefijo).await? {
+            Some(api_key) => api_key,
+            None => return Ok(None),
+        };
+
+        if !hash_coincide(&api_key.hash, raw_key) || !api_key.esta_vigente(chrono::Utc::now()) {
+            return Ok(None);
+        }
+
+        if let Err(e) = self.repository.touch(api_key.id).await {
+            tracing::warn!("No se pudo registrar el uso de la API key {}: {:?}", api_key.id, e);
+        }
+
+        Ok(Some(ApiKeyPrincipal {
+            id: api_key.id,
+            nombre: api_key.nombre,
+            scopes: api_key.scopes,
+        }))
+    }
+}
+
+/// Extrae el prefijo de `ck_<prefijo>_<secreto>`
+fn parse_prefijo(raw_key: &str) -> Option<&str> {
+    let mut partes = raw_key.splitn(3, '_');
+    match (partes.next(), partes.next(), partes.next()) {
+        (Some(API_KEY_PREFIX), Some(prefijo), Some(secreto)) if !prefijo.is_empty() && !secreto.is_empty() => {
+            Some(prefijo)
+        }
+        _ => None,
+    }
+}
+
+fn hash_key(raw_key: &str) -> String {
+    hex::encode(Sha256::digest(raw_key.as_bytes()))
+}
+
+/// Compara el hash guardado con el de la key recibida en tiempo constante
+fn hash_coincide(hash: &str, raw_key: &str) -> bool {
+    hash.as_bytes().ct_eq(hash_key(raw_key).as_bytes()).into()
+}
+
+fn random_hex(bytes: usize) -> String {
+    let mut buf = vec![0u8; bytes];
+    rand::thread_rng().fill_bytes(&mut buf);
+    hex::encode(buf)
+}
+
+// ============================================================================
+// CONVERSIONES
+// ============================================================================
+
+impl From<ApiKey> for ApiKeyResponseDTO {
+    fn from(api_key: ApiKey) -> Self {
+        Self {
+            id: api_key.id,
+            nombre: api_key.nombre,
+            prefijo: api_key.prefijo,
+            scopes: api_key.scopes,
+            expira_en: api_key.expira_en,
+            ultimo_uso: api_key.ultimo_uso,
+            revocada_en: api_key.revocada_en,
+            creada_por: api_key.creada_por,
+            created_at: api_key.created_at,
+        }
+    }
+}
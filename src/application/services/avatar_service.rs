@@ -0,0 +1,154 @@
+use imagesize::ImageType;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::UserResponseDTO;
+use crate::domain::repositories::UserRepository;
+use crate::domain::storage::{BlobStorage, StoredBlob};
+use crate::shared::error::{AppError, AppResult};
+
+/// Tamaño máximo del archivo de avatar
+pub const AVATAR_MAX_BYTES: usize = 2 * 1024 * 1024;
+
+/// Lado mínimo y máximo de la imagen, en píxeles
+const AVATAR_MIN_DIMENSION: usize = 32;
+const AVATAR_MAX_DIMENSION: usize = 2048;
+
+/// Prefijo de las claves de avatar en el almacenamiento
+const AVATAR_KEY_PREFIX: &str = "avatars/";
+
+/// Service de fotos de perfil
+///
+/// Valida el archivo, lo guarda en `BlobStorage` y escribe la clave en `users.foto_perfil`.
+pub struct AvatarService {
+    user_repo: Arc<dyn UserRepository>,
+    storage: Arc<dyn BlobStorage>,
+}
+
+impl AvatarService {
+    pub fn new(user_repo: Arc<dyn UserRepository>, storage: Arc<dyn BlobStorage>) -> Self {
+        Self { user_repo, storage }
+    }
+
+    /// Reemplaza la foto de perfil del usuario
+    ///
+    /// El formato se detecta por el contenido (PNG, JPEG o WebP) y debe coincidir con el
+    /// content type declarado en el multipart, si lo hay.
+    pub async fn upload(
+        &self,
+        id_usuario: Uuid,
+        content_type: Option<&str>,
+        data: Vec<u8>,
+    ) -> AppResult<UserResponseDTO> {
+        if data.is_empty() {
+            return Err(AppError::BadRequest("El archivo está vacío".to_string()));
+        }
+        if data.len() > AVATAR_MAX_BYTES {
+            return Err(AppError::BadRequest(format!(
+                "El archivo supera el máximo de {} bytes",
+                AVATAR_MAX_BYTES
+            )));
+        }
+
+        let (extension, detected) = match imagesize::image_type(&data) {
+            Ok(ImageType::Png) => ("png", "image/png"),
+            Ok(ImageType::Jpeg) => ("jpg", "image/jpeg"),
+            Ok(ImageType::Webp) => ("webp", "image/webp"),
+            _ => {
+                return Err(AppError::BadRequest(
+                    "Formato no soportado. Formatos permitidos: PNG, JPEG, WebP".to_string(),
+                ));
+            }
+        };
+        // `application/octet-stream` equivale a no declarar el tipo
+        let declared = content_type
+            .map(|ct| if ct == "image/jpg" { "image/jpeg" } else { ct })
+            .filter(|ct| *ct != "application/octet-stream");
+        if let Some(declared) = declared
+            && declared != detected
+        {
+            return Err(AppError::BadRequest(format!(
+                "El content type declarado ({}) no coincide con el archivo ({})",
+                declared, detected
+            )));
+        }
+
+        let size = imagesize::blob_size(&data)
+            .map_err(|_| AppError::BadRequest("No se pudieron leer las dimensiones de la imagen".to_string()))?;
+        let lados = AVATAR_MIN_DIMENSION..=AVATAR_MAX_DIMENSION;
+        if !lados.contains(&size.width) || !lados.contains(&size.height) {
+            return Err(AppError::BadRequest(format!(
+                "La imagen debe medir entre {min}x{min} y {max}x{max} px (recibido {}x{})",
+                size.width,
+                size.height,
+                min = AVATAR_MIN_DIMENSION,
+                max = AVATAR_MAX_DIMENSION,
+            )));
+        }
+
+        let user = self
+            .user_repo
+            .find_by_id(id_usuario)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Usuario no encontrado".to_string()))?;
+
+        // Clave nueva en cada subida: los clientes pueden cachear la anterior sin conflicto
+        let key = format!("{}{}/{}.{}", AVATAR_KEY_PREFIX, id_usuario, Uuid::new_v4(), extension);
+        self.storage.put(&key, data, detected).await?;
+
+        let updated = match self.user_repo.update_foto_perfil(id_usuario, Some(&key)).await {
+            Ok(updated) => updated,
+            Err(e) => {
+                let _ = self.storage.delete(&key).await;
+                return Err(e.into());
+            }
+        };
+
+        self.delete_stored(user.foto_perfil.as_deref()).await;
+
+        Ok(UserResponseDTO::from(updated))
+    }
+
+    /// Quita la foto de perfil del usuario
+    pub async fn remove(&self, id_usuario: Uuid) -> AppResult<UserResponseDTO> {
+        let user = self
+            .user_repo
+            .find_by_id(id_usuario)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Usuario no encontrado".to_string()))?;
+
+        let updated = self.user_repo.update_foto_perfil(id_usuario, None).await?;
+        self.delete_stored(user.foto_perfil.as_deref()).await;
+
+        Ok(UserResponseDTO::from(updated))
+    }
+
+    /// Lee la foto de perfil almacenada del usuario
+    pub async fn download(&self, id_usuario: Uuid) -> AppResult<StoredBlob> {
+        let not_found = || AppError::NotFound("El usuario no tiene foto de perfil".to_string());
+
+        let user = self
+            .user_repo
+            .find_by_id(id_usuario)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Usuario no encontrado".to_string()))?;
+
+        // Los valores antiguos pueden ser URLs externas: solo se sirven claves propias
+        let key = user
+            .foto_perfil
+            .filter(|foto| foto.starts_with(AVATAR_KEY_PREFIX))
+            .ok_or_else(not_found)?;
+
+        self.storage.get(&key).await?.ok_or_else(not_found)
+    }
+
+    /// Borra el archivo de un avatar si estaba en el almacenamiento (un fallo solo se registra)
+    pub async fn delete_stored(&self, foto_perfil: Option<&str>) {
+        let Some(key) = foto_perfil.filter(|foto| foto.starts_with(AVATAR_KEY_PREFIX)) else {
+            return;
+        };
+        if let Err(e) = self.storage.delete(key).await {
+            tracing::warn!("No se pudo borrar el avatar anterior {}: {:?}", key, e);
+        }
+    }
+}
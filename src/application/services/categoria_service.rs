@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    CategoriaArbolDTO, CategoriaResponseDTO, CategoriasArbolResponseDTO, CategoriasListResponseDTO,
+    CreateCategoriaDTO, UpdateCategoriaDTO,
+};
+use crate::domain::entities::Categoria;
+use crate::domain::repositories::{CategoriaCambios, CategoriaRepository, NuevaCategoria};
+use crate::domain::validation::slugify;
+use crate::shared::error::{AppError, AppResult};
+
+const NOMBRE_MAX: usize = 100;
+const SLUG_MAX: usize = 120;
+
+/// Service del árbol de categorías de productos
+pub struct CategoriaService {
+    repository: Arc<dyn CategoriaRepository>,
+}
+
+impl CategoriaService {
+    pub fn new(repository: Arc<dyn CategoriaRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Árbol de categorías activas con sus productos activos (incluye subcategorías).
+    /// Una categoría inactiva oculta todo su subárbol.
+    pub async fn get_arbol(&self) -> AppResult<CategoriasArbolResponseDTO> {
+        let (categorias, counts) = tokio::try_join!(
+            self.repository.find_all(),
+            self.repository.count_productos(true),
+        )?;
+        let counts: HashMap<Uuid, i64> = counts.into_iter().collect();
+
+        Ok(CategoriasArbolResponseDTO {
+            categorias: arbol(&categorias, &counts, None),
+        })
+    }
+
+    /// Lista plana de todas las categorías (incluye inactivas) - Admin
+    pub async fn list_categorias(&self) -> AppResult<CategoriasListResponseDTO> {
+        let (categorias, counts) = tokio::try_join!(
+            self.repository.find_all(),
+            self.repository.count_productos(false),
+        )?;
+        let counts: HashMap<Uuid, i64> = counts.into_iter().collect();
+
+        let categorias: Vec<CategoriaResponseDTO> = categorias
+            .into_iter()
+            .map(|c| {
+                let productos = counts.get(&c.id_categoria).copied().unwrap_or(0);
+                (c, productos).into()
+            })
+            .collect();
+
+        Ok(CategoriasListResponseDTO {
+            total: categorias.len(),
+            categorias,
+        })
+    }
+
+    /// Crea una categoría; sin slug se genera a partir del nombre
+    pub async fn create_categoria(&self, dto: CreateCategoriaDTO) -> AppResult<CategoriaResponseDTO> {
+        let nombre = validar_nombre(&dto.nombre)?;
+        let slug = match dto.slug.as_deref() {
+            Some(slug) => validar_slug(slug)?,
+            None => {
+                let slug = slugify(&nombre);
+                if slug.is_empty() {
+                    return Err(AppError::InvalidField {
+                        field: "nombre",
+                        message: "El nombre debe contener letras o dígitos".to_string(),
+                    });
+                }
+                slug.chars().take(SLUG_MAX).collect::<String>().trim_end_matches('-').to_string()
+            }
+        };
+
+        self.verificar_slug_libre(&slug, None).await?;
+        if let Some(id_padre) = dto.id_padre {
+            self.verificar_padre(id_padre).await?;
+        }
+
+        let categoria = self
+            .repository
+            .create(&NuevaCategoria {
+                nombre,
+                slug,
+                id_padre: dto.id_padre,
+                orden: dto.orden.unwrap_or(0),
+                activo: dto.activo.unwrap_or(true),
+            })
+            .await?;
+
+        tracing::info!("Categoría creada: {} ({})", categoria.slug, categoria.id_categoria);
+        Ok((categoria, 0).into())
+    }
+
+    /// Actualiza una categoría. El slug no cambia al renombrar, para no romper enlaces.
+    pub async fn update_categoria(&self, id: Uuid, dto: UpdateCategoriaDTO) -> AppResult<CategoriaResponseDTO> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Categoría {} no encontrada", id)))?;
+
+        let nombre = dto.nombre.as_deref().map(validar_nombre).transpose()?;
+        let slug = dto.slug.as_deref().map(validar_slug).transpose()?;
+
+        if let Some(slug) = &slug {
+            self.verificar_slug_libre(slug, Some(id)).await?;
+        }
+        if let Some(Some(id_padre)) = dto.id_padre {
+            self.verificar_padre(id_padre).await?;
+        }
+
+        let categoria = self
+            .repository
+            .update(
+                id,
+                &CategoriaCambios {
+                    nombre,
+                    slug,
+                    id_padre: dto.id_padre,
+                    orden: dto.orden,
+                    activo: dto.activo,
+                },
+            )
+            .await?;
+
+        let productos = self
+            .repository
+            .count_productos(false)
+            .await?
+            .into_iter()
+            .find_map(|(id_categoria, total)| (id_categoria == id).then_some(total))
+            .unwrap_or(0);
+
+        tracing::info!("Categoría actualizada: {}", id);
+        Ok((categoria, productos).into())
+    }
+
+    /// Elimina una categoría sin subcategorías ni productos
+    pub async fn delete_categoria(&self, id: Uuid) -> AppResult<()> {
+        let (categorias, counts) = tokio::try_join!(
+            self.repository.find_all(),
+            self.repository.count_productos(false),
+        )?;
+
+        if !categorias.iter().any(|c| c.id_categoria == id) {
+            return Err(AppError::NotFound(format!("Categoría {} no encontrada", id)));
+        }
+        if categorias.iter().any(|c| c.id_padre == Some(id)) {
+            return Err(AppError::BadRequest(
+                "La categoría tiene subcategorías; muévalas o elimínelas primero".to_string(),
+            ));
+        }
+        if let Some((_, total)) = counts.iter().find(|(id_categoria, _)| *id_categoria == id) {
+            return Err(AppError::BadRequest(format!(
+                "La categoría tiene {} producto(s); reasígnelos o desactive la categoría",
+                total
+            )));
+        }
+
+        self.repository.delete(id).await?;
+        tracing::info!("Categoría eliminada: {}", id);
+        Ok(())
+    }
+
+    // ========================================================================
+    // HELPERS PRIVADOS
+    // ========================================================================
+
+    async fn verificar_slug_libre(&self, slug: &str, id_actual: Option<Uuid>) -> AppResult<()> {
+        if let Some(existente) = self.repository.find_by_slug(slug).await?
+            && Some(existente.id_categoria) != id_actual
+        {
+            return Err(AppError::InvalidField {
+                field: "slug",
+                message: format!("Ya existe una categoría con slug '{}'", slug),
+            });
+        }
+        Ok(())
+    }
+
+    async fn verificar_padre(&self, id_padre: Uuid) -> AppResult<()> {
+        if self.repository.find_by_id(id_padre).await?.is_none() {
+            return Err(AppError::InvalidField {
+                field: "id_padre",
+                message: format!("La categoría padre {} no existe", id_padre),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn validar_nombre(nombre: &str) -> AppResult<String> {
+    let nombre = nombre.trim();
+    if nombre.is_empty() || nombre.chars().count() > NOMBRE_MAX {
+        return Err(AppError::InvalidField {
+            field: "nombre",
+            message: format!("El nombre es requerido (máximo {} caracteres)", NOMBRE_MAX),
+        });
+    }
+    Ok(nombre.to_string())
+}
+
+fn validar_slug(slug: &str) -> AppResult<String> {
+    let slug = slug.trim();
+    if slug.is_empty() || slug.len() > SLUG_MAX || slugify(slug) != slug {
+        return Err(AppError::InvalidField {
+            field: "slug",
+            message: "Solo minúsculas sin acentos, dígitos y guiones (ej: telefonos-moviles)".to_string(),
+        });
+    }
+    Ok(slug.to_string())
+}
+
+/// Subárbol activo bajo `id_padre`; `categorias` ya viene ordenado por orden y nombre
+fn arbol(categorias: &[Categoria], counts: &HashMap<Uuid, i64>, id_padre: Option<Uuid>) -> Vec<CategoriaArbolDTO> {
+    categorias
+        .iter()
+        .filter(|c| c.activo && c.id_padre == id_padre)
+        .map(|c| {
+            let hijos = arbol(categorias, counts, Some(c.id_categoria));
+            let productos = counts.get(&c.id_categoria).copied().unwrap_or(0)
+                + hijos.iter().map(|h| h.productos).sum::<i64>();
+            CategoriaArbolDTO {
+                id_categoria: c.id_categoria,
+                nombre: c.nombre.clone(),
+                slug: c.slug.clone(),
+                orden: c.orden,
+                productos,
+                hijos,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,156 @@
+use rust_decimal::Decimal;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    ConfigurarCreditoDTO, CreditoAlertasQueryDTO, CreditoAlertasResponseDTO, EstadoCreditoResponseDTO,
+};
+use crate::domain::entities::AuditLog;
+use crate::domain::repositories::{AuditLogRepository, CreditoRepository};
+use crate::shared::error::{AppError, AppResult};
+
+/// Acción registrada en la bitácora al cambiar el crédito de un perfil
+pub const ACCION_CREDITO_CONFIG: &str = "credito.config";
+
+/// Umbral por defecto del listado de alertas (80% del límite)
+const UMBRAL_ALERTA_DEFAULT: f64 = 0.8;
+
+/// Plazo de pago máximo, en días
+const DIAS_CREDITO_MAX: i32 = 365;
+
+/// Máximo que admite `limite_credito NUMERIC(12,2)`, en centavos
+const LIMITE_CREDITO_MAX_CENTAVOS: i64 = 999_999_999_999;
+
+/// Service de crédito de clientes postpago
+///
+/// El saldo se calcula en cada consulta; la verificación al crear pedidos la
+/// hace `PedidoRepository::create` dentro de su transacción.
+pub struct CreditoService {
+    repository: Arc<dyn CreditoRepository>,
+    audit_repo: Arc<dyn AuditLogRepository>,
+}
+
+impl CreditoService {
+    pub fn new(repository: Arc<dyn CreditoRepository>, audit_repo: Arc<dyn AuditLogRepository>) -> Self {
+        Self { repository, audit_repo }
+    }
+
+    /// Situación de crédito de un perfil
+    pub async fn get_estado(&self, id_perfil: Uuid) -> AppResult<EstadoCreditoResponseDTO> {
+        let estado = self
+            .repository
+            .find_estado(id_perfil)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Perfil no encontrado".to_string()))?;
+        Ok(estado.into())
+    }
+
+    /// Perfiles con crédito cerca o sobre su límite (admin)
+    pub async fn list_alertas(&self, query: CreditoAlertasQueryDTO) -> AppResult<CreditoAlertasResponseDTO> {
+        let umbral = query.umbral.unwrap_or(UMBRAL_ALERTA_DEFAULT);
+        if !umbral.is_finite() || umbral < 0.0 {
+            return Err(AppError::InvalidField {
+                field: "umbral",
+                message: "El umbral debe ser un número mayor o igual a 0 (ej: 0.8)".to_string(),
+            });
+        }
+
+        let perfiles: Vec<EstadoCreditoResponseDTO> = self
+            .repository
+            .find_sobre_umbral(umbral)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(CreditoAlertasResponseDTO {
+            umbral,
+            total: perfiles.len(),
+            perfiles,
+        })
+    }
+
+    /// Configura límite y plazo de crédito de un perfil (admin)
+    pub async fn configurar(
+        &self,
+        id_perfil: Uuid,
+        id_admin: Uuid,
+        dto: ConfigurarCreditoDTO,
+    ) -> AppResult<EstadoCreditoResponseDTO> {
+        let limite_credito = dto.limite_credito.map(limite_en_centavos).transpose()?;
+        if let Some(dias) = dto.dias_credito {
+            if dto.limite_credito.is_none() {
+                return Err(AppError::InvalidField {
+                    field: "dias_credito",
+                    message: "El plazo de pago requiere un límite de crédito".to_string(),
+                });
+            }
+            if !(0..=DIAS_CREDITO_MAX).contains(&dias) {
+                return Err(AppError::InvalidField {
+                    field: "dias_credito",
+                    message: format!("El plazo de pago debe estar entre 0 y {} días", DIAS_CREDITO_MAX),
+                });
+            }
+        }
+
+        let estado = self
+            .repository
+            .update_config(id_perfil, limite_credito, dto.dias_credito)
+            .await?;
+
+        let entry = AuditLog::new(ACCION_CREDITO_CONFIG, Some(id_admin), None)
+            .recurso(format!("perfiles/{}", id_perfil))
+            .detalle(json!({
+                "limite_credito": dto.limite_credito,
+                "dias_credito": dto.dias_credito,
+            }));
+        self.audit_repo.create(&entry).await?;
+
+        Ok(estado.into())
+    }
+}
+
+/// Límite de crédito en centavos exactos (`NUMERIC(12,2)`): no negativo, finito y
+/// con dos decimales como máximo
+fn limite_en_centavos(limite: f64) -> AppResult<Decimal> {
+    let maximo = Decimal::new(LIMITE_CREDITO_MAX_CENTAVOS, 2);
+    let invalido = || AppError::InvalidField {
+        field: "limite_credito",
+        message: format!("El límite de crédito debe estar entre 0 y {} con dos decimales como máximo", maximo),
+    };
+
+    let limite = Decimal::try_from(limite).map_err(|_| invalido())?;
+    if limite.is_sign_negative() && !limite.is_zero() || limite > maximo || limite.round_dp(2) != limite {
+        return Err(invalido());
+    }
+    Ok(limite.round_dp(2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(valor: &str) -> Decimal {
+        valor.parse().unwrap()
+    }
+
+    #[test]
+    fn limite_con_centavos_se_conserva_exacto() {
+        assert_eq!(limite_en_centavos(3.3).unwrap(), d("3.30"));
+        assert_eq!(limite_en_centavos(5000.0).unwrap(), d("5000"));
+        assert_eq!(limite_en_centavos(0.0).unwrap(), Decimal::ZERO);
+        assert_eq!(limite_en_centavos(1234.56).unwrap(), d("1234.56"));
+    }
+
+    #[test]
+    fn rechaza_limites_negativos_no_finitos_o_con_mas_de_dos_decimales() {
+        for limite in [-0.01, f64::NAN, f64::INFINITY, 10.005, 1e13] {
+            assert!(
+                matches!(limite_en_centavos(limite), Err(AppError::InvalidField { field: "limite_credito", .. })),
+                "{} debería rechazarse",
+                limite
+            );
+        }
+    }
+}
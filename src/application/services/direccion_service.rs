@@ -67,6 +67,12 @@ impl DireccionService {
         Ok(direccion.into())
     }
 
+    /// Dirección predeterminada del usuario, si tiene
+    pub async fn find_predeterminada(&self, id_perfil: Uuid) -> AppResult<Option<DireccionResponseDTO>> {
+        let direccion = self.repository.find_predeterminada(id_perfil).await?;
+        Ok(direccion.map(Into::into))
+    }
+
     // ========================================================================
     // CONSULTAS - ALMACENES (público)
     // ========================================================================
@@ -343,6 +349,13 @@ impl DireccionService {
 
         // TODO: Verificar que no esté asociado a pedidos activos
 
+        // El kardex referencia al almacén: borrarlo rompería el historial
+        if self.repository.tiene_inventario(id).await? {
+            return Err(AppError::BadRequest(
+                "El almacén tiene inventario o movimientos de stock; desactívelo en lugar de eliminarlo".into(),
+            ));
+        }
+
         self.repository.delete(id).await?;
         tracing::info!("Almacén {} eliminado permanentemente", id);
         Ok(())
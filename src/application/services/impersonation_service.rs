@@ -0,0 +1,79 @@
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::auth::ROL_ADMIN;
+use crate::domain::entities::{AuditLog, User};
+use crate::domain::repositories::{AuditLogRepository, UserRepository};
+use crate::shared::error::{AppError, AppResult};
+
+/// Acción registrada en la bitácora por cada request suplantado
+pub const ACCION_IMPERSONATION: &str = "impersonation.request";
+
+/// Service de suplantación de usuarios (`X-Act-As`) para soporte
+///
+/// Solo un admin activo puede suplantar, y nunca a otro admin.
+pub struct ImpersonationService {
+    user_repo: Arc<dyn UserRepository>,
+    audit_repo: Arc<dyn AuditLogRepository>,
+}
+
+impl ImpersonationService {
+    pub fn new(user_repo: Arc<dyn UserRepository>, audit_repo: Arc<dyn AuditLogRepository>) -> Self {
+        Self { user_repo, audit_repo }
+    }
+
+    /// Valida que `actor_id` pueda suplantar a `target_id` y devuelve el usuario objetivo
+    pub async fn resolve_target(&self, actor_id: Uuid, target_id: Uuid) -> AppResult<User> {
+        let actor = self
+            .user_repo
+            .find_by_id(actor_id)
+            .await
+            .map_err(|_| AppError::Internal("Error al obtener usuario".to_string()))?;
+        if !actor.is_some_and(|a| a.activo && a.rol == ROL_ADMIN) {
+            return Err(AppError::Forbidden(
+                "Solo los administradores pueden usar X-Act-As".to_string(),
+            ));
+        }
+
+        if actor_id == target_id {
+            return Err(AppError::BadRequest("No puede suplantarse a sí mismo".to_string()));
+        }
+
+        let target = self
+            .user_repo
+            .find_by_id(target_id)
+            .await
+            .map_err(|_| AppError::Internal("Error al obtener usuario".to_string()))?
+            .ok_or_else(|| AppError::NotFound("Usuario a suplantar no encontrado".to_string()))?;
+
+        if target.rol == ROL_ADMIN {
+            return Err(AppError::Forbidden(
+                "No se puede suplantar a otro administrador".to_string(),
+            ));
+        }
+        if !target.activo {
+            return Err(AppError::Forbidden(
+                "No se puede suplantar a un usuario desactivado".to_string(),
+            ));
+        }
+
+        Ok(target)
+    }
+
+    /// Registra en la bitácora un request hecho en nombre de otro usuario
+    ///
+    /// Un fallo al escribir no afecta la respuesta ya generada, pero se loguea.
+    pub async fn record_request(&self, actor_id: Uuid, target_id: Uuid, method: &str, path: &str, status: u16) {
+        let entry = AuditLog::new(ACCION_IMPERSONATION, Some(actor_id), Some(target_id))
+            .recurso(format!("{} {}", method, path))
+            .detalle(json!({ "status": status }));
+
+        if let Err(e) = self.audit_repo.create(&entry).await {
+            tracing::error!(
+                "No se pudo auditar la suplantación de {} por {} ({} {}): {:?}",
+                target_id, actor_id, method, path, e
+            );
+        }
+    }
+}
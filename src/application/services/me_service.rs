@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::MeResponseDTO;
+use crate::application::services::{
+    DireccionService, PedidoService, PerfilClienteService, PerfilMiembroService, UserService,
+};
+use crate::domain::auth::ROL_CLIENTE;
+use crate::shared::error::AppResult;
+
+/// Service que arma la vista agregada del usuario autenticado
+///
+/// Reemplaza las llamadas a `/auth/me`, `/api/perfil`, `/api/direcciones` y
+/// `/api/pedidos` al iniciar el frontend; reutiliza los services de cada módulo.
+pub struct MeService {
+    users: Arc<UserService>,
+    perfiles: Arc<PerfilClienteService>,
+    direcciones: Arc<DireccionService>,
+    pedidos: Arc<PedidoService>,
+    miembros: Arc<PerfilMiembroService>,
+}
+
+impl MeService {
+    pub fn new(
+        users: Arc<UserService>,
+        perfiles: Arc<PerfilClienteService>,
+        direcciones: Arc<DireccionService>,
+        pedidos: Arc<PedidoService>,
+        miembros: Arc<PerfilMiembroService>,
+    ) -> Self {
+        Self { users, perfiles, direcciones, pedidos, miembros }
+    }
+
+    /// Usuario, perfil, dirección predeterminada y resumen de pedidos
+    ///
+    /// Usuario, perfil y membresías se consultan en paralelo; con el perfil,
+    /// dirección y conteo de pedidos también.
+    pub async fn get_me(&self, user_id: Uuid) -> AppResult<MeResponseDTO> {
+        let (usuario, perfil, organizaciones) = tokio::try_join!(
+            self.users.get_user(user_id),
+            self.perfiles.find_my_perfil(user_id),
+            self.miembros.list_membresias(user_id),
+        )?;
+
+        let (direccion_predeterminada, pedidos_por_estado) = match &perfil {
+            Some(perfil) => tokio::try_join!(
+                self.direcciones.find_predeterminada(perfil.id_perfil),
+                self.pedidos.count_by_estado(perfil.id_perfil),
+            )?,
+            None => (None, PedidoService::conteo_vacio()),
+        };
+
+        let requiere_onboarding = perfil.is_none() && organizaciones.is_empty() && usuario.rol == ROL_CLIENTE;
+
+        Ok(MeResponseDTO {
+            total_pedidos: pedidos_por_estado.values().sum(),
+            usuario,
+            perfil,
+            direccion_predeterminada,
+            pedidos_por_estado,
+            organizaciones,
+            requiere_onboarding,
+        })
+    }
+}
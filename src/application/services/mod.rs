@@ -3,9 +3,29 @@ pub mod producto_service;
 pub mod pedido_service;
 pub mod perfil_cliente_service;
 pub mod direccion_service;
+pub mod session_service;
+pub mod api_key_service;
+pub mod impersonation_service;
+pub mod privacy_service;
+pub mod avatar_service;
+pub mod me_service;
+pub mod perfil_miembro_service;
+pub mod credito_service;
+pub mod perfil_fusion_service;
+pub mod categoria_service;
 
 pub use user_service::UserService;
 pub use pedido_service::PedidoService;
 pub use perfil_cliente_service::PerfilClienteService;
 pub use producto_service::ProductoService;
 pub use direccion_service::DireccionService;
+pub use session_service::{SessionService, SessionStatus};
+pub use api_key_service::ApiKeyService;
+pub use impersonation_service::ImpersonationService;
+pub use privacy_service::PrivacyService;
+pub use avatar_service::{AvatarService, AVATAR_MAX_BYTES};
+pub use me_service::MeService;
+pub use perfil_miembro_service::PerfilMiembroService;
+pub use credito_service::CreditoService;
+pub use perfil_fusion_service::PerfilFusionService;
+pub use categoria_service::CategoriaService;
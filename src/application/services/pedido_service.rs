@@ -1,3 +1,5 @@
+use serde_json::json;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -5,13 +7,17 @@ use crate::application::dto::{
     CreatePedidoDTO, PedidoResponseDTO, PedidosListResponseDTO,
     UpdateEstadoPedidoDTO, AsignarTransportistaDTO,
 };
-use crate::domain::entities::Pedido;
+use crate::application::policies::{PedidoAccion, PedidoPolicy, Solicitante};
+use crate::domain::entities::{AuditLog, Pedido};
 use crate::domain::repositories::PedidoRepository;
 use crate::shared::{AppError, AppResult};
 
 /// Estados válidos para transiciones
 const ESTADOS_VALIDOS: [&str; 5] = ["pendiente", "confirmado", "en_transito", "entregado", "cancelado"];
 
+/// Acción registrada en la bitácora cuando un admin omite el límite de crédito
+pub const ACCION_CREDITO_OVERRIDE: &str = "credito.override";
+
 /// Service de pedidos - contiene la lógica de negocio
 /// Sigue SRP: solo lógica de negocio, delega persistencia al repository
 pub struct PedidoService {
@@ -24,12 +30,8 @@ impl PedidoService {
     }
 
     /// Obtener pedido por ID
-    pub async fn get_by_id(&self, id: Uuid) -> AppResult<PedidoResponseDTO> {
-        let pedido = self.repository
-            .find_by_id(id)
-            .await?
-            .ok_or_else(|| AppError::NotFound(format!("Pedido {} no encontrado", id)))?;
-
+    pub async fn get_by_id(&self, id: Uuid, solicitante: &Solicitante) -> AppResult<PedidoResponseDTO> {
+        let pedido = self.find_autorizado(id, solicitante, PedidoAccion::Ver).await?;
         Ok(pedido.into())
     }
 
@@ -44,6 +46,18 @@ impl PedidoService {
         })
     }
 
+    /// Cantidad de pedidos de un perfil por estado; los estados sin pedidos aparecen con 0
+    pub async fn count_by_estado(&self, id_perfil: Uuid) -> AppResult<BTreeMap<String, i64>> {
+        let mut counts = Self::conteo_vacio();
+        counts.extend(self.repository.count_by_estado(id_perfil).await?);
+        Ok(counts)
+    }
+
+    /// Conteo por estado con todos los estados en 0
+    pub fn conteo_vacio() -> BTreeMap<String, i64> {
+        ESTADOS_VALIDOS.iter().map(|estado| (estado.to_string(), 0)).collect()
+    }
+
     /// Listar pedidos de un transportista
     pub async fn list_by_transportista(&self, id_transportista: Uuid) -> AppResult<PedidosListResponseDTO> {
         let pedidos = self.repository.find_by_transportista(id_transportista).await?;
@@ -56,12 +70,30 @@ impl PedidoService {
     }
 
     /// Crear nuevo pedido
-    pub async fn create(&self, id_perfil: Uuid, dto: CreatePedidoDTO) -> AppResult<PedidoResponseDTO> {
+    ///
+    /// `id_admin` es el admin que suplanta al cliente (`X-Act-As`), el único que
+    /// puede pedir `omitir_limite_credito`; la omisión queda en la bitácora.
+    pub async fn create(
+        &self,
+        id_perfil: Uuid,
+        dto: CreatePedidoDTO,
+        id_admin: Option<Uuid>,
+    ) -> AppResult<PedidoResponseDTO> {
         // Validar monto
         if dto.monto_total <= 0.0 {
             return Err(AppError::BadRequest("El monto total debe ser mayor a 0".to_string()));
         }
 
+        let id_admin_override = match (dto.omitir_limite_credito, id_admin) {
+            (false, _) => None,
+            (true, Some(id_admin)) => Some(id_admin),
+            (true, None) => {
+                return Err(AppError::Forbidden(
+                    "Solo un administrador puede omitir el límite de crédito".to_string(),
+                ));
+            }
+        };
+
         // Crear entidad de pedido
         let pedido = Pedido {
             id_pedido: Uuid::new_v4(),
@@ -78,12 +110,27 @@ impl PedidoService {
             updated_at: chrono::Utc::now(),
         };
 
-        let created = self.repository.create(&pedido).await?;
+        // La omisión se registra en la misma transacción que el pedido
+        let omision = id_admin_override.map(|id_admin| {
+            AuditLog::new(ACCION_CREDITO_OVERRIDE, Some(id_admin), None)
+                .recurso(format!("pedidos/{}", pedido.id_pedido))
+                .detalle(json!({
+                    "id_perfil": id_perfil,
+                    "monto_total": pedido.monto_total,
+                }))
+        });
+        let created = self.repository.create(&pedido, omision.as_ref()).await?;
+
         Ok(created.into())
     }
 
     /// Actualizar estado del pedido
-    pub async fn update_estado(&self, id: Uuid, dto: UpdateEstadoPedidoDTO) -> AppResult<PedidoResponseDTO> {
+    pub async fn update_estado(
+        &self,
+        id: Uuid,
+        solicitante: &Solicitante,
+        dto: UpdateEstadoPedidoDTO,
+    ) -> AppResult<PedidoResponseDTO> {
         // Validar estado
         if !ESTADOS_VALIDOS.contains(&dto.estado.as_str()) {
             return Err(AppError::BadRequest(format!(
@@ -92,11 +139,8 @@ impl PedidoService {
             )));
         }
 
-        // Obtener pedido actual para validar transición
-        let pedido_actual = self.repository
-            .find_by_id(id)
-            .await?
-            .ok_or_else(|| AppError::NotFound(format!("Pedido {} no encontrado", id)))?;
+        // Obtener pedido actual para validar permisos y transición
+        let pedido_actual = self.find_autorizado(id, solicitante, PedidoAccion::ActualizarEstado).await?;
 
         // Validar transición de estado
         self.validar_transicion_estado(&pedido_actual.estado, &dto.estado)?;
@@ -106,12 +150,14 @@ impl PedidoService {
     }
 
     /// Asignar transportista al pedido
-    pub async fn assign_transportista(&self, id: Uuid, dto: AsignarTransportistaDTO) -> AppResult<PedidoResponseDTO> {
-        // Verificar que el pedido existe
-        let pedido = self.repository
-            .find_by_id(id)
-            .await?
-            .ok_or_else(|| AppError::NotFound(format!("Pedido {} no encontrado", id)))?;
+    pub async fn assign_transportista(
+        &self,
+        id: Uuid,
+        solicitante: &Solicitante,
+        dto: AsignarTransportistaDTO,
+    ) -> AppResult<PedidoResponseDTO> {
+        // Verificar que el pedido existe y el solicitante puede asignar
+        let pedido = self.find_autorizado(id, solicitante, PedidoAccion::AsignarTransportista).await?;
 
         // Solo se puede asignar en estados pendiente o confirmado
         if pedido.estado != "pendiente" && pedido.estado != "confirmado" {
@@ -125,12 +171,9 @@ impl PedidoService {
     }
 
     /// Cancelar pedido
-    pub async fn cancel(&self, id: Uuid) -> AppResult<()> {
-        // Verificar que el pedido existe y puede ser cancelado
-        let pedido = self.repository
-            .find_by_id(id)
-            .await?
-            .ok_or_else(|| AppError::NotFound(format!("Pedido {} no encontrado", id)))?;
+    pub async fn cancel(&self, id: Uuid, solicitante: &Solicitante) -> AppResult<()> {
+        // Verificar que el pedido existe y el solicitante puede cancelarlo
+        let pedido = self.find_autorizado(id, solicitante, PedidoAccion::Cancelar).await?;
 
         // No se puede cancelar si ya está entregado
         if pedido.estado == "entregado" {
@@ -139,7 +182,25 @@ impl PedidoService {
             ));
         }
 
-        self.repository.delete(id).await
+        self.repository.delete(id).await?;
+        tracing::info!("Pedido {} cancelado por usuario {}", id, solicitante.id_usuario);
+        Ok(())
+    }
+
+    /// Obtiene el pedido y aplica la política de acceso para la acción
+    async fn find_autorizado(
+        &self,
+        id: Uuid,
+        solicitante: &Solicitante,
+        accion: PedidoAccion,
+    ) -> AppResult<Pedido> {
+        let pedido = self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Pedido {} no encontrado", id)))?;
+
+        PedidoPolicy::autorizar(solicitante, &pedido, accion)?;
+        Ok(pedido)
     }
 
     /// Validar transición de estado (regla de negocio)
@@ -6,6 +6,7 @@ use crate::application::dto::{
     PerfilesClienteListResponseDTO,
 };
 use crate::domain::repositories::PerfilClienteRepository;
+use crate::domain::validation::{normalizar_telefono, validar_documento, Telefono, TipoDocumento};
 use crate::shared::error::{AppError, AppResult};
 
 /// Service que maneja la lógica de negocio de perfiles de cliente
@@ -29,6 +30,12 @@ impl PerfilClienteService {
         Ok(perfil.into())
     }
 
+    /// Perfil del usuario actual, o `None` si todavía no lo creó
+    pub async fn find_my_perfil(&self, user_id: Uuid) -> AppResult<Option<PerfilClienteResponseDTO>> {
+        let perfil = self.repository.find_by_usuario(user_id).await?;
+        Ok(perfil.map(Into::into))
+    }
+
     /// Obtiene un perfil por su ID (admin)
     pub async fn get_perfil_by_id(&self, id: Uuid) -> AppResult<PerfilClienteResponseDTO> {
         let perfil = self
@@ -59,39 +66,18 @@ impl PerfilClienteService {
             ));
         }
 
-        // Validar documento de identidad único si se proporciona
-        if let Some(ref doc) = dto.documento_identidad {
-            if !doc.is_empty() {
-                if let Some(_existing) = self.repository.find_by_documento(doc).await? {
-                    return Err(AppError::BadRequest(
-                        "El documento de identidad ya está registrado".into(),
-                    ));
-                }
-                
-                // Validar formato de documento (básico)
-                if doc.len() < 5 || doc.len() > 20 {
-                    return Err(AppError::BadRequest(
-                        "El documento de identidad debe tener entre 5 y 20 caracteres".into(),
-                    ));
-                }
-            }
-        }
+        let documento = self
+            .normalizar_documento(dto.documento_identidad.as_deref(), None)
+            .await?;
 
-        // Validar teléfono si se proporciona
-        if let Some(ref tel) = dto.telefono {
-            if !tel.is_empty() && !Self::validar_telefono(tel) {
-                return Err(AppError::BadRequest(
-                    "Formato de teléfono inválido. Use formato internacional (+593...)".into(),
-                ));
-            }
-        }
+        let telefono = Self::normalizar_telefono(dto.telefono.as_deref())?;
 
         let perfil = self
             .repository
             .create(
                 user_id,
-                dto.documento_identidad.as_deref(),
-                dto.telefono.as_deref(),
+                documento.as_ref().map(|(doc, tipo)| (doc.as_str(), *tipo)),
+                telefono.as_ref(),
             )
             .await?;
 
@@ -112,41 +98,18 @@ impl PerfilClienteService {
             .await?
             .ok_or_else(|| AppError::NotFound("Perfil no encontrado".into()))?;
 
-        // Validar documento si se actualiza
-        if let Some(ref doc) = dto.documento_identidad {
-            if !doc.is_empty() {
-                // Verificar que no esté en uso por otro usuario
-                if let Some(existing) = self.repository.find_by_documento(doc).await? {
-                    if existing.id_perfil != perfil.id_perfil {
-                        return Err(AppError::BadRequest(
-                            "El documento de identidad ya está registrado".into(),
-                        ));
-                    }
-                }
-                
-                if doc.len() < 5 || doc.len() > 20 {
-                    return Err(AppError::BadRequest(
-                        "El documento de identidad debe tener entre 5 y 20 caracteres".into(),
-                    ));
-                }
-            }
-        }
+        let documento = self
+            .normalizar_documento(dto.documento_identidad.as_deref(), Some(perfil.id_perfil))
+            .await?;
 
-        // Validar teléfono si se actualiza
-        if let Some(ref tel) = dto.telefono {
-            if !tel.is_empty() && !Self::validar_telefono(tel) {
-                return Err(AppError::BadRequest(
-                    "Formato de teléfono inválido. Use formato internacional (+593...)".into(),
-                ));
-            }
-        }
+        let telefono = Self::normalizar_telefono(dto.telefono.as_deref())?;
 
         let updated = self
             .repository
             .update(
                 perfil.id_perfil,
-                dto.documento_identidad.as_deref(),
-                dto.telefono.as_deref(),
+                documento.as_ref().map(|(doc, tipo)| (doc.as_str(), *tipo)),
+                telefono.as_ref(),
             )
             .await?;
 
@@ -167,25 +130,17 @@ impl PerfilClienteService {
             .await?
             .ok_or_else(|| AppError::NotFound(format!("Perfil con ID {} no encontrado", id)))?;
 
-        // Validar documento si se actualiza
-        if let Some(ref doc) = dto.documento_identidad {
-            if !doc.is_empty() {
-                if let Some(existing) = self.repository.find_by_documento(doc).await? {
-                    if existing.id_perfil != perfil.id_perfil {
-                        return Err(AppError::BadRequest(
-                            "El documento de identidad ya está registrado".into(),
-                        ));
-                    }
-                }
-            }
-        }
+        let documento = self
+            .normalizar_documento(dto.documento_identidad.as_deref(), Some(perfil.id_perfil))
+            .await?;
+        let telefono = Self::normalizar_telefono(dto.telefono.as_deref())?;
 
         let updated = self
             .repository
             .update(
                 id,
-                dto.documento_identidad.as_deref(),
-                dto.telefono.as_deref(),
+                documento.as_ref().map(|(doc, tipo)| (doc.as_str(), *tipo)),
+                telefono.as_ref(),
             )
             .await?;
 
@@ -221,16 +176,44 @@ impl PerfilClienteService {
     // HELPERS PRIVADOS
     // ========================================================================
 
-    /// Valida el formato de un número de teléfono
-    fn validar_telefono(telefono: &str) -> bool {
-        // Formato básico: debe empezar con + y tener entre 10 y 15 dígitos
-        let cleaned: String = telefono.chars().filter(|c| c.is_ascii_digit()).collect();
-        
-        if telefono.starts_with('+') {
-            cleaned.len() >= 10 && cleaned.len() <= 15
-        } else {
-            // También aceptar formato local (10 dígitos)
-            cleaned.len() >= 9 && cleaned.len() <= 15
+    /// Normaliza y valida el documento de identidad, y verifica que no lo use otro perfil
+    ///
+    /// Devuelve el documento normalizado con su tipo, o `None` si no se envió.
+    async fn normalizar_documento(
+        &self,
+        documento: Option<&str>,
+        id_perfil_actual: Option<Uuid>,
+    ) -> AppResult<Option<(String, TipoDocumento)>> {
+        let Some(doc) = documento.map(|d| d.trim().to_uppercase()).filter(|d| !d.is_empty()) else {
+            return Ok(None);
+        };
+
+        let tipo = validar_documento(&doc).map_err(|e| AppError::InvalidField {
+            field: "documento_identidad",
+            message: e.to_string(),
+        })?;
+
+        if let Some(existing) = self.repository.find_by_documento(&doc).await?
+            && Some(existing.id_perfil) != id_perfil_actual
+        {
+            return Err(AppError::InvalidField {
+                field: "documento_identidad",
+                message: "El documento de identidad ya está registrado".into(),
+            });
         }
+
+        Ok(Some((doc, tipo)))
+    }
+
+    /// Normaliza el teléfono a E.164; `None` si no se envió
+    fn normalizar_telefono(telefono: Option<&str>) -> AppResult<Option<Telefono>> {
+        let Some(tel) = telefono.map(str::trim).filter(|t| !t.is_empty()) else {
+            return Ok(None);
+        };
+
+        normalizar_telefono(tel).map(Some).map_err(|e| AppError::InvalidField {
+            field: "telefono",
+            message: e.to_string(),
+        })
     }
 }
@@ -0,0 +1,258 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    DuplicadosGrupoDTO, DuplicadosResponseDTO, FusionResponseDTO, FusionarPerfilDTO,
+    FusionesListResponseDTO,
+};
+use crate::application::policies::RolMiembro;
+use crate::domain::entities::AuditLog;
+use crate::domain::repositories::PerfilFusionRepository;
+use crate::shared::error::{AppError, AppResult};
+
+/// Acción registrada en la bitácora al fusionar perfiles
+pub const ACCION_PERFIL_MERGE: &str = "perfil.merge";
+
+/// Service de perfiles duplicados: detección y fusión (admin)
+pub struct PerfilFusionService {
+    repository: Arc<dyn PerfilFusionRepository>,
+}
+
+impl PerfilFusionService {
+    pub fn new(repository: Arc<dyn PerfilFusionRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Grupos de perfiles que comparten documento, teléfono o email normalizado
+    pub async fn find_duplicados(&self) -> AppResult<DuplicadosResponseDTO> {
+        let mut grupos: Vec<DuplicadosGrupoDTO> = Vec::new();
+
+        // Las filas llegan ordenadas por criterio y clave
+        for duplicado in self.repository.find_duplicados().await? {
+            match grupos.last_mut() {
+                Some(grupo) if grupo.criterio == duplicado.criterio && grupo.clave == duplicado.clave => {
+                    grupo.perfiles.push(duplicado.into());
+                }
+                _ => grupos.push(DuplicadosGrupoDTO {
+                    criterio: duplicado.criterio.clone(),
+                    clave: duplicado.clave.clone(),
+                    perfiles: vec![duplicado.into()],
+                }),
+            }
+        }
+
+        Ok(DuplicadosResponseDTO {
+            total: grupos.len(),
+            grupos,
+        })
+    }
+
+    /// Fusiona `dto.id_perfil_origen` en `id_destino` (irreversible)
+    pub async fn fusionar(
+        &self,
+        id_destino: Uuid,
+        id_admin: Uuid,
+        dto: FusionarPerfilDTO,
+    ) -> AppResult<FusionResponseDTO> {
+        if dto.id_perfil_origen == id_destino {
+            return Err(AppError::BadRequest("No se puede fusionar un perfil consigo mismo".to_string()));
+        }
+
+        let rol = dto
+            .rol_miembro_origen
+            .as_deref()
+            .map(|rol| {
+                RolMiembro::parse(rol).ok_or_else(|| AppError::InvalidField {
+                    field: "rol_miembro_origen",
+                    message: "Rol inválido. Roles válidos: owner, shipper, viewer".to_string(),
+                })
+            })
+            .transpose()?;
+
+        let entry = AuditLog::new(ACCION_PERFIL_MERGE, Some(id_admin), None)
+            .recurso(format!("perfiles/{}", id_destino));
+        let (perfil, fusion) = self
+            .repository
+            .fusionar(id_destino, dto.id_perfil_origen, rol.as_ref().map(RolMiembro::as_str), &entry)
+            .await?;
+
+        tracing::info!(
+            "Perfil {} fusionado en {} por {}: {} direcciones, {} pedidos",
+            dto.id_perfil_origen, id_destino, id_admin, fusion.direcciones_movidas, fusion.pedidos_movidos
+        );
+
+        Ok(FusionResponseDTO {
+            perfil: perfil.into(),
+            fusion: fusion.into(),
+        })
+    }
+
+    /// Registro de fusiones realizadas
+    pub async fn list_fusiones(&self) -> AppResult<FusionesListResponseDTO> {
+        let fusiones: Vec<_> = self
+            .repository
+            .find_fusiones()
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(FusionesListResponseDTO {
+            total: fusiones.len(),
+            fusiones,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PerfilCliente, PerfilDuplicado, PerfilFusion};
+    use chrono::Utc;
+    use std::sync::Mutex;
+
+    /// Repositorio en memoria: devuelve `duplicados` y registra las fusiones pedidas
+    #[derive(Default)]
+    struct FakeFusiones {
+        duplicados: Vec<PerfilDuplicado>,
+        fusiones: Mutex<Vec<(Uuid, Uuid, Option<String>)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl PerfilFusionRepository for FakeFusiones {
+        async fn find_duplicados(&self) -> AppResult<Vec<PerfilDuplicado>> {
+            Ok(self.duplicados.clone())
+        }
+        async fn fusionar(
+            &self,
+            id_destino: Uuid,
+            id_origen: Uuid,
+            rol_miembro_origen: Option<&str>,
+            audit: &AuditLog,
+        ) -> AppResult<(PerfilCliente, PerfilFusion)> {
+            self.fusiones
+                .lock()
+                .unwrap()
+                .push((id_destino, id_origen, rol_miembro_origen.map(str::to_string)));
+            let perfil = PerfilCliente {
+                id_perfil: id_destino,
+                id_usuario: Uuid::new_v4(),
+                documento_identidad: None,
+                tipo_documento: None,
+                telefono: None,
+                tipo_telefono: None,
+                limite_credito: None,
+                dias_credito: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+            let fusion = PerfilFusion {
+                id_perfil_origen: id_origen,
+                id_perfil_destino: id_destino,
+                id_usuario_origen: None,
+                documento_origen: None,
+                telefono_origen: None,
+                direcciones_movidas: 0,
+                pedidos_movidos: 0,
+                miembros_movidos: 0,
+                fusionado_por: audit.id_actor,
+                created_at: audit.created_at,
+            };
+            Ok((perfil, fusion))
+        }
+        async fn find_fusiones(&self) -> AppResult<Vec<PerfilFusion>> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn duplicado(criterio: &str, clave: &str) -> PerfilDuplicado {
+        PerfilDuplicado {
+            criterio: criterio.to_string(),
+            clave: clave.to_string(),
+            id_perfil: Uuid::new_v4(),
+            id_usuario: Uuid::new_v4(),
+            email: None,
+            documento_identidad: None,
+            telefono: None,
+            direcciones: 0,
+            pedidos: 0,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn dto(id_perfil_origen: Uuid, rol: Option<&str>) -> FusionarPerfilDTO {
+        FusionarPerfilDTO {
+            id_perfil_origen,
+            rol_miembro_origen: rol.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn agrupa_las_filas_por_criterio_y_clave() {
+        let repo = FakeFusiones {
+            duplicados: vec![
+                duplicado("documento", "1710034065"),
+                duplicado("documento", "1710034065"),
+                duplicado("telefono", "+593991234567"),
+                duplicado("telefono", "+593991234567"),
+                duplicado("telefono", "+593991234568"),
+                duplicado("telefono", "+593991234568"),
+            ],
+            ..Default::default()
+        };
+        let service = PerfilFusionService::new(Arc::new(repo));
+
+        let response = service.find_duplicados().await.unwrap();
+
+        assert_eq!(response.total, 3);
+        let grupos: Vec<_> = response.grupos.iter().map(|g| (g.criterio.as_str(), g.clave.as_str(), g.perfiles.len())).collect();
+        assert_eq!(
+            grupos,
+            [
+                ("documento", "1710034065", 2),
+                ("telefono", "+593991234567", 2),
+                ("telefono", "+593991234568", 2),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn rechaza_fusionar_un_perfil_consigo_mismo() {
+        let repo = Arc::new(FakeFusiones::default());
+        let service = PerfilFusionService::new(repo.clone());
+        let id = Uuid::new_v4();
+
+        let result = service.fusionar(id, Uuid::new_v4(), dto(id, None)).await;
+
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+        assert!(repo.fusiones.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rechaza_un_rol_de_miembro_invalido() {
+        let repo = Arc::new(FakeFusiones::default());
+        let service = PerfilFusionService::new(repo.clone());
+
+        let result = service
+            .fusionar(Uuid::new_v4(), Uuid::new_v4(), dto(Uuid::new_v4(), Some("admin")))
+            .await;
+
+        assert!(matches!(result, Err(AppError::InvalidField { field: "rol_miembro_origen", .. })));
+        assert!(repo.fusiones.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn fusiona_con_el_rol_indicado() {
+        let repo = Arc::new(FakeFusiones::default());
+        let service = PerfilFusionService::new(repo.clone());
+        let (destino, origen, admin) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let response = service.fusionar(destino, admin, dto(origen, Some("shipper"))).await.unwrap();
+
+        assert_eq!(response.fusion.fusionado_por, Some(admin));
+        assert_eq!(
+            *repo.fusiones.lock().unwrap(),
+            [(destino, origen, Some("shipper".to_string()))]
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    AddMiembroDTO, MiembroResponseDTO, MiembrosListResponseDTO, PerfilAccesibleDTO,
+    PerfilesAccesiblesResponseDTO, UpdateMiembroRolDTO,
+};
+use crate::application::policies::RolMiembro;
+use crate::domain::entities::PerfilCliente;
+use crate::domain::repositories::{PerfilClienteRepository, PerfilMiembroRepository, UserRepository};
+use crate::shared::error::{AppError, AppResult};
+
+/// Service de cuentas de empresa: miembros que comparten un perfil de cliente
+///
+/// El titular del perfil es siempre owner y no se guarda en `perfil_miembros`.
+/// Los permisos de cada rol están en `PerfilPolicy`; los handlers los verifican.
+pub struct PerfilMiembroService {
+    perfil_repo: Arc<dyn PerfilClienteRepository>,
+    miembro_repo: Arc<dyn PerfilMiembroRepository>,
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl PerfilMiembroService {
+    pub fn new(
+        perfil_repo: Arc<dyn PerfilClienteRepository>,
+        miembro_repo: Arc<dyn PerfilMiembroRepository>,
+        user_repo: Arc<dyn UserRepository>,
+    ) -> Self {
+        Self { perfil_repo, miembro_repo, user_repo }
+    }
+
+    /// Lista el titular y los miembros del perfil
+    pub async fn list_miembros(&self, perfil: &PerfilCliente) -> AppResult<MiembrosListResponseDTO> {
+        let (titular, miembros) = tokio::try_join!(
+            async { Ok::<_, AppError>(self.user_repo.find_by_id(perfil.id_usuario).await?) },
+            self.miembro_repo.find_by_perfil(perfil.id_perfil),
+        )?;
+
+        let titular = MiembroResponseDTO {
+            id_usuario: perfil.id_usuario,
+            email: titular.as_ref().and_then(|u| u.email.clone()),
+            nombre: titular.as_ref().and_then(|u| u.nombre.clone()),
+            apellido: titular.as_ref().and_then(|u| u.apellido.clone()),
+            rol: RolMiembro::Owner.as_str().to_string(),
+            titular: true,
+            created_at: perfil.created_at,
+        };
+        let miembros: Vec<MiembroResponseDTO> = std::iter::once(titular)
+            .chain(miembros.into_iter().map(Into::into))
+            .collect();
+
+        Ok(MiembrosListResponseDTO {
+            id_perfil: perfil.id_perfil,
+            total: miembros.len(),
+            miembros,
+        })
+    }
+
+    /// Agrega un usuario registrado como miembro del perfil
+    ///
+    /// Solo los perfiles con RUC (empresa o persona natural con RUC) admiten miembros.
+    pub async fn add_miembro(
+        &self,
+        perfil: &PerfilCliente,
+        id_actor: Uuid,
+        dto: AddMiembroDTO,
+    ) -> AppResult<MiembroResponseDTO> {
+        let rol = Self::parse_rol(&dto.rol)?;
+
+        let es_ruc = perfil
+            .tipo_documento
+            .as_deref()
+            .is_some_and(|tipo| tipo.starts_with("ruc_"));
+        if !es_ruc {
+            return Err(AppError::BadRequest(
+                "Solo los perfiles con RUC pueden tener miembros".into(),
+            ));
+        }
+
+        let user = self
+            .user_repo
+            .find_by_email(dto.email.trim())
+            .await?
+            .ok_or_else(|| AppError::NotFound("No existe un usuario con ese email".into()))?;
+
+        if !user.activo {
+            return Err(AppError::BadRequest("El usuario está desactivado".into()));
+        }
+        if user.id == perfil.id_usuario {
+            return Err(AppError::BadRequest("El usuario es el titular del perfil".into()));
+        }
+        if self.miembro_repo.find(perfil.id_perfil, user.id).await?.is_some() {
+            return Err(AppError::BadRequest("El usuario ya es miembro del perfil".into()));
+        }
+
+        let miembro = self
+            .miembro_repo
+            .create(perfil.id_perfil, user.id, rol.as_str(), id_actor)
+            .await?;
+
+        tracing::info!("Usuario {} agregado al perfil {} como {}", user.id, perfil.id_perfil, rol.as_str());
+        Ok(miembro.into())
+    }
+
+    /// Cambia el rol de un miembro (el titular no se puede cambiar)
+    pub async fn update_rol(
+        &self,
+        perfil: &PerfilCliente,
+        id_usuario: Uuid,
+        dto: UpdateMiembroRolDTO,
+    ) -> AppResult<MiembroResponseDTO> {
+        let rol = Self::parse_rol(&dto.rol)?;
+        if id_usuario == perfil.id_usuario {
+            return Err(AppError::BadRequest("El titular del perfil siempre es owner".into()));
+        }
+
+        let miembro = self
+            .miembro_repo
+            .update_rol(perfil.id_perfil, id_usuario, rol.as_str())
+            .await?;
+        Ok(miembro.into())
+    }
+
+    /// Quita un miembro del perfil (o el propio miembro abandona el perfil)
+    pub async fn remove_miembro(&self, perfil: &PerfilCliente, id_usuario: Uuid) -> AppResult<()> {
+        if id_usuario == perfil.id_usuario {
+            return Err(AppError::BadRequest("No se puede quitar al titular del perfil".into()));
+        }
+
+        self.miembro_repo.delete(perfil.id_perfil, id_usuario).await?;
+        tracing::info!("Usuario {} quitado del perfil {}", id_usuario, perfil.id_perfil);
+        Ok(())
+    }
+
+    /// Perfiles con los que puede operar el usuario: el propio y los de empresa
+    pub async fn list_perfiles_accesibles(&self, id_usuario: Uuid) -> AppResult<PerfilesAccesiblesResponseDTO> {
+        let (propio, membresias) = tokio::try_join!(
+            async { Ok::<_, AppError>(self.perfil_repo.find_by_usuario(id_usuario).await?) },
+            self.list_membresias(id_usuario),
+        )?;
+
+        let perfiles: Vec<PerfilAccesibleDTO> = propio
+            .map(|perfil| PerfilAccesibleDTO {
+                perfil: perfil.into(),
+                rol: RolMiembro::Owner.as_str().to_string(),
+                titular: true,
+            })
+            .into_iter()
+            .chain(membresias)
+            .collect();
+
+        Ok(PerfilesAccesiblesResponseDTO {
+            total: perfiles.len(),
+            perfiles,
+        })
+    }
+
+    /// Perfiles de empresa de los que el usuario es miembro (sin el propio)
+    pub async fn list_membresias(&self, id_usuario: Uuid) -> AppResult<Vec<PerfilAccesibleDTO>> {
+        let membresias = self.miembro_repo.find_by_usuario(id_usuario).await?;
+
+        let mut perfiles = Vec::with_capacity(membresias.len());
+        for miembro in membresias {
+            if let Some(perfil) = self.perfil_repo.find_by_id(miembro.id_perfil).await? {
+                perfiles.push(PerfilAccesibleDTO {
+                    perfil: perfil.into(),
+                    rol: miembro.rol,
+                    titular: false,
+                });
+            }
+        }
+        Ok(perfiles)
+    }
+
+    fn parse_rol(rol: &str) -> AppResult<RolMiembro> {
+        RolMiembro::parse(rol).ok_or_else(|| AppError::InvalidField {
+            field: "rol",
+            message: "Rol inválido. Roles válidos: owner, shipper, viewer".into(),
+        })
+    }
+}
@@ -0,0 +1,119 @@
+use chrono::Utc;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    AnonymizationResponseDTO, DireccionResponseDTO, PedidoResponseDTO, PerfilClienteResponseDTO,
+    PersonalDataExportDTO, UserResponseDTO,
+};
+use crate::application::services::{AvatarService, SessionService};
+use crate::domain::entities::AuditLog;
+use crate::domain::repositories::{
+    AuditLogRepository, DataPrivacyRepository, DireccionRepository, PedidoRepository,
+    PerfilClienteRepository, UserRepository,
+};
+use crate::shared::error::{AppError, AppResult};
+
+/// Acciones registradas en la bitácora
+pub const ACCION_DATA_EXPORT: &str = "privacy.export";
+pub const ACCION_ANONYMIZE: &str = "privacy.anonymize";
+
+/// Service de privacidad: exportación de datos personales y derecho al olvido
+pub struct PrivacyService {
+    user_repo: Arc<dyn UserRepository>,
+    perfil_repo: Arc<dyn PerfilClienteRepository>,
+    direccion_repo: Arc<dyn DireccionRepository>,
+    pedido_repo: Arc<dyn PedidoRepository>,
+    privacy_repo: Arc<dyn DataPrivacyRepository>,
+    audit_repo: Arc<dyn AuditLogRepository>,
+    sessions: Arc<SessionService>,
+    avatars: Arc<AvatarService>,
+}
+
+impl PrivacyService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_repo: Arc<dyn UserRepository>,
+        perfil_repo: Arc<dyn PerfilClienteRepository>,
+        direccion_repo: Arc<dyn DireccionRepository>,
+        pedido_repo: Arc<dyn PedidoRepository>,
+        privacy_repo: Arc<dyn DataPrivacyRepository>,
+        audit_repo: Arc<dyn AuditLogRepository>,
+        sessions: Arc<SessionService>,
+        avatars: Arc<AvatarService>,
+    ) -> Self {
+        Self { user_repo, perfil_repo, direccion_repo, pedido_repo, privacy_repo, audit_repo, sessions, avatars }
+    }
+
+    /// Reúne usuario, perfil, direcciones y pedidos en un solo documento
+    ///
+    /// `id_actor` es quien lo solicita (el propio usuario o un admin) y queda en la bitácora.
+    pub async fn export_user_data(&self, id_usuario: Uuid, id_actor: Uuid) -> AppResult<PersonalDataExportDTO> {
+        let user = self
+            .user_repo
+            .find_by_id(id_usuario)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Usuario no encontrado".to_string()))?;
+
+        let perfil = self.perfil_repo.find_by_usuario(id_usuario).await?;
+        let (direcciones, pedidos) = match &perfil {
+            Some(perfil) => tokio::try_join!(
+                self.direccion_repo.find_all_by_perfil(perfil.id_perfil),
+                self.pedido_repo.find_by_perfil(perfil.id_perfil),
+            )?,
+            None => (Vec::new(), Vec::new()),
+        };
+
+        let export = PersonalDataExportDTO {
+            generado_en: Utc::now(),
+            usuario: UserResponseDTO::from(user),
+            perfil: perfil.map(PerfilClienteResponseDTO::from),
+            direcciones: direcciones.into_iter().map(DireccionResponseDTO::from).collect(),
+            pedidos: pedidos.into_iter().map(PedidoResponseDTO::from).collect(),
+        };
+
+        let entry = AuditLog::new(ACCION_DATA_EXPORT, Some(id_actor), Some(id_usuario))
+            .recurso(format!("users/{}", id_usuario))
+            .detalle(json!({
+                "direcciones": export.direcciones.len(),
+                "pedidos": export.pedidos.len(),
+            }));
+        self.audit_repo.create(&entry).await?;
+
+        Ok(export)
+    }
+
+    /// Anonimiza los datos personales del usuario (irreversible)
+    ///
+    /// Todo ocurre en una transacción junto con el registro en la bitácora; después
+    /// se revocan las sesiones del usuario, que queda desactivado, y se borra el
+    /// archivo de su foto de perfil.
+    pub async fn anonymize_user(&self, id_usuario: Uuid, id_actor: Uuid) -> AppResult<AnonymizationResponseDTO> {
+        if id_usuario == id_actor {
+            return Err(AppError::BadRequest("No puede anonimizar su propia cuenta".to_string()));
+        }
+
+        let foto_perfil = self
+            .user_repo
+            .find_by_id(id_usuario)
+            .await?
+            .and_then(|user| user.foto_perfil);
+
+        let entry = AuditLog::new(ACCION_ANONYMIZE, Some(id_actor), Some(id_usuario))
+            .recurso(format!("users/{}", id_usuario));
+        let summary = self.privacy_repo.anonymize_user(id_usuario, &entry).await?;
+
+        self.sessions
+            .revoke_sessions(id_usuario, Some("Datos anonimizados"), Some(id_actor))
+            .await?;
+        self.avatars.delete_stored(foto_perfil.as_deref()).await;
+
+        Ok(AnonymizationResponseDTO {
+            id_usuario,
+            perfiles_anonimizados: summary.perfiles,
+            direcciones_anonimizadas: summary.direcciones,
+            anonimizado_en: entry.created_at,
+        })
+    }
+}
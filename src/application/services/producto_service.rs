@@ -1,38 +1,90 @@
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::application::dto::{
     CreateProductoDTO, UpdateProductoDTO, UpdateStockDTO, UpdateEstadoProductoDTO,
-    ProductoResponseDTO, ProductosListResponseDTO,
+    ProductoResponseDTO, ProductosListResponseDTO, ProductosQueryDTO, PaginacionDTO, CategoriaFacetaDTO,
+    ModoBusqueda, ProductosBusquedaResponseDTO, MovimientosStockQueryDTO, MovimientosStockResponseDTO,
+    ConciliacionStockResponseDTO, TransferenciaStockDTO, TransferenciaStockResponseDTO, InventarioAlmacenDTO,
 };
-use crate::domain::repositories::ProductoRepository;
+use crate::domain::entities::{ActorMovimiento, AlmacenCercano, Categoria, DescuadreStock, Producto};
+use crate::domain::repositories::{
+    CategoriaRepository, DireccionRepository, MovimientosStockQuery, NuevaTransferenciaStock, NuevoMovimientoStock,
+    ProductoFilter, ProductoPageQuery, ProductoRepository, ProductoSortField,
+};
+use crate::domain::validation::slugify;
 use crate::shared::error::{AppError, AppResult};
+use crate::shared::Cursor;
+
+/// Tamaño de página por defecto y máximo del catálogo
+const CATALOGO_PAGE_DEFAULT: i64 = 20;
+const CATALOGO_PAGE_MAX: i64 = 100;
+
+const BUSQUEDA_LIMITE_DEFAULT: i64 = 20;
+const BUSQUEDA_LIMITE_MAX: i64 = 100;
+
+/// Tamaño de página por defecto y máximo del kardex
+const KARDEX_PAGE_DEFAULT: i64 = 50;
+const KARDEX_PAGE_MAX: i64 = 200;
+
+const MOTIVO_MAX: usize = 500;
+
+/// Motivo del movimiento que registra el stock con el que se crea un producto
+const MOTIVO_STOCK_INICIAL: &str = "Stock inicial";
+
+/// Motivo de la transferencia que asigna a un almacén el stock sin almacén
+/// (el mismo que usa la migración 016)
+const MOTIVO_ASIGNACION_SIN_ALMACEN: &str = "Asignación del stock existente a un almacén";
 
 /// Service que maneja la lógica de negocio de productos
 pub struct ProductoService {
     repository: Arc<dyn ProductoRepository>,
+    categorias: Arc<dyn CategoriaRepository>,
+    almacenes: Arc<dyn DireccionRepository>,
 }
 
 impl ProductoService {
-    pub fn new(repository: Arc<dyn ProductoRepository>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<dyn ProductoRepository>,
+        categorias: Arc<dyn CategoriaRepository>,
+        almacenes: Arc<dyn DireccionRepository>,
+    ) -> Self {
+        Self { repository, categorias, almacenes }
     }
 
     // ========================================================================
     // CONSULTAS
     // ========================================================================
 
-    /// Lista todos los productos activos
-    pub async fn list_productos(&self) -> AppResult<ProductosListResponseDTO> {
-        let productos = self.repository.find_activos().await?;
-        Ok(productos.into())
+    /// Catálogo de productos activos: filtros, orden y paginación
+    ///
+    /// Se pagina por cursor (keyset) o por número de página (`page`); el cursor
+    /// no se desplaza cuando se agregan productos entre una página y otra.
+    /// La disponibilidad es la suma de los almacenes activos o, con
+    /// `disponibilidad=cercano`, la del almacén activo más cercano al cliente.
+    pub async fn list_productos(&self, query: ProductosQueryDTO) -> AppResult<ProductosListResponseDTO> {
+        let numero_pagina = query.page;
+        let cercano = self.almacen_cercano(&query).await?;
+        let mut page = Self::page_query(query)?;
+        page.filter.id_almacen = cercano.as_ref().map(|a| a.id_almacen);
+        self.catalogo(page, numero_pagina, cercano).await
     }
 
-    /// Lista todos los productos (incluye inactivos) - Admin
+    /// Lista todos los productos (incluye inactivos) con su stock por almacén - Admin
     pub async fn list_all_productos(&self) -> AppResult<ProductosListResponseDTO> {
         let productos = self.repository.find_all().await?;
-        Ok(productos.into())
+        let ids: Vec<Uuid> = productos.iter().map(|p| p.id_producto).collect();
+        let mut inventario = self.inventario_por_producto(&ids).await?;
+
+        let mut response: ProductosListResponseDTO = productos.into();
+        for producto in &mut response.productos {
+            producto.inventario = Some(inventario.remove(&producto.id_producto).unwrap_or_default());
+        }
+        Ok(response)
     }
 
     /// Obtiene un producto por ID
@@ -43,7 +95,7 @@ impl ProductoService {
             .await?
             .ok_or_else(|| AppError::NotFound(format!("Producto con ID {} no encontrado", id)))?;
 
-        Ok(producto.into())
+        self.con_disponibilidad(producto).await
     }
 
     /// Obtiene un producto por SKU
@@ -54,23 +106,59 @@ impl ProductoService {
             .await?
             .ok_or_else(|| AppError::NotFound(format!("Producto con SKU {} no encontrado", sku)))?;
 
-        Ok(producto.into())
+        self.con_disponibilidad(producto).await
     }
 
-    /// Busca productos por categoría
-    pub async fn get_by_categoria(&self, categoria: &str) -> AppResult<ProductosListResponseDTO> {
-        let productos = self.repository.find_by_categoria(categoria).await?;
-        Ok(productos.into())
+    /// Catálogo de una categoría y sus subcategorías (mismos parámetros que el
+    /// catálogo, salvo `categoria`)
+    pub async fn get_by_categoria(
+        &self,
+        categoria: &str,
+        query: ProductosQueryDTO,
+    ) -> AppResult<ProductosListResponseDTO> {
+        let slug = slugify(categoria);
+        let categoria = self
+            .categorias
+            .find_by_slug(&slug)
+            .await?
+            .filter(|c| c.activo)
+            .ok_or_else(|| AppError::NotFound(format!("Categoría {} no encontrada", categoria)))?;
+
+        let numero_pagina = query.page;
+        let cercano = self.almacen_cercano(&query).await?;
+        let mut page = Self::page_query(ProductosQueryDTO { categoria: None, ..query })?;
+        page.filter.categorias = vec![categoria.slug];
+        page.filter.id_almacen = cercano.as_ref().map(|a| a.id_almacen);
+        self.catalogo(page, numero_pagina, cercano).await
     }
 
-    /// Busca productos por nombre, SKU o descripción
-    pub async fn search_productos(&self, query: &str) -> AppResult<ProductosListResponseDTO> {
-        if query.trim().is_empty() {
+    /// Busca productos por relevancia (texto completo y prefijo de SKU). Si no
+    /// hay coincidencias se reintenta por similitud del nombre, para tolerar
+    /// errores de tipeo.
+    pub async fn search_productos(
+        &self,
+        query: &str,
+        limit: Option<i64>,
+    ) -> AppResult<ProductosBusquedaResponseDTO> {
+        let query = query.trim();
+        if query.is_empty() {
             return Err(AppError::BadRequest("El término de búsqueda no puede estar vacío".into()));
         }
-        
-        let productos = self.repository.search(query).await?;
-        Ok(productos.into())
+        let limit = limit.unwrap_or(BUSQUEDA_LIMITE_DEFAULT).clamp(1, BUSQUEDA_LIMITE_MAX);
+
+        let mut modo = ModoBusqueda::Texto;
+        let mut resultados = self.repository.search(query, limit).await?;
+        if resultados.is_empty() {
+            modo = ModoBusqueda::Similitud;
+            resultados = self.repository.search_similares(query, limit).await?;
+        }
+
+        Ok(ProductosBusquedaResponseDTO {
+            q: query.to_string(),
+            modo,
+            total: resultados.len(),
+            productos: resultados.into_iter().map(Into::into).collect(),
+        })
     }
 
     // ========================================================================
@@ -78,7 +166,7 @@ impl ProductoService {
     // ========================================================================
 
     /// Crea un nuevo producto
-    pub async fn create_producto(&self, dto: CreateProductoDTO) -> AppResult<ProductoResponseDTO> {
+    pub async fn create_producto(&self, actor: ActorMovimiento, dto: CreateProductoDTO) -> AppResult<ProductoResponseDTO> {
         // Validar nombre
         if dto.nombre_producto.trim().is_empty() {
             return Err(AppError::BadRequest("El nombre del producto es requerido".into()));
@@ -89,11 +177,20 @@ impl ProductoService {
             return Err(AppError::BadRequest("El precio debe ser mayor a 0".into()));
         }
 
-        // Validar stock inicial
-        let stock = dto.stock.unwrap_or(0);
-        if stock < 0 {
-            return Err(AppError::BadRequest("El stock no puede ser negativo".into()));
-        }
+        // Validar stock inicial: entra en un almacén activo
+        let stock_inicial = match validar_stock_inicial(dto.stock, dto.id_almacen)? {
+            Some((id_almacen, stock)) => {
+                self.verificar_almacen(id_almacen, "id_almacen", true).await?;
+                Some(NuevoMovimientoStock {
+                    id_almacen,
+                    cantidad: stock,
+                    motivo: MOTIVO_STOCK_INICIAL.to_string(),
+                    actor: Some(actor),
+                    id_pedido: None,
+                })
+            }
+            None => None,
+        };
 
         // Validar SKU único si se proporciona
         if let Some(ref sku) = dto.sku {
@@ -115,20 +212,22 @@ impl ProductoService {
         let precio = Decimal::try_from(dto.precio)
             .map_err(|_| AppError::BadRequest("Precio inválido".into()))?;
 
+        let id_categoria = self.resolver_categoria(dto.id_categoria, dto.categoria.as_deref()).await?;
+
         let producto = self
             .repository
             .create(
                 dto.nombre_producto.trim(),
                 dto.descripcion.as_deref(),
                 precio,
-                stock,
-                dto.categoria.as_deref(),
+                id_categoria,
                 dto.sku.as_deref(),
+                stock_inicial.as_ref(),
             )
             .await?;
 
         tracing::info!("Producto creado: {} ({})", producto.nombre_producto, producto.id_producto);
-        Ok(producto.into())
+        self.con_inventario(producto).await
     }
 
     /// Actualiza un producto existente
@@ -167,6 +266,15 @@ impl ProductoService {
             }
         }
 
+        // `id_categoria: null` quita la categoría; ausente, se resuelve desde `categoria`
+        let id_categoria = match dto.id_categoria {
+            Some(None) => Some(None),
+            id => self
+                .resolver_categoria(id.flatten(), dto.categoria.as_deref())
+                .await?
+                .map(Some),
+        };
+
         let producto = self
             .repository
             .update(
@@ -174,40 +282,219 @@ impl ProductoService {
                 dto.nombre_producto.as_deref(),
                 dto.descripcion.as_deref(),
                 precio,
-                dto.categoria.as_deref(),
+                id_categoria,
                 dto.sku.as_deref(),
             )
             .await?;
 
         tracing::info!("Producto actualizado: {}", id);
-        Ok(producto.into())
+        self.con_inventario(producto).await
     }
 
-    /// Actualiza el stock de un producto
-    pub async fn update_stock(&self, id: Uuid, dto: UpdateStockDTO) -> AppResult<ProductoResponseDTO> {
+    /// Ajusta el stock de un producto en un almacén y lo registra en el kardex
+    pub async fn update_stock(&self, id: Uuid, actor: ActorMovimiento, dto: UpdateStockDTO) -> AppResult<ProductoResponseDTO> {
+        let motivo = validar_ajuste(&dto)?;
+
         // Verificar que existe
-        let producto_actual = self
-            .repository
+        self.repository
             .find_by_id(id)
             .await?
             .ok_or_else(|| AppError::NotFound(format!("Producto con ID {} no encontrado", id)))?;
 
-        // Verificar que el stock resultante no sea negativo
-        let nuevo_stock = producto_actual.stock + dto.cantidad;
-        if nuevo_stock < 0 {
-            return Err(AppError::BadRequest(format!(
-                "Stock insuficiente. Stock actual: {}, ajuste solicitado: {}",
-                producto_actual.stock, dto.cantidad
-            )));
-        }
+        // Las salidas se permiten desde almacenes inactivos, para vaciarlos
+        self.verificar_almacen(dto.id_almacen, "id_almacen", dto.cantidad > 0).await?;
 
-        let producto = self.repository.update_stock(id, dto.cantidad).await?;
+        // El stock suficiente (total y del almacén) lo garantiza el repositorio
+        // dentro de la transacción
+        let (producto, movimiento) = self
+            .repository
+            .update_stock(
+                id,
+                &NuevoMovimientoStock {
+                    id_almacen: dto.id_almacen,
+                    cantidad: dto.cantidad,
+                    motivo,
+                    actor: Some(actor),
+                    id_pedido: dto.id_pedido,
+                },
+            )
+            .await?;
 
         tracing::info!(
-            "Stock actualizado para {}: {} -> {} (motivo: {:?})",
-            id, producto_actual.stock, producto.stock, dto.motivo
+            "Stock actualizado para {} en almacén {}: {:+} -> {} por {} (motivo: {})",
+            id, dto.id_almacen, movimiento.cantidad, movimiento.saldo, actor, movimiento.motivo
         );
-        Ok(producto.into())
+        self.con_inventario(producto).await
+    }
+
+    /// Mueve stock de un producto entre dos almacenes; el total no cambia
+    pub async fn transferir_stock(
+        &self,
+        id: Uuid,
+        actor: ActorMovimiento,
+        dto: TransferenciaStockDTO,
+    ) -> AppResult<TransferenciaStockResponseDTO> {
+        let motivo = validar_transferencia(&dto)?;
+
+        self.verificar_almacen(dto.id_almacen_origen, "id_almacen_origen", false).await?;
+        self.verificar_almacen(dto.id_almacen_destino, "id_almacen_destino", true).await?;
+
+        let (producto, movimientos) = self
+            .repository
+            .transferir_stock(
+                id,
+                &NuevaTransferenciaStock {
+                    id_almacen_origen: dto.id_almacen_origen,
+                    id_almacen_destino: dto.id_almacen_destino,
+                    cantidad: dto.cantidad,
+                    motivo,
+                    actor: Some(actor),
+                },
+            )
+            .await?;
+
+        let id_transferencia = movimientos
+            .first()
+            .and_then(|m| m.id_transferencia)
+            .ok_or_else(|| AppError::Internal("La transferencia no registró movimientos".to_string()))?;
+
+        tracing::info!(
+            "Transferencia {} de {}: {} unidad(es) de {} a {} por {}",
+            id_transferencia, id, dto.cantidad, dto.id_almacen_origen, dto.id_almacen_destino, actor
+        );
+        Ok(TransferenciaStockResponseDTO {
+            id_transferencia,
+            producto: self.con_inventario(producto).await?,
+            movimientos: movimientos.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Kardex de un producto, del movimiento más reciente al más antiguo
+    pub async fn list_movimientos(
+        &self,
+        id: Uuid,
+        query: MovimientosStockQueryDTO,
+    ) -> AppResult<MovimientosStockResponseDTO> {
+        let producto = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Producto con ID {} no encontrado", id)))?;
+
+        if let (Some(desde), Some(hasta)) = (query.desde, query.hasta)
+            && desde > hasta
+        {
+            return Err(AppError::InvalidField {
+                field: "hasta",
+                message: "hasta debe ser posterior a desde".to_string(),
+            });
+        }
+
+        let limit = query.limit.unwrap_or(KARDEX_PAGE_DEFAULT).clamp(1, KARDEX_PAGE_MAX);
+        let after = query
+            .cursor
+            .as_deref()
+            .map(Cursor::<MovimientoCursor>::decode)
+            .transpose()?
+            .map(|c| (c.created_at, c.id));
+
+        let page = MovimientosStockQuery {
+            id_producto: id,
+            desde: query.desde,
+            hasta: query.hasta,
+            id_almacen: query.id_almacen,
+            after,
+            limit,
+        };
+
+        // Se pide un movimiento extra para saber si hay página siguiente
+        let fetch = MovimientosStockQuery { limit: limit + 1, ..page.clone() };
+        let (mut movimientos, total) = tokio::try_join!(
+            self.repository.find_movimientos(&fetch),
+            self.repository.count_movimientos(&page),
+        )?;
+
+        let next_cursor = if movimientos.len() as i64 > limit {
+            movimientos.truncate(limit as usize);
+            movimientos
+                .last()
+                .map(|last| {
+                    Cursor(MovimientoCursor {
+                        created_at: last.created_at,
+                        id: last.id_movimiento,
+                    })
+                    .encode()
+                })
+                .transpose()?
+        } else {
+            None
+        };
+
+        Ok(MovimientosStockResponseDTO {
+            id_producto: producto.id_producto,
+            stock: producto.stock,
+            total,
+            movimientos: movimientos.into_iter().map(Into::into).collect(),
+            next_cursor,
+        })
+    }
+
+    /// Compara el stock de cada producto con la suma de su kardex y de sus
+    /// almacenes, y el de cada almacén con sus movimientos
+    pub async fn conciliar_stock(&self) -> AppResult<ConciliacionStockResponseDTO> {
+        let (descuadres, productos_revisados) = tokio::try_join!(
+            self.repository.find_descuadres_stock(),
+            self.repository.count_all(),
+        )?;
+
+        if !descuadres.is_empty() {
+            tracing::warn!("Conciliación de stock: {} producto(s) con descuadre", descuadres.len());
+        }
+
+        Ok(ConciliacionStockResponseDTO {
+            conciliado: descuadres.is_empty(),
+            productos_revisados,
+            descuadres: descuadres.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Asigna a un almacén activo el stock que no está en ninguno (la migración 016
+    /// lo deja así si no había almacenes). Devuelve cada producto con las unidades
+    /// asignadas; con `dry_run` solo calcula las que se asignarían.
+    pub async fn asignar_stock_sin_almacen(
+        &self,
+        id_almacen: Uuid,
+        dry_run: bool,
+    ) -> AppResult<Vec<(DescuadreStock, i64)>> {
+        self.verificar_almacen(id_almacen, "id_almacen", true).await?;
+
+        let mut asignados = Vec::new();
+        for descuadre in self.repository.find_descuadres_stock().await? {
+            let pendientes = unidades_sin_almacen(&descuadre);
+            if pendientes == 0 {
+                continue;
+            }
+
+            let unidades = if dry_run {
+                pendientes
+            } else {
+                // El repositorio recalcula las unidades con el producto bloqueado
+                let unidades = self
+                    .repository
+                    .asignar_stock_sin_almacen(descuadre.id_producto, id_almacen, MOTIVO_ASIGNACION_SIN_ALMACEN)
+                    .await?;
+                tracing::info!(
+                    "Stock sin almacén de {} asignado a {}: {} unidad(es)",
+                    descuadre.id_producto, id_almacen, unidades
+                );
+                i64::from(unidades)
+            };
+            if unidades > 0 {
+                asignados.push((descuadre, unidades));
+            }
+        }
+
+        Ok(asignados)
     }
 
     /// Cambia el estado de un producto (activar/desactivar)
@@ -218,21 +505,18 @@ impl ProductoService {
             "Estado del producto {} cambiado a: {}",
             id, if dto.estado { "activo" } else { "inactivo" }
         );
-        Ok(producto.into())
+        self.con_inventario(producto).await
     }
 
-    /// Elimina un producto (hard delete)
+    /// Elimina un producto (hard delete); los que tienen kardex solo se desactivan
     pub async fn delete_producto(&self, id: Uuid) -> AppResult<()> {
-        // Verificar que existe
-        self.repository
-            .find_by_id(id)
-            .await?
-            .ok_or_else(|| AppError::NotFound(format!("Producto con ID {} no encontrado", id)))?;
-
         // TODO: Verificar que no esté asociado a pedidos activos
-        
-        self.repository.delete(id).await?;
-        tracing::info!("Producto eliminado: {}", id);
+
+        if self.repository.delete(id).await? {
+            tracing::info!("Producto eliminado: {}", id);
+        } else {
+            tracing::info!("Producto {} desactivado en lugar de eliminado: tiene movimientos de stock", id);
+        }
         Ok(())
     }
 
@@ -240,6 +524,277 @@ impl ProductoService {
     // HELPERS PRIVADOS
     // ========================================================================
 
+    /// Página del catálogo con su total, cursor siguiente, conteo por categoría
+    /// y disponibilidad de cada producto
+    async fn catalogo(
+        &self,
+        page: ProductoPageQuery,
+        numero_pagina: Option<i64>,
+        cercano: Option<AlmacenCercano>,
+    ) -> AppResult<ProductosListResponseDTO> {
+        let limit = page.limit;
+
+        // Se pide un producto extra para saber si hay página siguiente
+        let fetch = ProductoPageQuery { limit: limit + 1, ..page.clone() };
+        let (mut productos, total, counts) = tokio::try_join!(
+            self.repository.find_page(&fetch),
+            self.repository.count(&page.filter),
+            self.repository.count_by_categoria(&page.filter),
+        )?;
+
+        let next_cursor = if productos.len() as i64 > limit {
+            productos.truncate(limit as usize);
+            productos
+                .last()
+                .map(|last| {
+                    Cursor(ProductoCursor {
+                        sort: page.sort.as_str().to_string(),
+                        desc: page.descending,
+                        value: page.sort.key(last),
+                        id: last.id_producto,
+                    })
+                    .encode()
+                })
+                .transpose()?
+        } else {
+            None
+        };
+
+        let ids: Vec<Uuid> = productos.iter().map(|p| p.id_producto).collect();
+        let disponibilidad: HashMap<Uuid, i64> = self
+            .repository
+            .find_disponibilidad(&ids, page.filter.id_almacen)
+            .await?
+            .into_iter()
+            .collect();
+
+        Ok(ProductosListResponseDTO {
+            productos: productos
+                .into_iter()
+                .map(|p| {
+                    let disponible = disponibilidad.get(&p.id_producto).copied().unwrap_or(0);
+                    ProductoResponseDTO { disponible: Some(disponible), ..p.into() }
+                })
+                .collect(),
+            total,
+            paginacion: Some(PaginacionDTO {
+                limit,
+                page: numero_pagina,
+                total_pages: (total + limit - 1) / limit,
+                next_cursor,
+            }),
+            categorias: counts
+                .into_iter()
+                .map(|c| CategoriaFacetaDTO {
+                    id_categoria: c.id_categoria,
+                    slug: c.slug,
+                    categoria: c.nombre,
+                    total: c.total,
+                })
+                .collect(),
+            almacen_cercano: cercano.map(Into::into),
+        })
+    }
+
+    /// Almacén para `disponibilidad=cercano`; None con la disponibilidad total
+    async fn almacen_cercano(&self, query: &ProductosQueryDTO) -> AppResult<Option<AlmacenCercano>> {
+        let Some((lat, lng)) = coordenadas_cercano(query)? else {
+            return Ok(None);
+        };
+
+        let almacen = self
+            .almacenes
+            .find_almacen_cercano(lat, lng)
+            .await?
+            .ok_or_else(|| AppError::BadRequest("No hay almacenes activos".to_string()))?;
+        Ok(Some(almacen))
+    }
+
+    /// Verifica que el ID sea un almacén; `para_entrada` exige además que esté activo
+    async fn verificar_almacen(&self, id: Uuid, field: &'static str, para_entrada: bool) -> AppResult<()> {
+        let almacen = self.almacenes.find_by_id(id).await?.filter(|d| d.tipo == "almacen");
+        match almacen {
+            None => Err(AppError::InvalidField {
+                field,
+                message: format!("El almacén {} no existe", id),
+            }),
+            Some(almacen) if para_entrada && !almacen.activo => Err(AppError::InvalidField {
+                field,
+                message: format!("El almacén {} está inactivo", id),
+            }),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Stock por almacén de varios productos
+    async fn inventario_por_producto(&self, ids: &[Uuid]) -> AppResult<HashMap<Uuid, Vec<InventarioAlmacenDTO>>> {
+        let mut inventario: HashMap<Uuid, Vec<InventarioAlmacenDTO>> = HashMap::new();
+        for item in self.repository.find_inventario(ids).await? {
+            inventario.entry(item.id_producto).or_default().push(item.into());
+        }
+        Ok(inventario)
+    }
+
+    /// Respuesta de administración: el producto con su stock por almacén
+    async fn con_inventario(&self, producto: Producto) -> AppResult<ProductoResponseDTO> {
+        let inventario = self
+            .inventario_por_producto(&[producto.id_producto])
+            .await?
+            .remove(&producto.id_producto)
+            .unwrap_or_default();
+        Ok(ProductoResponseDTO { inventario: Some(inventario), ..producto.into() })
+    }
+
+    /// Respuesta pública: el producto con las unidades de sus almacenes activos
+    async fn con_disponibilidad(&self, producto: Producto) -> AppResult<ProductoResponseDTO> {
+        let disponible = self
+            .repository
+            .find_disponibilidad(&[producto.id_producto], None)
+            .await?
+            .first()
+            .map(|(_, total)| *total)
+            .unwrap_or(0);
+        Ok(ProductoResponseDTO { disponible: Some(disponible), ..producto.into() })
+    }
+
+    /// Valida los parámetros del catálogo y los convierte en una consulta de página
+    fn page_query(query: ProductosQueryDTO) -> AppResult<ProductoPageQuery> {
+        let precio = |field: &'static str, valor: Option<Decimal>| -> AppResult<Option<Decimal>> {
+            match valor {
+                Some(v) if v.is_sign_negative() && !v.is_zero() => Err(AppError::InvalidField {
+                    field,
+                    message: "El precio debe ser un número mayor o igual a 0".to_string(),
+                }),
+                _ => Ok(valor),
+            }
+        };
+        let precio_min = precio("precio_min", query.precio_min)?;
+        let precio_max = precio("precio_max", query.precio_max)?;
+        if let (Some(min), Some(max)) = (precio_min, precio_max)
+            && min > max
+        {
+            return Err(AppError::InvalidField {
+                field: "precio_max",
+                message: "precio_max debe ser mayor o igual a precio_min".to_string(),
+            });
+        }
+
+        let sort = match query.sort.as_deref() {
+            None => ProductoSortField::Nombre,
+            Some(value) => ProductoSortField::parse(value).ok_or_else(|| AppError::InvalidField {
+                field: "sort",
+                message: format!("No se puede ordenar por '{}'. Valores: precio, nombre, created_at", value),
+            })?,
+        };
+
+        let descending = match query.order.as_deref() {
+            None | Some("asc") => false,
+            Some("desc") => true,
+            Some(_) => {
+                return Err(AppError::InvalidField {
+                    field: "order",
+                    message: "order debe ser 'asc' o 'desc'".to_string(),
+                });
+            }
+        };
+
+        let limit = query.limit.unwrap_or(CATALOGO_PAGE_DEFAULT).clamp(1, CATALOGO_PAGE_MAX);
+
+        let offset = match query.page {
+            None => 0,
+            Some(_) if query.cursor.is_some() => {
+                return Err(AppError::InvalidField {
+                    field: "page",
+                    message: "Use page o cursor, no ambos".to_string(),
+                });
+            }
+            Some(page) if page >= 1 => (page - 1).saturating_mul(limit),
+            Some(_) => {
+                return Err(AppError::InvalidField {
+                    field: "page",
+                    message: "page debe ser mayor o igual a 1".to_string(),
+                });
+            }
+        };
+
+        let after = match query.cursor.as_deref() {
+            None => None,
+            Some(raw) => {
+                let cursor = ProductoCursor::decode(raw)?;
+                if cursor.sort != sort.as_str() || cursor.desc != descending {
+                    return Err(AppError::BadRequest(
+                        "El cursor no corresponde al orden solicitado".to_string(),
+                    ));
+                }
+                Some((cursor.value, cursor.id))
+            }
+        };
+
+        let categorias = query
+            .categoria
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(slugify)
+            .filter(|c| !c.is_empty())
+            .collect();
+
+        Ok(ProductoPageQuery {
+            filter: ProductoFilter {
+                categorias,
+                precio_min,
+                precio_max,
+                solo_en_stock: query.en_stock.unwrap_or(false),
+                id_almacen: None,
+            },
+            sort,
+            descending,
+            after,
+            offset,
+            limit,
+        })
+    }
+
+    /// Categoría de un producto a partir de `id_categoria` o, si no se envía, del
+    /// texto `categoria` (slug, nombre o texto libre anterior a las categorías).
+    /// Solo se asignan categorías activas.
+    async fn resolver_categoria(&self, id: Option<Uuid>, texto: Option<&str>) -> AppResult<Option<Uuid>> {
+        let (categoria, field) = if let Some(id) = id {
+            match self.categorias.find_by_id(id).await? {
+                Some(categoria) => (categoria, "id_categoria"),
+                None => {
+                    return Err(AppError::InvalidField {
+                        field: "id_categoria",
+                        message: format!("La categoría {} no existe", id),
+                    })
+                }
+            }
+        } else {
+            let Some(texto) = texto.map(str::trim).filter(|t| !t.is_empty()) else {
+                return Ok(None);
+            };
+
+            let encontrada = match self.categorias.find_by_slug(&slugify(texto)).await? {
+                Some(categoria) => Some(categoria),
+                None => self.categorias.find_by_texto_legado(texto).await?,
+            };
+            match encontrada {
+                Some(categoria) => (categoria, "categoria"),
+                None => {
+                    return Err(AppError::InvalidField {
+                        field: "categoria",
+                        message: format!(
+                            "No existe la categoría '{}'. Créela en /api/admin/categorias o envíe id_categoria",
+                            texto
+                        ),
+                    })
+                }
+            }
+        };
+
+        categoria_asignable(&categoria, field).map(Some)
+    }
+
     /// Valida el formato de un SKU
     fn validar_sku(sku: &str) -> bool {
         !sku.is_empty() 
@@ -247,3 +802,459 @@ impl ProductoService {
             && sku.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
     }
 }
+
+/// Una categoría desactivada no admite productos nuevos ni reasignados
+fn categoria_asignable(categoria: &Categoria, field: &'static str) -> AppResult<Uuid> {
+    if !categoria.activo {
+        return Err(AppError::InvalidField {
+            field,
+            message: format!("La categoría '{}' está desactivada", categoria.nombre),
+        });
+    }
+    Ok(categoria.id_categoria)
+}
+
+/// Motivo de un movimiento de stock: requerido, sin espacios sobrantes
+fn validar_motivo(motivo: Option<&str>) -> AppResult<String> {
+    let motivo = motivo.map(str::trim).unwrap_or_default();
+    if motivo.is_empty() || motivo.chars().count() > MOTIVO_MAX {
+        return Err(AppError::InvalidField {
+            field: "motivo",
+            message: format!("El motivo es requerido (máximo {} caracteres)", MOTIVO_MAX),
+        });
+    }
+    Ok(motivo.to_string())
+}
+
+/// Stock inicial de un producto nuevo: no negativo y, si hay unidades, con el
+/// almacén que las recibe. None = sin stock inicial.
+/// Unidades del producto que no están en ningún almacén
+fn unidades_sin_almacen(descuadre: &DescuadreStock) -> i64 {
+    (i64::from(descuadre.stock) - descuadre.suma_almacenes).max(0)
+}
+
+fn validar_stock_inicial(stock: Option<i32>, id_almacen: Option<Uuid>) -> AppResult<Option<(Uuid, i32)>> {
+    let stock = stock.unwrap_or(0);
+    if stock < 0 {
+        return Err(AppError::BadRequest("El stock no puede ser negativo".into()));
+    }
+    match id_almacen {
+        _ if stock == 0 => Ok(None),
+        Some(id_almacen) => Ok(Some((id_almacen, stock))),
+        None => Err(AppError::InvalidField {
+            field: "id_almacen",
+            message: "Indique el almacén que recibe el stock inicial".to_string(),
+        }),
+    }
+}
+
+/// Ajuste de stock: cantidad distinta de 0 y motivo. Que el almacén no quede en
+/// negativo lo garantiza el repositorio dentro de la transacción.
+fn validar_ajuste(dto: &UpdateStockDTO) -> AppResult<String> {
+    if dto.cantidad == 0 {
+        return Err(AppError::InvalidField {
+            field: "cantidad",
+            message: "La cantidad debe ser distinta de 0".to_string(),
+        });
+    }
+    validar_motivo(dto.motivo.as_deref())
+}
+
+/// Transferencia: cantidad positiva entre dos almacenes distintos, con motivo
+fn validar_transferencia(dto: &TransferenciaStockDTO) -> AppResult<String> {
+    if dto.cantidad <= 0 {
+        return Err(AppError::InvalidField {
+            field: "cantidad",
+            message: "La cantidad debe ser mayor a 0".to_string(),
+        });
+    }
+    if dto.id_almacen_origen == dto.id_almacen_destino {
+        return Err(AppError::InvalidField {
+            field: "id_almacen_destino",
+            message: "El almacén de destino debe ser distinto del de origen".to_string(),
+        });
+    }
+    validar_motivo(Some(&dto.motivo))
+}
+
+/// Coordenadas del cliente con `disponibilidad=cercano`; None con la disponibilidad total
+fn coordenadas_cercano(query: &ProductosQueryDTO) -> AppResult<Option<(f64, f64)>> {
+    match query.disponibilidad.as_deref() {
+        None | Some("total") => return Ok(None),
+        Some("cercano") => {}
+        Some(_) => {
+            return Err(AppError::InvalidField {
+                field: "disponibilidad",
+                message: "disponibilidad debe ser 'total' o 'cercano'".to_string(),
+            });
+        }
+    }
+
+    let (Some(lat), Some(lng)) = (query.lat, query.lng) else {
+        return Err(AppError::InvalidField {
+            field: "lat",
+            message: "Con disponibilidad=cercano se requieren lat y lng".to_string(),
+        });
+    };
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(AppError::InvalidField {
+            field: "lat",
+            message: "Latitud debe estar entre -90 y 90".to_string(),
+        });
+    }
+    if !(-180.0..=180.0).contains(&lng) {
+        return Err(AppError::InvalidField {
+            field: "lng",
+            message: "Longitud debe estar entre -180 y 180".to_string(),
+        });
+    }
+    Ok(Some((lat, lng)))
+}
+
+/// Cursor opaco del catálogo: orden solicitado y posición del último producto entregado
+#[derive(Debug, Serialize, Deserialize)]
+struct ProductoCursor {
+    sort: String,
+    desc: bool,
+    value: String,
+    id: Uuid,
+}
+
+impl ProductoCursor {
+    fn decode(raw: &str) -> AppResult<Self> {
+        // El valor se castea en SQL según la columna: validarlo antes de consultar
+        Cursor::decode_valid(raw, |cursor: &Self| match ProductoSortField::parse(&cursor.sort) {
+            Some(ProductoSortField::Precio) => cursor.value.parse::<Decimal>().is_ok(),
+            Some(ProductoSortField::CreatedAt) => cursor.value.parse::<DateTime<Utc>>().is_ok(),
+            Some(ProductoSortField::Nombre) => true,
+            None => false,
+        })
+    }
+}
+
+/// Cursor opaco del kardex: fecha e id del último movimiento entregado
+#[derive(Debug, Serialize, Deserialize)]
+struct MovimientoCursor {
+    created_at: DateTime<Utc>,
+    id: Uuid,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::Query;
+    use axum::http::Uri;
+    use std::str::FromStr;
+
+    fn query(params: &str) -> ProductosQueryDTO {
+        let uri = Uri::from_str(&format!("/api/productos?{}", params)).unwrap();
+        Query::<ProductosQueryDTO>::try_from_uri(&uri).unwrap().0
+    }
+
+    fn cursor(sort: &str, desc: bool, value: &str) -> String {
+        Cursor(ProductoCursor {
+            sort: sort.to_string(),
+            desc,
+            value: value.to_string(),
+            id: Uuid::nil(),
+        })
+        .encode()
+        .unwrap()
+    }
+
+    fn campo_invalido(result: AppResult<ProductoPageQuery>) -> Option<&'static str> {
+        match result {
+            Err(AppError::InvalidField { field, .. }) => Some(field),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn valores_por_defecto() {
+        let page = ProductoService::page_query(query("")).unwrap();
+
+        assert_eq!(page.sort, ProductoSortField::Nombre);
+        assert!(!page.descending);
+        assert_eq!((page.offset, page.limit), (0, CATALOGO_PAGE_DEFAULT));
+        assert!(page.after.is_none());
+        assert!(page.filter.categorias.is_empty());
+        assert!(!page.filter.solo_en_stock);
+    }
+
+    #[test]
+    fn precios_como_decimal_exacto() {
+        let page = ProductoService::page_query(query("precio_min=10.10&precio_max=20.35")).unwrap();
+
+        assert_eq!(page.filter.precio_min, Some(Decimal::new(1010, 2)));
+        assert_eq!(page.filter.precio_max, Some(Decimal::new(2035, 2)));
+    }
+
+    #[test]
+    fn rechaza_precios_negativos_o_invertidos() {
+        assert_eq!(campo_invalido(ProductoService::page_query(query("precio_min=-1"))), Some("precio_min"));
+        assert_eq!(
+            campo_invalido(ProductoService::page_query(query("precio_min=30&precio_max=20"))),
+            Some("precio_max")
+        );
+        assert!(ProductoService::page_query(query("precio_min=0&precio_max=0")).is_ok());
+    }
+
+    #[test]
+    fn precio_no_numerico_no_se_deserializa() {
+        let uri = Uri::from_static("/api/productos?precio_min=abc");
+        assert!(Query::<ProductosQueryDTO>::try_from_uri(&uri).is_err());
+    }
+
+    #[test]
+    fn orden_y_direccion() {
+        let page = ProductoService::page_query(query("sort=precio&order=desc")).unwrap();
+        assert_eq!(page.sort, ProductoSortField::Precio);
+        assert!(page.descending);
+
+        assert_eq!(campo_invalido(ProductoService::page_query(query("sort=stock"))), Some("sort"));
+        assert_eq!(campo_invalido(ProductoService::page_query(query("order=up"))), Some("order"));
+    }
+
+    #[test]
+    fn limit_se_acota() {
+        assert_eq!(ProductoService::page_query(query("limit=0")).unwrap().limit, 1);
+        assert_eq!(ProductoService::page_query(query("limit=1000")).unwrap().limit, CATALOGO_PAGE_MAX);
+    }
+
+    #[test]
+    fn page_se_convierte_en_offset() {
+        let page = ProductoService::page_query(query("page=3&limit=10")).unwrap();
+        assert_eq!((page.offset, page.limit), (20, 10));
+
+        assert_eq!(campo_invalido(ProductoService::page_query(query("page=0"))), Some("page"));
+    }
+
+    #[test]
+    fn page_y_cursor_son_excluyentes() {
+        let raw = cursor("nombre", false, "Cable");
+        let result = ProductoService::page_query(query(&format!("page=2&cursor={}", raw)));
+        assert_eq!(campo_invalido(result), Some("page"));
+    }
+
+    #[test]
+    fn cursor_del_mismo_orden() {
+        let raw = cursor("precio", true, "19.99");
+        let page = ProductoService::page_query(query(&format!("sort=precio&order=desc&cursor={}", raw))).unwrap();
+
+        assert_eq!(page.after, Some(("19.99".to_string(), Uuid::nil())));
+    }
+
+    #[test]
+    fn rechaza_cursor_de_otro_orden() {
+        let raw = cursor("precio", true, "19.99");
+
+        let result = ProductoService::page_query(query(&format!("sort=precio&cursor={}", raw)));
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+
+        let result = ProductoService::page_query(query(&format!("sort=nombre&order=desc&cursor={}", raw)));
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[test]
+    fn rechaza_cursor_con_valor_que_no_corresponde_a_la_columna() {
+        let invalido = |result: AppResult<ProductoPageQuery>| {
+            matches!(result, Err(AppError::BadRequest(msg)) if msg == "Cursor inválido")
+        };
+        for (sort, value) in [("precio", "barato"), ("created_at", "ayer")] {
+            let raw = cursor(sort, false, value);
+            assert!(invalido(ProductoService::page_query(query(&format!("sort={}&cursor={}", sort, raw)))), "{}", sort);
+        }
+
+        let raw = cursor("stock", false, "1");
+        assert!(invalido(ProductoService::page_query(query(&format!("cursor={}", raw)))));
+    }
+
+    #[test]
+    fn categorias_separadas_por_coma_como_slugs() {
+        let page = ProductoService::page_query(query("categoria=Electr%C3%B3nica,%20,Hogar%20y%20Jard%C3%ADn")).unwrap();
+        assert_eq!(page.filter.categorias, ["electronica", "hogar-y-jardin"]);
+    }
+
+    #[test]
+    fn clave_de_orden_del_cursor() {
+        let producto = Producto {
+            id_producto: Uuid::nil(),
+            nombre_producto: "Cable".to_string(),
+            descripcion: None,
+            precio: Decimal::new(1050, 2),
+            stock: 0,
+            categoria: None,
+            id_categoria: None,
+            sku: None,
+            estado: true,
+            created_at: DateTime::parse_from_rfc3339("2025-01-15T10:30:00Z").unwrap().with_timezone(&Utc),
+            updated_at: Utc::now(),
+        };
+
+        assert_eq!(ProductoSortField::Precio.key(&producto), "10.50");
+        assert_eq!(ProductoSortField::Nombre.key(&producto), "Cable");
+        assert_eq!(ProductoSortField::CreatedAt.key(&producto), "2025-01-15T10:30:00.000000Z");
+    }
+
+    fn categoria(activo: bool) -> Categoria {
+        Categoria {
+            id_categoria: Uuid::new_v4(),
+            nombre: "Audio".to_string(),
+            slug: "audio".to_string(),
+            id_padre: None,
+            orden: 0,
+            activo,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn asigna_categorias_activas() {
+        let activa = categoria(true);
+        assert_eq!(categoria_asignable(&activa, "id_categoria").unwrap(), activa.id_categoria);
+    }
+
+    #[test]
+    fn rechaza_categorias_desactivadas_en_el_campo_enviado() {
+        let inactiva = categoria(false);
+
+        for field in ["id_categoria", "categoria"] {
+            match categoria_asignable(&inactiva, field) {
+                Err(AppError::InvalidField { field: f, message }) => {
+                    assert_eq!(f, field);
+                    assert!(message.contains("desactivada"));
+                }
+                otro => panic!("se esperaba InvalidField, no {:?}", otro),
+            }
+        }
+    }
+
+    fn campo<T: std::fmt::Debug>(result: AppResult<T>) -> &'static str {
+        match result {
+            Err(AppError::InvalidField { field, .. }) => field,
+            otro => panic!("se esperaba InvalidField, no {:?}", otro),
+        }
+    }
+
+    fn transferencia(cantidad: i32, origen: Uuid, destino: Uuid, motivo: &str) -> TransferenciaStockDTO {
+        TransferenciaStockDTO {
+            id_almacen_origen: origen,
+            id_almacen_destino: destino,
+            cantidad,
+            motivo: motivo.to_string(),
+        }
+    }
+
+    fn ajuste(cantidad: i32, motivo: Option<&str>) -> UpdateStockDTO {
+        UpdateStockDTO {
+            id_almacen: Uuid::new_v4(),
+            cantidad,
+            motivo: motivo.map(str::to_string),
+            id_pedido: None,
+        }
+    }
+
+    #[test]
+    fn transferencia_valida_devuelve_el_motivo_limpio() {
+        let dto = transferencia(5, Uuid::new_v4(), Uuid::new_v4(), "  Reposición Guayaquil ");
+        assert_eq!(validar_transferencia(&dto).unwrap(), "Reposición Guayaquil");
+    }
+
+    #[test]
+    fn transferencia_exige_cantidad_positiva() {
+        for cantidad in [0, -3] {
+            let dto = transferencia(cantidad, Uuid::new_v4(), Uuid::new_v4(), "Reposición");
+            assert_eq!(campo(validar_transferencia(&dto)), "cantidad");
+        }
+    }
+
+    #[test]
+    fn transferencia_entre_el_mismo_almacen() {
+        let almacen = Uuid::new_v4();
+        assert_eq!(campo(validar_transferencia(&transferencia(5, almacen, almacen, "Reposición"))), "id_almacen_destino");
+    }
+
+    #[test]
+    fn transferencia_sin_motivo_o_demasiado_largo() {
+        let (origen, destino) = (Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(campo(validar_transferencia(&transferencia(5, origen, destino, "   "))), "motivo");
+
+        let largo = "x".repeat(MOTIVO_MAX + 1);
+        assert_eq!(campo(validar_transferencia(&transferencia(5, origen, destino, &largo))), "motivo");
+        assert!(validar_transferencia(&transferencia(5, origen, destino, &largo[1..])).is_ok());
+    }
+
+    #[test]
+    fn ajuste_acepta_entradas_y_salidas_pero_no_cero() {
+        assert_eq!(validar_ajuste(&ajuste(10, Some("Compra"))).unwrap(), "Compra");
+        assert_eq!(validar_ajuste(&ajuste(-10, Some("Merma"))).unwrap(), "Merma");
+        assert_eq!(campo(validar_ajuste(&ajuste(0, Some("Nada")))), "cantidad");
+        assert_eq!(campo(validar_ajuste(&ajuste(-1, None))), "motivo");
+    }
+
+    #[test]
+    fn stock_inicial_no_negativo_y_con_almacen() {
+        let almacen = Uuid::new_v4();
+
+        assert!(matches!(validar_stock_inicial(Some(-1), Some(almacen)), Err(AppError::BadRequest(_))));
+        assert_eq!(validar_stock_inicial(None, None).unwrap(), None);
+        assert_eq!(validar_stock_inicial(Some(0), Some(almacen)).unwrap(), None);
+        assert_eq!(validar_stock_inicial(Some(12), Some(almacen)).unwrap(), Some((almacen, 12)));
+        assert_eq!(campo(validar_stock_inicial(Some(12), None)), "id_almacen");
+    }
+
+    fn descuadre(stock: i32, suma_movimientos: i64, suma_almacenes: i64) -> DescuadreStock {
+        DescuadreStock {
+            id_producto: Uuid::new_v4(),
+            nombre_producto: "Caja".to_string(),
+            sku: None,
+            stock,
+            suma_movimientos,
+            movimientos: 1,
+            suma_almacenes,
+            almacenes_descuadrados: 0,
+        }
+    }
+
+    #[test]
+    fn unidades_sin_almacen_son_las_que_faltan_en_los_almacenes() {
+        // Stock previo a la migración 016 sin ningún almacén
+        assert_eq!(unidades_sin_almacen(&descuadre(40, 40, 0)), 40);
+        assert_eq!(unidades_sin_almacen(&descuadre(40, 40, 15)), 25);
+    }
+
+    #[test]
+    fn unidades_sin_almacen_ignora_descuadres_de_otro_tipo() {
+        assert_eq!(unidades_sin_almacen(&descuadre(10, 12, 10)), 0);
+        assert_eq!(unidades_sin_almacen(&descuadre(10, 10, 14)), 0);
+    }
+
+    #[test]
+    fn disponibilidad_total_no_usa_coordenadas() {
+        assert_eq!(coordenadas_cercano(&query("")).unwrap(), None);
+        assert_eq!(coordenadas_cercano(&query("disponibilidad=total&lat=1&lng=2")).unwrap(), None);
+    }
+
+    #[test]
+    fn disponibilidad_cercano_con_coordenadas() {
+        assert_eq!(
+            coordenadas_cercano(&query("disponibilidad=cercano&lat=-2.17&lng=-79.92")).unwrap(),
+            Some((-2.17, -79.92))
+        );
+        assert_eq!(coordenadas_cercano(&query("disponibilidad=cercano&lat=90&lng=-180")).unwrap(), Some((90.0, -180.0)));
+    }
+
+    #[test]
+    fn disponibilidad_cercano_valida_las_coordenadas() {
+        assert_eq!(campo(coordenadas_cercano(&query("disponibilidad=cercano"))), "lat");
+        assert_eq!(campo(coordenadas_cercano(&query("disponibilidad=cercano&lat=-2.17"))), "lat");
+        assert_eq!(campo(coordenadas_cercano(&query("disponibilidad=cercano&lat=91&lng=0"))), "lat");
+        assert_eq!(campo(coordenadas_cercano(&query("disponibilidad=cercano&lat=0&lng=180.5"))), "lng");
+    }
+
+    #[test]
+    fn disponibilidad_desconocida() {
+        assert_eq!(campo(coordenadas_cercano(&query("disponibilidad=almacen"))), "disponibilidad");
+    }
+}
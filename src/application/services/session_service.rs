@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::domain::auth::{Claims, ROL_CLIENTE};
+use crate::domain::entities::{SessionRevocation, User};
+use crate::domain::repositories::{SessionRevocationRepository, UserRepository};
+use crate::shared::error::{AppError, AppResult};
+
+/// Tiempo que se reutiliza el estado de un usuario sin volver a consultar la BD
+const SESSION_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Tamaño a partir del cual se purgan entradas expiradas del caché
+const SESSION_CACHE_MAX: usize = 10_000;
+
+/// Resultado de verificar la sesión asociada a un token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionStatus {
+    Active,
+    UserInactive,
+    Revoked,
+}
+
+#[derive(Clone)]
+struct CachedUserState {
+    activo: bool,
+    email: Option<String>,
+    revocation: Option<SessionRevocation>,
+    loaded_at: Instant,
+}
+
+/// Service de sesiones: estado de cuenta y revocación de tokens
+///
+/// Si el `sub` del token no existe en public.users, se aprovisiona en el momento
+/// (rol `cliente`) y el email se mantiene sincronizado con el de los claims.
+/// Mantiene un caché en memoria por usuario para no consultar la BD en cada request.
+/// Las operaciones de este proceso (desactivar, revocar) invalidan el caché al instante;
+/// cambios hechos por fuera se ven como máximo tras `SESSION_CACHE_TTL`.
+pub struct SessionService {
+    user_repo: Arc<dyn UserRepository>,
+    revocation_repo: Arc<dyn SessionRevocationRepository>,
+    cache: RwLock<HashMap<Uuid, CachedUserState>>,
+}
+
+impl SessionService {
+    pub fn new(
+        user_repo: Arc<dyn UserRepository>,
+        revocation_repo: Arc<dyn SessionRevocationRepository>,
+    ) -> Self {
+        Self {
+            user_repo,
+            revocation_repo,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Verifica si el token sigue siendo válido para el usuario
+    pub async fn check_session(&self, claims: &Claims) -> AppResult<SessionStatus> {
+        let state = match self.cached(claims.sub) {
+            Some(state) if !email_changed(&state, claims) => state,
+            _ => self.load(claims).await?,
+        };
+
+        if !state.activo {
+            return Ok(SessionStatus::UserInactive);
+        }
+        if state.revocation.as_ref().is_some_and(|r| r.revoca(claims.iat)) {
+            return Ok(SessionStatus::Revoked);
+        }
+
+        Ok(SessionStatus::Active)
+    }
+
+    /// Revoca todas las sesiones actuales del usuario (logout forzado)
+    pub async fn revoke_sessions(
+        &self,
+        id_usuario: Uuid,
+        motivo: Option<&str>,
+        revocado_por: Option<Uuid>,
+    ) -> AppResult<SessionRevocation> {
+        let revocation = self
+            .revocation_repo
+            .revoke_all(id_usuario, motivo, revocado_por)
+            .await?;
+
+        self.invalidate(id_usuario);
+        tracing::info!("Sesiones del usuario {} revocadas ({:?})", id_usuario, motivo);
+
+        Ok(revocation)
+    }
+
+    /// Descarta el estado cacheado del usuario
+    pub fn invalidate(&self, id_usuario: Uuid) {
+        if let Ok(mut cache) = self.cache.write() {
+            cache.remove(&id_usuario);
+        }
+    }
+
+    fn cached(&self, id_usuario: Uuid) -> Option<CachedUserState> {
+        let cache = self.cache.read().ok()?;
+        cache
+            .get(&id_usuario)
+            .filter(|state| state.loaded_at.elapsed() < SESSION_CACHE_TTL)
+            .cloned()
+    }
+
+    async fn load(&self, claims: &Claims) -> AppResult<CachedUserState> {
+        let id_usuario = claims.sub;
+        let user = self
+            .user_repo
+            .find_by_id(id_usuario)
+            .await
+            .map_err(|_| AppError::Internal("Error al obtener usuario".to_string()))?;
+
+        let user = match user {
+            // Una cuenta desactivada (o anonimizada) no recupera el email desde los claims
+            Some(user) if user.activo => self.sync_email(user, claims).await,
+            Some(user) => user,
+            None => self.provision(claims).await?,
+        };
+        let revocation = self.revocation_repo.find_by_usuario(id_usuario).await?;
+
+        let state = CachedUserState {
+            activo: user.activo,
+            // Si la sincronización falló se cachea el email de los claims para
+            // no reintentar en cada request (se reintenta al expirar el caché)
+            email: claims.email.clone().or(user.email),
+            revocation,
+            loaded_at: Instant::now(),
+        };
+
+        if let Ok(mut cache) = self.cache.write() {
+            if cache.len() >= SESSION_CACHE_MAX {
+                cache.retain(|_, s| s.loaded_at.elapsed() < SESSION_CACHE_TTL);
+            }
+            cache.insert(id_usuario, state.clone());
+        }
+
+        Ok(state)
+    }
+
+    /// Crea la fila en public.users a partir de los claims (primer request del usuario)
+    async fn provision(&self, claims: &Claims) -> AppResult<User> {
+        let user = self
+            .user_repo
+            .provision(claims.sub, claims.email.as_deref(), ROL_CLIENTE)
+            .await
+            .map_err(|e| {
+                tracing::error!("No se pudo aprovisionar el usuario {}: {:?}", claims.sub, e);
+                AppError::Internal("Error al registrar usuario".to_string())
+            })?;
+
+        if user.email.is_none() && claims.email.is_some() {
+            tracing::warn!(
+                "Usuario {} aprovisionado sin email: {:?} ya pertenece a otro usuario",
+                claims.sub,
+                claims.email
+            );
+        } else {
+            tracing::info!("Usuario {} aprovisionado ({:?})", claims.sub, claims.email);
+        }
+
+        Ok(user)
+    }
+
+    /// Actualiza el email si cambió en Supabase; un fallo no bloquea el request
+    async fn sync_email(&self, user: User, claims: &Claims) -> User {
+        let email = match claims.email.as_deref() {
+            Some(email) if user.email.as_deref() != Some(email) => email,
+            _ => return user,
+        };
+
+        match self.user_repo.update_email(user.id, email).await {
+            Ok(updated) => {
+                tracing::info!("Email del usuario {} sincronizado", user.id);
+                updated
+            }
+            Err(e) => {
+                tracing::warn!("No se pudo sincronizar el email del usuario {}: {:?}", user.id, e);
+                user
+            }
+        }
+    }
+}
+
+/// Indica si el email de los claims difiere del último conocido
+fn email_changed(state: &CachedUserState, claims: &Claims) -> bool {
+    claims.email.is_some() && claims.email != state.email
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::{UserFilter, UserPageQuery};
+    use chrono::Utc;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Repositorio de usuarios en memoria que cuenta las lecturas
+    struct FakeUsers {
+        users: Mutex<HashMap<Uuid, User>>,
+        lecturas: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl UserRepository for FakeUsers {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, sqlx::Error> {
+            self.lecturas.fetch_add(1, Ordering::SeqCst);
+            Ok(self.users.lock().unwrap().get(&id).cloned())
+        }
+        async fn find_by_email(&self, _: &str) -> Result<Option<User>, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn find_page(&self, _: &UserPageQuery) -> Result<Vec<User>, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn count_by_rol(&self, _: &UserFilter) -> Result<Vec<(String, i64)>, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn create(&self, _: &User) -> Result<User, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn update(&self, _: &User) -> Result<User, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn update_role(&self, _: Uuid, _: &str) -> Result<User, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn update_status(&self, _: Uuid, _: bool) -> Result<User, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn provision(&self, id: Uuid, email: Option<&str>, rol: &str) -> Result<User, sqlx::Error> {
+            let user = self
+                .users
+                .lock()
+                .unwrap()
+                .entry(id)
+                .or_insert_with(|| usuario(id, email, rol, true))
+                .clone();
+            Ok(user)
+        }
+        async fn update_email(&self, id: Uuid, email: &str) -> Result<User, sqlx::Error> {
+            let mut users = self.users.lock().unwrap();
+            let user = users.get_mut(&id).ok_or(sqlx::Error::RowNotFound)?;
+            user.email = Some(email.to_string());
+            Ok(user.clone())
+        }
+        async fn update_foto_perfil(&self, _: Uuid, _: Option<&str>) -> Result<User, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn delete(&self, _: Uuid) -> Result<(), sqlx::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[derive(Default)]
+    struct FakeRevocations {
+        revocaciones: Mutex<HashMap<Uuid, SessionRevocation>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SessionRevocationRepository for FakeRevocations {
+        async fn find_by_usuario(&self, id_usuario: Uuid) -> AppResult<Option<SessionRevocation>> {
+            Ok(self.revocaciones.lock().unwrap().get(&id_usuario).cloned())
+        }
+        async fn revoke_all(
+            &self,
+            id_usuario: Uuid,
+            motivo: Option<&str>,
+            revocado_por: Option<Uuid>,
+        ) -> AppResult<SessionRevocation> {
+            let revocation = SessionRevocation {
+                id_usuario,
+                revocado_antes: Utc::now(),
+                motivo: motivo.map(str::to_string),
+                revocado_por,
+                updated_at: Utc::now(),
+            };
+            self.revocaciones.lock().unwrap().insert(id_usuario, revocation.clone());
+            Ok(revocation)
+        }
+    }
+
+    fn usuario(id: Uuid, email: Option<&str>, rol: &str, activo: bool) -> User {
+        User {
+            id,
+            email: email.map(str::to_string),
+            nombre: None,
+            apellido: None,
+            rol: rol.to_string(),
+            foto_perfil: None,
+            activo,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn claims(sub: Uuid, email: &str, iat: i64) -> Claims {
+        Claims {
+            aud: "authenticated".to_string(),
+            exp: iat + 3600,
+            iat,
+            iss: "http://localhost/auth/v1".to_string(),
+            sub,
+            email: Some(email.to_string()),
+            role: Some("authenticated".to_string()),
+        }
+    }
+
+    fn service(users: Vec<User>) -> (SessionService, Arc<FakeUsers>) {
+        let fake = Arc::new(FakeUsers {
+            users: Mutex::new(users.into_iter().map(|u| (u.id, u)).collect()),
+            lecturas: AtomicUsize::new(0),
+        });
+        let service = SessionService::new(fake.clone(), Arc::new(FakeRevocations::default()));
+        (service, fake)
+    }
+
+    #[tokio::test]
+    async fn reutiliza_el_estado_cacheado() {
+        let id = Uuid::new_v4();
+        let (service, users) = service(vec![usuario(id, Some("a@x.com"), "cliente", true)]);
+        let token = claims(id, "a@x.com", Utc::now().timestamp());
+
+        assert_eq!(service.check_session(&token).await.unwrap(), SessionStatus::Active);
+        assert_eq!(service.check_session(&token).await.unwrap(), SessionStatus::Active);
+        assert_eq!(users.lecturas.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn revocar_invalida_el_cache_al_instante() {
+        let id = Uuid::new_v4();
+        let (service, users) = service(vec![usuario(id, Some("a@x.com"), "cliente", true)]);
+        let anterior = claims(id, "a@x.com", Utc::now().timestamp() - 60);
+
+        assert_eq!(service.check_session(&anterior).await.unwrap(), SessionStatus::Active);
+        service.revoke_sessions(id, Some("prueba"), None).await.unwrap();
+
+        assert_eq!(service.check_session(&anterior).await.unwrap(), SessionStatus::Revoked);
+        assert_eq!(users.lecturas.load(Ordering::SeqCst), 2);
+
+        // La sesión abierta justo después de revocar sigue siendo válida
+        let nuevo = claims(id, "a@x.com", Utc::now().timestamp());
+        assert_eq!(service.check_session(&nuevo).await.unwrap(), SessionStatus::Active);
+    }
+
+    #[tokio::test]
+    async fn usuario_inactivo() {
+        let id = Uuid::new_v4();
+        let (service, _) = service(vec![usuario(id, Some("a@x.com"), "cliente", false)]);
+        let token = claims(id, "a@x.com", Utc::now().timestamp());
+
+        assert_eq!(service.check_session(&token).await.unwrap(), SessionStatus::UserInactive);
+    }
+
+    #[tokio::test]
+    async fn email_distinto_recarga_y_sincroniza() {
+        let id = Uuid::new_v4();
+        let (service, users) = service(vec![usuario(id, Some("a@x.com"), "cliente", true)]);
+        let now = Utc::now().timestamp();
+
+        service.check_session(&claims(id, "a@x.com", now)).await.unwrap();
+        service.check_session(&claims(id, "b@x.com", now)).await.unwrap();
+
+        assert_eq!(users.lecturas.load(Ordering::SeqCst), 2);
+        let email = users.users.lock().unwrap()[&id].email.clone();
+        assert_eq!(email.as_deref(), Some("b@x.com"));
+    }
+
+    #[tokio::test]
+    async fn aprovisiona_usuarios_nuevos_como_cliente() {
+        let id = Uuid::new_v4();
+        let (service, users) = service(vec![]);
+
+        let status = service.check_session(&claims(id, "n@x.com", Utc::now().timestamp())).await.unwrap();
+
+        assert_eq!(status, SessionStatus::Active);
+        assert_eq!(users.users.lock().unwrap()[&id].rol, ROL_CLIENTE);
+    }
+}
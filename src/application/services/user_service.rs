@@ -1,21 +1,40 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use futures::{stream, Stream, StreamExt};
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::application::dto::{
     CreateUserDTO, UpdateUserDTO, UpdateUserRoleDTO, UpdateUserStatusDTO,
-    UserResponseDTO, UsersListResponseDTO,
+    UserResponseDTO, UsersListResponseDTO, UsersQueryDTO,
+    UserImportEstado, UserImportQueryDTO, UserImportReportDTO, UserImportRowDTO,
 };
+use crate::application::services::SessionService;
+use crate::domain::auth::{ROLES_VALIDOS, ROL_CLIENTE};
 use crate::domain::entities::User;
-use crate::domain::repositories::UserRepository;
+use crate::domain::repositories::{UserFilter, UserPageQuery, UserRepository, UserSortField};
 use crate::shared::error::{AppError, AppResult};
+use crate::shared::Cursor;
+
+/// Tamaño de página por defecto y máximo del directorio de usuarios
+const USERS_PAGE_DEFAULT: i64 = 20;
+const USERS_PAGE_MAX: i64 = 100;
+
+/// Máximo de filas por archivo de importación
+const USERS_IMPORT_MAX_FILAS: usize = 1000;
+
+/// Usuarios leídos por consulta al exportar
+const USERS_EXPORT_BATCH: i64 = 500;
 
 pub struct UserService {
     repository: Arc<dyn UserRepository>,
+    sessions: Arc<SessionService>,
 }
 
 impl UserService {
-    pub fn new(repository: Arc<dyn UserRepository>) -> Self {
-        Self { repository }
+    pub fn new(repository: Arc<dyn UserRepository>, sessions: Arc<SessionService>) -> Self {
+        Self { repository, sessions }
     }
 
     /// Obtiene un usuario por ID
@@ -39,64 +58,290 @@ impl UserService {
         Ok(user.map(UserResponseDTO::from))
     }
 
-    /// Lista todos los usuarios
-    pub async fn list_users(&self) -> AppResult<UsersListResponseDTO> {
-        let users = self.repository
-            .find_all()
-            .await
-            .map_err(|_| AppError::Internal("Error al listar usuarios".to_string()))?;
+    /// Directorio de usuarios: filtros, orden y paginación por cursor
+    ///
+    /// La paginación es por keyset (valor de orden + id del último usuario), así que
+    /// las páginas no se desplazan cuando se insertan usuarios entre una y otra.
+    pub async fn list_users(&self, query: UsersQueryDTO) -> AppResult<UsersListResponseDTO> {
+        let page = self.page_query(query)?;
+        let limit = page.limit;
+
+        // Se pide un usuario extra para saber si hay página siguiente
+        let fetch = UserPageQuery { limit: limit + 1, ..page.clone() };
+        let (mut users, counts) = tokio::try_join!(
+            self.repository.find_page(&fetch),
+            self.repository.count_by_rol(&page.filter),
+        )?;
+
+        let next_cursor = if users.len() as i64 > limit {
+            users.truncate(limit as usize);
+            users
+                .last()
+                .map(|last| {
+                    Cursor(UserCursor {
+                        sort: page.sort.as_str().to_string(),
+                        desc: page.descending,
+                        value: page.sort.key(last),
+                        id: last.id,
+                    })
+                    .encode()
+                })
+                .transpose()?
+        } else {
+            None
+        };
+
+        let mut counts_por_rol: BTreeMap<String, i64> =
+            ROLES_VALIDOS.iter().map(|rol| (rol.to_string(), 0)).collect();
+        counts_por_rol.extend(counts);
 
-        let total = users.len() as i64;
-        let user_dtos = users.into_iter().map(UserResponseDTO::from).collect();
+        let total = match &page.filter.rol {
+            Some(rol) => counts_por_rol.get(rol).copied().unwrap_or(0),
+            None => counts_por_rol.values().sum(),
+        };
 
         Ok(UsersListResponseDTO {
             total,
-            users: user_dtos,
+            users: users.into_iter().map(UserResponseDTO::from).collect(),
+            next_cursor,
+            counts_por_rol,
         })
     }
 
-    /// Lista usuarios con paginación
-    pub async fn list_users_paginated(&self, limit: i64, offset: i64) -> AppResult<UsersListResponseDTO> {
-        let (users, total) = self.repository
-            .find_all_paginated(limit, offset)
-            .await
-            .map_err(|_| AppError::Internal("Error al listar usuarios".to_string()))?;
+    /// Valida los parámetros del directorio y los convierte en una consulta de página
+    fn page_query(&self, query: UsersQueryDTO) -> AppResult<UserPageQuery> {
+        if let Some(rol) = &query.rol
+            && !ROLES_VALIDOS.contains(&rol.as_str())
+        {
+            return Err(AppError::BadRequest(format!(
+                "Rol inválido. Valores permitidos: {}",
+                ROLES_VALIDOS.join(", ")
+            )));
+        }
 
-        let user_dtos = users.into_iter().map(UserResponseDTO::from).collect();
+        if let (Some(desde), Some(hasta)) = (query.created_desde, query.created_hasta)
+            && desde > hasta
+        {
+            return Err(AppError::BadRequest(
+                "created_desde debe ser anterior a created_hasta".to_string(),
+            ));
+        }
 
-        Ok(UsersListResponseDTO {
-            total,
-            users: user_dtos,
+        let sort = match query.sort.as_deref() {
+            None => UserSortField::CreatedAt,
+            Some(value) => UserSortField::parse(value).ok_or_else(|| {
+                AppError::BadRequest(format!("No se puede ordenar por '{}'", value))
+            })?,
+        };
+
+        let descending = match query.order.as_deref() {
+            None | Some("desc") => true,
+            Some("asc") => false,
+            Some(_) => {
+                return Err(AppError::BadRequest("order debe ser 'asc' o 'desc'".to_string()));
+            }
+        };
+
+        let after = match query.cursor.as_deref() {
+            None => None,
+            Some(raw) => {
+                let cursor = UserCursor::decode(raw)?;
+                if cursor.sort != sort.as_str() || cursor.desc != descending {
+                    return Err(AppError::BadRequest(
+                        "El cursor no corresponde al orden solicitado".to_string(),
+                    ));
+                }
+                Some((cursor.value, cursor.id))
+            }
+        };
+
+        let texto = query
+            .q
+            .map(|q| q.trim().to_string())
+            .filter(|q| !q.is_empty());
+
+        Ok(UserPageQuery {
+            filter: UserFilter {
+                rol: query.rol,
+                activo: query.activo,
+                created_desde: query.created_desde,
+                created_hasta: query.created_hasta,
+                texto,
+            },
+            sort,
+            descending,
+            after,
+            limit: query.limit.unwrap_or(USERS_PAGE_DEFAULT).clamp(1, USERS_PAGE_MAX),
         })
     }
 
     /// Crea un nuevo usuario (desde Supabase webhook)
     pub async fn create_user(&self, dto: CreateUserDTO) -> AppResult<UserResponseDTO> {
+        validate_new_user(&dto).map_err(AppError::BadRequest)?;
+
         // Verificar que el usuario no existe
         if let Ok(Some(_)) = self.repository.find_by_email(&dto.email).await {
             return Err(AppError::BadRequest("Usuario ya existe".to_string()));
         }
 
-        let user = User {
-            id: Uuid::new_v4(),
-            email: Some(dto.email),
-            nombre: dto.nombre,
-            apellido: dto.apellido,
-            rol: dto.rol,
-            foto_perfil: None,
-            activo: true,
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-        };
-
         let created = self.repository
-            .create(&user)
+            .create(&new_user(dto))
             .await
             .map_err(|_| AppError::Internal("Error al crear usuario".to_string()))?;
 
         Ok(UserResponseDTO::from(created))
     }
 
+    /// Importa usuarios desde CSV (columnas: email, nombre, apellido, rol)
+    ///
+    /// Cada fila se valida con las mismas reglas que `create_user`. En dry run no se
+    /// escribe nada; en commit se crean las filas válidas y las inválidas se informan
+    /// en el reporte sin detener la importación.
+    pub async fn import_users(&self, query: UserImportQueryDTO, csv: &str) -> AppResult<UserImportReportDTO> {
+        let commit = match query.mode.as_deref() {
+            None | Some("dry_run") => false,
+            Some("commit") => true,
+            Some(_) => {
+                return Err(AppError::BadRequest("mode debe ser 'dry_run' o 'commit'".to_string()));
+            }
+        };
+
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(csv.as_bytes());
+
+        let headers = reader
+            .headers()
+            .map_err(|e| AppError::BadRequest(format!("CSV inválido: {}", e)))?;
+        if !headers.iter().any(|h| h == "email") {
+            return Err(AppError::BadRequest("El CSV debe tener una columna 'email'".to_string()));
+        }
+
+        // Se lee y valida el archivo completo antes de escribir: un CSV sobre el
+        // máximo o con errores de formato no deja filas creadas a medias
+        let records: Vec<_> = reader
+            .deserialize::<UserImportCsvRow>()
+            .take(USERS_IMPORT_MAX_FILAS + 1)
+            .collect();
+        if records.len() > USERS_IMPORT_MAX_FILAS {
+            return Err(AppError::BadRequest(format!(
+                "El CSV supera el máximo de {} filas",
+                USERS_IMPORT_MAX_FILAS
+            )));
+        }
+
+        let mut filas = Vec::with_capacity(records.len());
+        let mut pendientes = Vec::new();
+        let mut emails_vistos = HashSet::new();
+
+        for (i, record) in records.into_iter().enumerate() {
+            let fila = i as u64 + 2;
+
+            let row = match record {
+                Ok(row) => row,
+                Err(e) => {
+                    filas.push(UserImportRowDTO::error(fila, None, format!("Fila ilegible: {}", e)));
+                    continue;
+                }
+            };
+
+            let dto = CreateUserDTO {
+                email: row.email,
+                nombre: row.nombre,
+                apellido: row.apellido,
+                rol: row.rol.unwrap_or_else(|| ROL_CLIENTE.to_string()),
+            };
+            let email = Some(dto.email.clone());
+
+            if let Err(e) = validate_new_user(&dto) {
+                filas.push(UserImportRowDTO::error(fila, email, e));
+                continue;
+            }
+            if !emails_vistos.insert(dto.email.to_lowercase()) {
+                filas.push(UserImportRowDTO::error(fila, email, "Email repetido en el archivo".to_string()));
+                continue;
+            }
+            if self.repository.find_by_email(&dto.email).await?.is_some() {
+                filas.push(UserImportRowDTO::error(fila, email, "Usuario ya existe".to_string()));
+                continue;
+            }
+
+            pendientes.push((filas.len(), dto));
+            filas.push(UserImportRowDTO {
+                fila,
+                email,
+                estado: UserImportEstado::Valido,
+                id: None,
+                error: None,
+            });
+        }
+
+        if commit {
+            for (i, dto) in pendientes {
+                let fila = &mut filas[i];
+                match self.repository.create(&new_user(dto)).await {
+                    Ok(user) => {
+                        fila.estado = UserImportEstado::Creado;
+                        fila.id = Some(user.id);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Importación: no se pudo crear la fila {}: {}", fila.fila, e);
+                        *fila = UserImportRowDTO::error(fila.fila, fila.email.take(), "Error al crear usuario".to_string());
+                    }
+                }
+            }
+        }
+
+        let errores = filas.iter().filter(|f| f.estado == UserImportEstado::Error).count();
+
+        Ok(UserImportReportDTO {
+            dry_run: !commit,
+            total: filas.len(),
+            validos: filas.len() - errores,
+            errores,
+            filas,
+        })
+    }
+
+    /// Exporta todos los usuarios como CSV, en bloques para no cargar la tabla en memoria
+    ///
+    /// El stream recorre la tabla por keyset (`created_at`, `id`) y emite la cabecera
+    /// seguida de un bloque de texto por cada página leída.
+    pub fn export_users(&self) -> impl Stream<Item = AppResult<String>> + Send + 'static {
+        let repository = self.repository.clone();
+        let header = stream::once(async {
+            Ok::<_, AppError>("id,email,nombre,apellido,rol,activo,created_at,updated_at\n".to_string())
+        });
+
+        let pages = stream::try_unfold(Some(None), move |state: Option<Option<(String, Uuid)>>| {
+            let repository = repository.clone();
+            async move {
+                let Some(after) = state else {
+                    return Ok(None);
+                };
+
+                let query = UserPageQuery {
+                    filter: UserFilter::default(),
+                    sort: UserSortField::CreatedAt,
+                    descending: false,
+                    after,
+                    limit: USERS_EXPORT_BATCH,
+                };
+                let users = repository.find_page(&query).await?;
+                if users.is_empty() {
+                    return Ok(None);
+                }
+
+                let next = (users.len() as i64 == USERS_EXPORT_BATCH).then(|| {
+                    users.last().map(|last| (UserSortField::CreatedAt.key(last), last.id))
+                });
+                Ok(Some((users_to_csv(&users)?, next)))
+            }
+        });
+
+        header.chain(pages)
+    }
+
     /// Actualiza un usuario
     pub async fn update_user(&self, id: Uuid, dto: UpdateUserDTO) -> AppResult<UserResponseDTO> {
         let mut user = self.repository
@@ -131,6 +376,13 @@ impl UserService {
 
     /// Actualiza el rol de un usuario (solo admin)
     pub async fn update_user_role(&self, id: Uuid, dto: UpdateUserRoleDTO) -> AppResult<UserResponseDTO> {
+        if !ROLES_VALIDOS.contains(&dto.rol.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Rol inválido. Roles válidos: {:?}",
+                ROLES_VALIDOS
+            )));
+        }
+
         let updated = self.repository
             .update_role(id, &dto.rol)
             .await
@@ -140,22 +392,57 @@ impl UserService {
     }
 
     /// Actualiza el estado de un usuario (solo admin)
+    ///
+    /// Al desactivar se revocan las sesiones existentes.
     pub async fn update_user_status(&self, id: Uuid, dto: UpdateUserStatusDTO) -> AppResult<UserResponseDTO> {
         let updated = self.repository
             .update_status(id, dto.activo)
             .await
             .map_err(|_| AppError::Internal("Error al actualizar estado".to_string()))?;
 
+        if dto.activo {
+            self.sessions.invalidate(id);
+        } else {
+            self.sessions
+                .revoke_sessions(id, Some("Cuenta desactivada"), None)
+                .await?;
+        }
+
         Ok(UserResponseDTO::from(updated))
     }
 
+    /// Revoca todas las sesiones de un usuario (logout forzado, solo admin)
+    pub async fn revoke_user_sessions(&self, id: Uuid, admin_id: Uuid) -> AppResult<()> {
+        self.repository
+            .find_by_id(id)
+            .await
+            .map_err(|_| AppError::Internal("Error al obtener usuario".to_string()))?
+            .ok_or_else(|| AppError::NotFound("Usuario no encontrado".to_string()))?;
+
+        self.sessions
+            .revoke_sessions(id, Some("Logout forzado por administrador"), Some(admin_id))
+            .await?;
+
+        Ok(())
+    }
+
     /// Elimina un usuario (soft delete)
     pub async fn delete_user(&self, id: Uuid) -> AppResult<()> {
+        self.repository
+            .find_by_id(id)
+            .await
+            .map_err(|_| AppError::Internal("Error al obtener usuario".to_string()))?
+            .ok_or_else(|| AppError::NotFound("Usuario no encontrado".to_string()))?;
+
         self.repository
             .delete(id)
             .await
             .map_err(|_| AppError::Internal("Error al eliminar usuario".to_string()))?;
 
+        self.sessions
+            .revoke_sessions(id, Some("Cuenta eliminada"), None)
+            .await?;
+
         Ok(())
     }
 }
@@ -179,3 +466,367 @@ impl From<User> for UserResponseDTO {
         }
     }
 }
+
+// ============================================================================
+// VALIDACIÓN E IMPORTACIÓN
+// ============================================================================
+
+/// Reglas de un usuario nuevo (alta manual e importación)
+fn validate_new_user(dto: &CreateUserDTO) -> Result<(), String> {
+    let email_valido = dto
+        .email
+        .split_once('@')
+        .is_some_and(|(local, dominio)| !local.is_empty() && dominio.contains('.'))
+        && !dto.email.contains(char::is_whitespace);
+    if !email_valido {
+        return Err("Email inválido".to_string());
+    }
+
+    if !ROLES_VALIDOS.contains(&dto.rol.as_str()) {
+        return Err(format!("Rol inválido. Roles válidos: {:?}", ROLES_VALIDOS));
+    }
+
+    Ok(())
+}
+
+fn new_user(dto: CreateUserDTO) -> User {
+    let now = chrono::Utc::now();
+    User {
+        id: Uuid::new_v4(),
+        email: Some(dto.email),
+        nombre: dto.nombre,
+        apellido: dto.apellido,
+        rol: dto.rol,
+        foto_perfil: None,
+        activo: true,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Fila del CSV de importación (las columnas vacías se leen como `None`)
+#[derive(Deserialize)]
+struct UserImportCsvRow {
+    email: String,
+    nombre: Option<String>,
+    apellido: Option<String>,
+    rol: Option<String>,
+}
+
+impl UserImportRowDTO {
+    fn error(fila: u64, email: Option<String>, error: String) -> Self {
+        Self { fila, email, estado: UserImportEstado::Error, id: None, error: Some(error) }
+    }
+}
+
+fn users_to_csv(users: &[User]) -> AppResult<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for user in users {
+        writer
+            .write_record([
+                user.id.to_string(),
+                user.email.clone().unwrap_or_default(),
+                user.nombre.clone().unwrap_or_default(),
+                user.apellido.clone().unwrap_or_default(),
+                user.rol.clone(),
+                user.activo.to_string(),
+                user.created_at.to_rfc3339(),
+                user.updated_at.to_rfc3339(),
+            ])
+            .map_err(|e| AppError::Internal(format!("Error al generar CSV: {}", e)))?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| AppError::Internal(format!("Error al generar CSV: {}", e)))?;
+    String::from_utf8(bytes).map_err(|e| AppError::Internal(format!("Error al generar CSV: {}", e)))
+}
+
+// ============================================================================
+// CURSOR DE PAGINACIÓN
+// ============================================================================
+
+/// Contenido del cursor opaco: orden solicitado y posición del último usuario entregado
+#[derive(Serialize, Deserialize)]
+struct UserCursor {
+    sort: String,
+    desc: bool,
+    value: String,
+    id: Uuid,
+}
+
+impl UserCursor {
+    fn decode(raw: &str) -> AppResult<Self> {
+        // El valor se castea en SQL según la columna: validarlo antes de consultar
+        Cursor::decode_valid(raw, |cursor: &Self| match UserSortField::parse(&cursor.sort) {
+            Some(UserSortField::Activo) => cursor.value.parse::<bool>().is_ok(),
+            Some(UserSortField::CreatedAt | UserSortField::UpdatedAt) => {
+                cursor.value.parse::<DateTime<Utc>>().is_ok()
+            }
+            Some(_) => true,
+            None => false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::SessionRevocation;
+    use crate::domain::repositories::SessionRevocationRepository;
+    use std::sync::Mutex;
+
+    /// Repositorio de usuarios en memoria (solo lo que usa la importación)
+    #[derive(Default)]
+    struct FakeUsers {
+        users: Mutex<Vec<User>>,
+    }
+
+    #[async_trait::async_trait]
+    impl UserRepository for FakeUsers {
+        async fn find_by_id(&self, _: Uuid) -> Result<Option<User>, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn find_by_email(&self, email: &str) -> Result<Option<User>, sqlx::Error> {
+            let users = self.users.lock().unwrap();
+            Ok(users.iter().find(|u| u.email.as_deref() == Some(email)).cloned())
+        }
+        async fn find_page(&self, query: &UserPageQuery) -> Result<Vec<User>, sqlx::Error> {
+            let users = self.users.lock().unwrap();
+            Ok(users.iter().take(query.limit as usize).cloned().collect())
+        }
+        async fn count_by_rol(&self, _: &UserFilter) -> Result<Vec<(String, i64)>, sqlx::Error> {
+            let mut counts: BTreeMap<String, i64> = BTreeMap::new();
+            for user in self.users.lock().unwrap().iter() {
+                *counts.entry(user.rol.clone()).or_default() += 1;
+            }
+            Ok(counts.into_iter().collect())
+        }
+        async fn create(&self, user: &User) -> Result<User, sqlx::Error> {
+            self.users.lock().unwrap().push(user.clone());
+            Ok(user.clone())
+        }
+        async fn update(&self, _: &User) -> Result<User, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn update_role(&self, _: Uuid, _: &str) -> Result<User, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn update_status(&self, _: Uuid, _: bool) -> Result<User, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn provision(&self, _: Uuid, _: Option<&str>, _: &str) -> Result<User, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn update_email(&self, _: Uuid, _: &str) -> Result<User, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn update_foto_perfil(&self, _: Uuid, _: Option<&str>) -> Result<User, sqlx::Error> {
+            unimplemented!()
+        }
+        async fn delete(&self, _: Uuid) -> Result<(), sqlx::Error> {
+            unimplemented!()
+        }
+    }
+
+    struct SinRevocaciones;
+
+    #[async_trait::async_trait]
+    impl SessionRevocationRepository for SinRevocaciones {
+        async fn find_by_usuario(&self, _: Uuid) -> AppResult<Option<SessionRevocation>> {
+            Ok(None)
+        }
+        async fn revoke_all(&self, _: Uuid, _: Option<&str>, _: Option<Uuid>) -> AppResult<SessionRevocation> {
+            unimplemented!()
+        }
+    }
+
+    fn service() -> (UserService, Arc<FakeUsers>) {
+        let users = Arc::new(FakeUsers::default());
+        let sessions = Arc::new(SessionService::new(users.clone(), Arc::new(SinRevocaciones)));
+        (UserService::new(users.clone(), sessions), users)
+    }
+
+    fn mode(mode: &str) -> UserImportQueryDTO {
+        UserImportQueryDTO { mode: Some(mode.to_string()) }
+    }
+
+    const CSV: &str = "email,nombre,rol\n\
+        ana@example.com,Ana,cliente\n\
+        no-es-email,Luis,cliente\n\
+        ANA@example.com,Ana,cliente\n\
+        eva@example.com,Eva,superusuario\n\
+        juan@example.com,Juan,\n";
+
+    #[tokio::test]
+    async fn dry_run_valida_sin_crear() {
+        let (service, users) = service();
+
+        let report = service.import_users(mode("dry_run"), CSV).await.unwrap();
+
+        assert!(report.dry_run);
+        assert_eq!((report.total, report.validos, report.errores), (5, 2, 3));
+        let estados: Vec<_> = report.filas.iter().map(|f| f.estado).collect();
+        assert_eq!(
+            estados,
+            [
+                UserImportEstado::Valido,
+                UserImportEstado::Error,
+                UserImportEstado::Error,
+                UserImportEstado::Error,
+                UserImportEstado::Valido,
+            ]
+        );
+        assert_eq!(report.filas[2].fila, 4);
+        assert!(users.users.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn commit_crea_solo_las_filas_validas() {
+        let (service, users) = service();
+
+        let report = service.import_users(mode("commit"), CSV).await.unwrap();
+
+        assert!(!report.dry_run);
+        assert_eq!((report.validos, report.errores), (2, 3));
+        let creadas: Vec<_> = report.filas.iter().filter(|f| f.estado == UserImportEstado::Creado).collect();
+        assert_eq!(creadas.len(), 2);
+        assert!(creadas.iter().all(|f| f.id.is_some()));
+
+        let users = users.users.lock().unwrap();
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[1].rol, ROL_CLIENTE);
+    }
+
+    #[tokio::test]
+    async fn commit_informa_usuarios_existentes() {
+        let (service, users) = service();
+        service.import_users(mode("commit"), CSV).await.unwrap();
+
+        let report = service.import_users(mode("commit"), CSV).await.unwrap();
+
+        assert_eq!(report.validos, 0);
+        assert_eq!(report.filas[0].error.as_deref(), Some("Usuario ya existe"));
+        assert_eq!(users.users.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn rechaza_el_archivo_sobre_el_maximo_sin_crear_filas() {
+        let (service, users) = service();
+        let mut csv = "email\n".to_string();
+        for i in 0..=USERS_IMPORT_MAX_FILAS {
+            csv.push_str(&format!("u{}@example.com\n", i));
+        }
+
+        let result = service.import_users(mode("commit"), &csv).await;
+
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+        assert!(users.users.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn acepta_el_maximo_exacto() {
+        let (service, _) = service();
+        let mut csv = "email\n".to_string();
+        for i in 0..USERS_IMPORT_MAX_FILAS {
+            csv.push_str(&format!("u{}@example.com\n", i));
+        }
+
+        let report = service.import_users(mode("dry_run"), &csv).await.unwrap();
+
+        assert_eq!(report.validos, USERS_IMPORT_MAX_FILAS);
+    }
+
+    fn directorio(params: &str) -> UsersQueryDTO {
+        let uri = format!("/api/admin/users?{}", params).parse::<axum::http::Uri>().unwrap();
+        axum::extract::Query::<UsersQueryDTO>::try_from_uri(&uri).unwrap().0
+    }
+
+    fn user_cursor(sort: &str, desc: bool, value: &str) -> String {
+        Cursor(UserCursor { sort: sort.to_string(), desc, value: value.to_string(), id: Uuid::nil() })
+            .encode()
+            .unwrap()
+    }
+
+    #[test]
+    fn directorio_valores_por_defecto() {
+        let (service, _) = service();
+        let page = service.page_query(directorio("q=%20%20")).unwrap();
+
+        assert_eq!(page.sort, UserSortField::CreatedAt);
+        assert!(page.descending);
+        assert_eq!(page.limit, USERS_PAGE_DEFAULT);
+        assert!(page.filter.texto.is_none());
+        assert!(page.after.is_none());
+    }
+
+    #[test]
+    fn directorio_valida_filtros_y_orden() {
+        let (service, _) = service();
+
+        assert!(service.page_query(directorio("rol=superusuario")).is_err());
+        assert!(service.page_query(directorio("sort=password")).is_err());
+        assert!(service.page_query(directorio("order=random")).is_err());
+        assert!(service
+            .page_query(directorio("created_desde=2025-02-01T00:00:00Z&created_hasta=2025-01-01T00:00:00Z"))
+            .is_err());
+        assert_eq!(service.page_query(directorio("limit=500")).unwrap().limit, USERS_PAGE_MAX);
+
+        let page = service.page_query(directorio("rol=admin&activo=false&q=%20ana%20&sort=email&order=asc")).unwrap();
+        assert_eq!(page.filter.rol.as_deref(), Some("admin"));
+        assert_eq!(page.filter.activo, Some(false));
+        assert_eq!(page.filter.texto.as_deref(), Some("ana"));
+        assert_eq!(page.sort, UserSortField::Email);
+        assert!(!page.descending);
+    }
+
+    #[test]
+    fn directorio_cursor_del_mismo_orden() {
+        let (service, _) = service();
+
+        let raw = user_cursor("activo", true, "true");
+        let page = service.page_query(directorio(&format!("sort=activo&cursor={}", raw))).unwrap();
+        assert_eq!(page.after, Some(("true".to_string(), Uuid::nil())));
+
+        // Otro orden o un valor que no corresponde a la columna
+        assert!(service.page_query(directorio(&format!("sort=activo&order=asc&cursor={}", raw))).is_err());
+        let raw = user_cursor("activo", true, "quizas");
+        assert!(service.page_query(directorio(&format!("sort=activo&cursor={}", raw))).is_err());
+        let raw = user_cursor("created_at", true, "ayer");
+        assert!(service.page_query(directorio(&format!("cursor={}", raw))).is_err());
+    }
+
+    #[tokio::test]
+    async fn directorio_pagina_y_cuenta_por_rol() {
+        let (service, _) = service();
+        service.import_users(mode("commit"), CSV).await.unwrap();
+        service
+            .import_users(mode("commit"), "email,rol\nluis@example.com,transportista\n")
+            .await
+            .unwrap();
+
+        let response = service.list_users(directorio("limit=2")).await.unwrap();
+
+        assert_eq!(response.users.len(), 2);
+        assert_eq!(response.total, 3);
+        assert_eq!(response.counts_por_rol.get("cliente"), Some(&2));
+        assert_eq!(response.counts_por_rol.get("transportista"), Some(&1));
+        assert_eq!(response.counts_por_rol.get("admin"), Some(&0));
+
+        let cursor = Cursor::<UserCursor>::decode(&response.next_cursor.unwrap()).unwrap();
+        assert_eq!(cursor.id, response.users[1].id);
+        assert_eq!((cursor.sort.as_str(), cursor.desc), ("created_at", true));
+
+        let response = service.list_users(directorio("limit=3&rol=cliente")).await.unwrap();
+        assert!(response.next_cursor.is_none());
+        assert_eq!(response.total, 2);
+    }
+
+    #[tokio::test]
+    async fn rechaza_modo_desconocido_y_csv_sin_email() {
+        let (service, _) = service();
+
+        assert!(service.import_users(mode("apply"), CSV).await.is_err());
+        assert!(service.import_users(mode("commit"), "nombre\nAna\n").await.is_err());
+    }
+}
@@ -49,6 +49,8 @@ pub fn create_cors_layer(environment: &str) -> CorsLayer {
                     "authorization".parse().expect("Invalid header"),
                     "content-type".parse().expect("Invalid header"),
                     "accept".parse().expect("Invalid header"),
+                    // Selección de perfil de empresa (ver CurrentPerfil)
+                    "x-perfil-id".parse().expect("Invalid header"),
                 ]))
                 .allow_credentials(true)
         }
@@ -72,6 +74,8 @@ pub fn create_cors_layer(environment: &str) -> CorsLayer {
                     "authorization".parse().expect("Invalid header"),
                     "content-type".parse().expect("Invalid header"),
                     "accept".parse().expect("Invalid header"),
+                    // Selección de perfil de empresa (ver CurrentPerfil)
+                    "x-perfil-id".parse().expect("Invalid header"),
                 ]))
                 .allow_credentials(true)
         }
@@ -86,6 +90,8 @@ pub fn create_cors_layer(environment: &str) -> CorsLayer {
                     "authorization".parse().expect("Invalid header"),
                     "content-type".parse().expect("Invalid header"),
                     "accept".parse().expect("Invalid header"),
+                    // Selección de perfil de empresa (ver CurrentPerfil)
+                    "x-perfil-id".parse().expect("Invalid header"),
                 ]))
                 .allow_credentials(true)
         }
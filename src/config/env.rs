@@ -5,16 +5,89 @@ pub struct AppConfig {
     pub database_url: String,
     pub server_host: String,
     pub server_port: u16,
+    /// `ENVIRONMENT` (default: production)
     pub environment: String,
+    /// Habilita `POST /dev/token` y `integrador dev-token` (solo con ENVIRONMENT=development)
+    pub dev_tokens_enabled: bool,
     pub supabase_url: String,
-    pub supabase_jwt_secret: String,
+    /// Secret HS256 (tokens firmados con el JWT secret del proyecto)
+    pub supabase_jwt_secret: Option<String>,
+    /// URL del JWKS (por defecto `{SUPABASE_URL}/auth/v1/.well-known/jwks.json`)
+    pub supabase_jwks_url: Option<String>,
+    /// Archivo JWKS local; tiene prioridad sobre la URL (desarrollo sin red)
+    pub supabase_jwks_file: Option<String>,
+    /// Issuer esperado en el claim `iss` (`SUPABASE_JWT_ISSUER` o `{SUPABASE_URL}/auth/v1`)
+    pub jwt_issuer: String,
+    /// Límites de requests por grupo de rutas
+    pub rate_limits: RateLimitConfig,
+    /// Directorio raíz del almacenamiento local de archivos (avatares)
+    pub storage_local_dir: String,
+}
+
+/// Límite de un grupo de rutas (token bucket)
+///
+/// `per_minute` es la tasa sostenida y `burst` la capacidad del bucket.
+/// Con `per_minute = 0` el grupo no tiene límite.
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimitRule {
+    pub per_minute: u32,
+    pub burst: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct RateLimitConfig {
+    /// Catálogo público y almacenes (por IP)
+    pub public: RateLimitRule,
+    /// Rutas autenticadas de clientes y transportistas (por usuario)
+    pub authenticated: RateLimitRule,
+    /// Rutas /api/admin/* (por usuario)
+    pub admin: RateLimitRule,
+    /// Requests que terminan en 401, en todas las rutas (por IP)
+    pub auth_failures: RateLimitRule,
+    /// Tomar la IP del cliente de `X-Forwarded-For` (solo detrás de un proxy confiable)
+    pub trust_proxy: bool,
+}
+
+impl RateLimitConfig {
+    fn from_env() -> Self {
+        Self {
+            public: RateLimitRule::from_env("PUBLIC", 60, 20),
+            authenticated: RateLimitRule::from_env("AUTHENTICATED", 120, 40),
+            admin: RateLimitRule::from_env("ADMIN", 300, 60),
+            auth_failures: RateLimitRule::from_env("AUTH_FAILURES", 30, 10),
+            trust_proxy: env::var("RATE_LIMIT_TRUST_PROXY")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+        }
+    }
+}
+
+impl RateLimitRule {
+    /// Lee `RATE_LIMIT_{GRUPO}_PER_MINUTE` y `RATE_LIMIT_{GRUPO}_BURST`
+    fn from_env(group: &str, per_minute: u32, burst: u32) -> Self {
+        let read = |suffix: &str, default: u32| {
+            let name = format!("RATE_LIMIT_{}_{}", group, suffix);
+            env::var(&name)
+                .ok()
+                .filter(|s| !s.is_empty())
+                .map(|v| v.parse().unwrap_or_else(|_| panic!("{} must be a valid number", name)))
+                .unwrap_or(default)
+        };
+
+        Self {
+            per_minute: read("PER_MINUTE", per_minute),
+            burst: read("BURST", burst),
+        }
+    }
 }
 
 impl AppConfig {
     pub fn from_env() -> Self {
         dotenvy::dotenv().ok();
 
-        Self {
+        let supabase_url = env::var("SUPABASE_URL").unwrap_or_default();
+
+        let config = Self {
             database_url: env::var("DATABASE_URL")
                 .expect("DATABASE_URL must be set"),
             server_host: env::var("SERVER_HOST")
@@ -23,12 +96,64 @@ impl AppConfig {
                 .unwrap_or_else(|_| "3000".to_string())
                 .parse()
                 .expect("SERVER_PORT must be a valid number"),
+            // Sin ENVIRONMENT se asume producción: nada de desarrollo queda expuesto por omisión
             environment: env::var("ENVIRONMENT")
-                .unwrap_or_else(|_| "development".to_string()),
-            supabase_url: env::var("SUPABASE_URL")
-                .unwrap_or_else(|_| "".to_string()),
+                .ok()
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| "production".to_string()),
+            dev_tokens_enabled: env::var("DEV_TOKENS_ENABLED")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            // El claim iss se valida siempre: sin issuer no se arranca
+            jwt_issuer: env::var("SUPABASE_JWT_ISSUER")
+                .ok()
+                .filter(|s| !s.is_empty())
+                .or_else(|| supabase_auth_url(&supabase_url))
+                .expect("SUPABASE_URL (or SUPABASE_JWT_ISSUER) must be set"),
+            supabase_url,
             supabase_jwt_secret: env::var("SUPABASE_JWT_SECRET")
-                .expect("SUPABASE_JWT_SECRET must be set for authentication"),
+                .ok()
+                .filter(|s| !s.is_empty()),
+            supabase_jwks_url: env::var("SUPABASE_JWKS_URL")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            supabase_jwks_file: env::var("SUPABASE_JWKS_FILE")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            rate_limits: RateLimitConfig::from_env(),
+            storage_local_dir: env::var("STORAGE_LOCAL_DIR")
+                .ok()
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| "./storage".to_string()),
+        };
+
+        // Los tokens de desarrollo firman con el secret real: no arrancar si se
+        // habilitaron fuera de development
+        if config.dev_tokens_enabled && config.environment != "development" {
+            panic!(
+                "DEV_TOKENS_ENABLED=true is only allowed with ENVIRONMENT=development (current: {})",
+                config.environment
+            );
         }
+
+        config
+    }
+
+    /// URL del JWKS: explícita o derivada de `SUPABASE_URL`
+    pub fn jwks_url(&self) -> Option<String> {
+        self.supabase_jwks_url.clone().or_else(|| {
+            supabase_auth_url(&self.supabase_url)
+                .map(|auth| format!("{}/.well-known/jwks.json", auth))
+        })
+    }
+}
+
+/// Base de Supabase Auth: `{SUPABASE_URL}/auth/v1`
+fn supabase_auth_url(supabase_url: &str) -> Option<String> {
+    let base = supabase_url.trim_end_matches('/');
+    if base.is_empty() {
+        None
+    } else {
+        Some(format!("{}/auth/v1", base))
     }
 }
@@ -34,11 +34,21 @@ impl Claims {
 }
 
 /// Usuario autenticado extraído del JWT
+///
+/// Cuando un admin usa `X-Act-As`, representa al usuario suplantado y
+/// `impersonated_by` guarda el ID del admin que realmente hace el request.
 #[derive(Debug, Clone)]
 pub struct AuthenticatedUser {
     pub id: Uuid,
     pub email: Option<String>,
     pub role: Option<String>,
+    pub impersonated_by: Option<Uuid>,
+}
+
+impl AuthenticatedUser {
+    pub fn is_impersonated(&self) -> bool {
+        self.impersonated_by.is_some()
+    }
 }
 
 impl From<Claims> for AuthenticatedUser {
@@ -47,6 +57,7 @@ impl From<Claims> for AuthenticatedUser {
             id: claims.sub,
             email: claims.email,
             role: claims.role,
+            impersonated_by: None,
         }
     }
 }
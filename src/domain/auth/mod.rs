@@ -1,3 +1,9 @@
 pub mod claims;
+pub mod principal;
+pub mod roles;
+pub mod scopes;
 
 pub use claims::*;
+pub use principal::*;
+pub use roles::*;
+pub use scopes::*;
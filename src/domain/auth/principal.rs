@@ -0,0 +1,27 @@
+use uuid::Uuid;
+
+use super::AuthenticatedUser;
+
+/// API key de servicio autenticada (escáneres de almacén, integraciones)
+#[derive(Debug, Clone)]
+pub struct ApiKeyPrincipal {
+    pub id: Uuid,
+    pub nombre: String,
+    pub scopes: Vec<String>,
+}
+
+impl ApiKeyPrincipal {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+/// Quién realizó el request: un usuario con JWT o una API key
+///
+/// `require_auth` siempre lo inserta en las extensiones; para usuarios también
+/// inserta `AuthenticatedUser`, que es lo que usan los handlers existentes.
+#[derive(Debug, Clone)]
+pub enum AuthenticatedPrincipal {
+    User(AuthenticatedUser),
+    ApiKey(ApiKeyPrincipal),
+}
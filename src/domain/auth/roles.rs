@@ -0,0 +1,10 @@
+/// Roles de aplicación definidos en la columna `public.users.rol`
+///
+/// No confundir con `Claims.role` de Supabase, que siempre es "authenticated"
+/// para usuarios con sesión.
+pub const ROL_CLIENTE: &str = "cliente";
+pub const ROL_TRANSPORTISTA: &str = "transportista";
+pub const ROL_ADMIN: &str = "admin";
+
+/// Todos los roles válidos del sistema
+pub const ROLES_VALIDOS: [&str; 3] = [ROL_CLIENTE, ROL_TRANSPORTISTA, ROL_ADMIN];
@@ -0,0 +1,9 @@
+/// Scopes que se pueden otorgar a una API key de servicio
+///
+/// Las API keys no tienen rol: cada ruta que las acepta declara el scope requerido.
+pub const SCOPE_PEDIDOS_READ: &str = "pedidos:read";
+pub const SCOPE_PEDIDOS_ESTADO: &str = "pedidos:estado";
+pub const SCOPE_PRODUCTOS_WRITE: &str = "productos:write";
+
+/// Todos los scopes válidos del sistema
+pub const SCOPES_VALIDOS: [&str; 3] = [SCOPE_PEDIDOS_READ, SCOPE_PEDIDOS_ESTADO, SCOPE_PRODUCTOS_WRITE];
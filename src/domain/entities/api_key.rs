@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// API key de servicio. El secreto nunca se guarda, solo su hash SHA-256.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub nombre: String,
+    /// Parte pública de la key, usada para buscarla (`ck_<prefijo>_...`)
+    pub prefijo: String,
+    #[serde(skip_serializing)]
+    pub hash: String,
+    pub scopes: Vec<String>,
+    pub expira_en: Option<DateTime<Utc>>,
+    pub ultimo_uso: Option<DateTime<Utc>>,
+    pub revocada_en: Option<DateTime<Utc>>,
+    pub creada_por: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    /// No revocada y no expirada
+    pub fn esta_vigente(&self, ahora: DateTime<Utc>) -> bool {
+        self.revocada_en.is_none() && self.expira_en.is_none_or(|expira| expira > ahora)
+    }
+}
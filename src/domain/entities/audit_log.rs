@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Entrada de la bitácora de auditoría
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditLog {
+    pub id: Uuid,
+    /// Quién ejecutó realmente la acción
+    pub id_actor: Option<Uuid>,
+    /// En nombre de quién se actuó o a quién afectó
+    pub id_usuario: Option<Uuid>,
+    pub accion: String,
+    pub recurso: Option<String>,
+    pub detalle: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditLog {
+    pub fn new(accion: &str, id_actor: Option<Uuid>, id_usuario: Option<Uuid>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            id_actor,
+            id_usuario,
+            accion: accion.to_string(),
+            recurso: None,
+            detalle: serde_json::Value::Object(Default::default()),
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn recurso(mut self, recurso: impl Into<String>) -> Self {
+        self.recurso = Some(recurso.into());
+        self
+    }
+
+    pub fn detalle(mut self, detalle: serde_json::Value) -> Self {
+        self.detalle = detalle;
+        self
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Entidad Categoria - Nodo del árbol de categorías de productos
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Categoria {
+    pub id_categoria: Uuid,
+    pub nombre: String,
+    /// Identificador legible y único (ej: "telefonos-moviles")
+    pub slug: String,
+    /// Categoría padre (None = raíz)
+    pub id_padre: Option<Uuid>,
+    /// Posición entre hermanas (ascendente)
+    pub orden: i32,
+    /// Las inactivas (y su subárbol) no se publican ni se usan como filtro
+    pub activo: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Cantidad de productos de una categoría (None = productos sin categoría)
+#[derive(Debug, Clone, FromRow)]
+pub struct CategoriaConteo {
+    pub id_categoria: Option<Uuid>,
+    pub slug: Option<String>,
+    pub nombre: Option<String>,
+    pub total: i64,
+}
@@ -0,0 +1,141 @@
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Situación de crédito de un perfil, calculada al consultarla
+///
+/// `saldo_facturas` son las facturas pendientes o vencidas menos sus pagos
+/// completados; `pedidos_por_facturar` los pedidos no cancelados que aún no
+/// tienen factura. Ambos cuentan para el límite. Los montos son `Decimal` en
+/// centavos exactos, para que llegar justo al límite no lo exceda por redondeo.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EstadoCredito {
+    pub id_perfil: Uuid,
+    pub documento_identidad: Option<String>,
+    pub limite_credito: Option<Decimal>,
+    pub dias_credito: Option<i32>,
+    pub saldo_facturas: Decimal,
+    /// Parte del saldo con el plazo de pago cumplido
+    pub saldo_vencido: Decimal,
+    pub pedidos_por_facturar: Decimal,
+}
+
+impl EstadoCredito {
+    /// Total que cuenta para el límite
+    pub fn comprometido(&self) -> Decimal {
+        self.saldo_facturas + self.pedidos_por_facturar
+    }
+
+    /// Crédito restante (None si el perfil no tiene límite)
+    pub fn disponible(&self) -> Option<Decimal> {
+        self.limite_credito.map(|limite| (limite - self.comprometido()).max(Decimal::ZERO))
+    }
+
+    /// Fracción del límite usada (1.0 = en el límite); None si el límite es 0 o no hay
+    pub fn utilizacion(&self) -> Option<f64> {
+        self.limite_credito
+            .filter(|limite| *limite > Decimal::ZERO)
+            .and_then(|limite| (self.comprometido() / limite).to_f64())
+    }
+
+    /// true si un pedido por `monto` dejaría al perfil sobre su límite
+    pub fn excede_con(&self, monto: Decimal) -> bool {
+        self.limite_credito
+            .is_some_and(|limite| self.comprometido() + monto > limite)
+    }
+}
+
+/// Monto de un pedido (`monto_total`, DOUBLE PRECISION) en centavos, para
+/// compararlo con el crédito; None si no es finito
+pub fn monto_en_centavos(monto: f64) -> Option<Decimal> {
+    Decimal::try_from(monto).ok().map(|monto| monto.round_dp(2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(valor: &str) -> Decimal {
+        valor.parse().unwrap()
+    }
+
+    fn estado(limite: Option<&str>, saldo_facturas: &str, pedidos_por_facturar: &str) -> EstadoCredito {
+        EstadoCredito {
+            id_perfil: Uuid::new_v4(),
+            documento_identidad: None,
+            limite_credito: limite.map(d),
+            dias_credito: Some(30),
+            saldo_facturas: d(saldo_facturas),
+            saldo_vencido: Decimal::ZERO,
+            pedidos_por_facturar: d(pedidos_por_facturar),
+        }
+    }
+
+    #[test]
+    fn disponible_descuenta_facturas_y_pedidos() {
+        assert_eq!(estado(Some("1000"), "300", "200").disponible(), Some(d("500")));
+        assert_eq!(estado(Some("1000"), "0", "0").disponible(), Some(d("1000")));
+    }
+
+    #[test]
+    fn disponible_no_es_negativo_sobre_el_limite() {
+        assert_eq!(estado(Some("100"), "150", "0").disponible(), Some(Decimal::ZERO));
+    }
+
+    #[test]
+    fn sin_limite_no_hay_disponible_ni_utilizacion() {
+        let estado = estado(None, "500", "500");
+        assert_eq!(estado.disponible(), None);
+        assert_eq!(estado.utilizacion(), None);
+        assert!(!estado.excede_con(d("1000000")));
+    }
+
+    #[test]
+    fn utilizacion_es_la_fraccion_del_limite() {
+        assert_eq!(estado(Some("1000"), "250", "250").utilizacion(), Some(0.5));
+        assert_eq!(estado(Some("100"), "150", "0").utilizacion(), Some(1.5));
+        assert_eq!(estado(Some("0"), "10", "0").utilizacion(), None);
+    }
+
+    #[test]
+    fn excede_con_permite_llegar_justo_al_limite() {
+        let estado = estado(Some("1000"), "600", "300");
+        assert!(!estado.excede_con(d("100")));
+        assert!(estado.excede_con(d("100.01")));
+    }
+
+    #[test]
+    fn excede_con_montos_con_centavos_llega_justo_al_limite() {
+        // En f64, 1.1 + 2.2 = 3.3000000000000003 > 3.3
+        let estado = estado(Some("3.30"), "1.10", "0");
+        assert!(!estado.excede_con(monto_en_centavos(2.2).unwrap()));
+        assert!(estado.excede_con(monto_en_centavos(2.21).unwrap()));
+        assert_eq!(estado.disponible(), Some(d("2.20")));
+    }
+
+    #[test]
+    fn excede_con_suma_facturas_y_pedidos_sin_error_de_redondeo() {
+        // En f64, 0.1 + 0.2 + 0.3 = 0.6000000000000001 > 0.6
+        let estado = estado(Some("0.60"), "0.10", "0.20");
+        assert!(!estado.excede_con(monto_en_centavos(0.3).unwrap()));
+        assert!(estado.excede_con(monto_en_centavos(0.31).unwrap()));
+    }
+
+    #[test]
+    fn monto_en_centavos_redondea_el_double() {
+        assert_eq!(monto_en_centavos(2.2), Some(d("2.20")));
+        assert_eq!(monto_en_centavos(0.1 + 0.2), Some(d("0.30")));
+        assert_eq!(monto_en_centavos(19.999), Some(d("20.00")));
+        assert_eq!(monto_en_centavos(f64::NAN), None);
+        assert_eq!(monto_en_centavos(f64::INFINITY), None);
+    }
+
+    #[test]
+    fn limite_cero_rechaza_cualquier_monto() {
+        let estado = estado(Some("0"), "0", "0");
+        assert!(!estado.excede_con(Decimal::ZERO));
+        assert!(estado.excede_con(d("0.01")));
+    }
+}
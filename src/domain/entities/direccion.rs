@@ -47,3 +47,12 @@ pub struct Direccion {
     /// Fecha de última actualización
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
+
+/// Almacén activo más cercano a un punto (distancia en línea recta)
+#[derive(Debug, Clone, FromRow)]
+pub struct AlmacenCercano {
+    pub id_almacen: Uuid,
+    pub calle: String,
+    pub ciudad: String,
+    pub distancia_km: f64,
+}
@@ -7,9 +7,26 @@ pub mod factura;
 pub mod pago;
 pub mod zona;
 pub mod perfil_cliente;
+pub mod session_revocation;
+pub mod api_key;
+pub mod audit_log;
+pub mod perfil_miembro;
+pub mod credito;
+pub mod perfil_fusion;
+pub mod categoria;
+pub mod movimiento_stock;
 
 pub use user::*;
 pub use producto::*;
 pub use pedido::*;
 pub use direccion::*;
 pub use perfil_cliente::*;
+pub use transportista::*;
+pub use session_revocation::*;
+pub use api_key::*;
+pub use audit_log::*;
+pub use perfil_miembro::*;
+pub use credito::*;
+pub use perfil_fusion::*;
+pub use categoria::*;
+pub use movimiento_stock::*;
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::domain::auth::AuthenticatedPrincipal;
+
+/// Entidad MovimientoStock - Entrada del kardex de un producto (solo inserción)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MovimientoStock {
+    pub id_movimiento: Uuid,
+    pub id_producto: Uuid,
+    /// Positiva = entrada, negativa = salida
+    pub cantidad: i32,
+    /// Stock total del producto después del movimiento
+    pub saldo: i32,
+    /// Almacén afectado (None = movimientos anteriores al inventario por almacén)
+    pub id_almacen: Option<Uuid>,
+    /// Stock del producto en el almacén después del movimiento
+    pub saldo_almacen: Option<i32>,
+    /// Agrupa la salida y la entrada de una transferencia entre almacenes
+    pub id_transferencia: Option<Uuid>,
+    pub motivo: String,
+    /// Usuario que registró el movimiento (None = API key, migración o proceso interno)
+    pub id_actor: Option<Uuid>,
+    /// API key que registró el movimiento
+    pub id_api_key: Option<Uuid>,
+    /// Pedido relacionado, si lo hay
+    pub id_pedido: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Quién registra un movimiento de stock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActorMovimiento {
+    Usuario(Uuid),
+    ApiKey(Uuid),
+}
+
+impl ActorMovimiento {
+    pub fn id_actor(&self) -> Option<Uuid> {
+        match self {
+            ActorMovimiento::Usuario(id) => Some(*id),
+            ActorMovimiento::ApiKey(_) => None,
+        }
+    }
+
+    pub fn id_api_key(&self) -> Option<Uuid> {
+        match self {
+            ActorMovimiento::ApiKey(id) => Some(*id),
+            ActorMovimiento::Usuario(_) => None,
+        }
+    }
+}
+
+/// Durante una suplantación el actor es el admin real, no el usuario suplantado
+impl From<&AuthenticatedPrincipal> for ActorMovimiento {
+    fn from(principal: &AuthenticatedPrincipal) -> Self {
+        match principal {
+            AuthenticatedPrincipal::User(user) => ActorMovimiento::Usuario(user.impersonated_by.unwrap_or(user.id)),
+            AuthenticatedPrincipal::ApiKey(api_key) => ActorMovimiento::ApiKey(api_key.id),
+        }
+    }
+}
+
+impl std::fmt::Display for ActorMovimiento {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActorMovimiento::Usuario(id) => write!(f, "usuario {}", id),
+            ActorMovimiento::ApiKey(id) => write!(f, "API key {}", id),
+        }
+    }
+}
+
+/// Producto cuyo stock no coincide con la suma de su kardex o de sus almacenes
+#[derive(Debug, Clone, FromRow)]
+pub struct DescuadreStock {
+    pub id_producto: Uuid,
+    pub nombre_producto: String,
+    pub sku: Option<String>,
+    pub stock: i32,
+    pub suma_movimientos: i64,
+    pub movimientos: i64,
+    /// Suma de `inventario_almacen.stock` del producto
+    pub suma_almacenes: i64,
+    /// Almacenes cuyo stock no coincide con sus movimientos
+    pub almacenes_descuadrados: i64,
+}
+
+/// Stock de un producto en un almacén
+#[derive(Debug, Clone, FromRow)]
+pub struct InventarioAlmacen {
+    pub id_producto: Uuid,
+    pub id_almacen: Uuid,
+    /// Nombre y calle del almacén (`direcciones.calle`)
+    pub almacen: String,
+    pub ciudad: String,
+    /// Los almacenes inactivos no cuentan como disponibilidad del catálogo
+    pub activo: bool,
+    pub stock: i32,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::auth::{ApiKeyPrincipal, AuthenticatedUser};
+
+    fn usuario(id: Uuid, impersonated_by: Option<Uuid>) -> AuthenticatedPrincipal {
+        AuthenticatedPrincipal::User(AuthenticatedUser { id, email: None, role: None, impersonated_by })
+    }
+
+    #[test]
+    fn el_actor_de_un_usuario_es_el_usuario() {
+        let id = Uuid::new_v4();
+        let actor = ActorMovimiento::from(&usuario(id, None));
+
+        assert_eq!(actor, ActorMovimiento::Usuario(id));
+        assert_eq!((actor.id_actor(), actor.id_api_key()), (Some(id), None));
+    }
+
+    #[test]
+    fn durante_una_suplantacion_el_actor_es_el_admin() {
+        let (suplantado, admin) = (Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(ActorMovimiento::from(&usuario(suplantado, Some(admin))), ActorMovimiento::Usuario(admin));
+    }
+
+    #[test]
+    fn el_actor_de_una_api_key_es_la_key() {
+        let id = Uuid::new_v4();
+        let principal = AuthenticatedPrincipal::ApiKey(ApiKeyPrincipal {
+            id,
+            nombre: "Escáner bodega".to_string(),
+            scopes: vec!["productos:write".to_string()],
+        });
+        let actor = ActorMovimiento::from(&principal);
+
+        assert_eq!(actor, ActorMovimiento::ApiKey(id));
+        assert_eq!((actor.id_actor(), actor.id_api_key()), (None, Some(id)));
+    }
+}
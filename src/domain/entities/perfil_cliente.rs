@@ -1,3 +1,4 @@
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
@@ -8,7 +9,16 @@ pub struct PerfilCliente {
     pub id_perfil: Uuid,
     pub id_usuario: Uuid,
     pub documento_identidad: Option<String>,
+    /// Tipo detectado al validar el documento (ver `TipoDocumento`)
+    pub tipo_documento: Option<String>,
+    /// Teléfono en formato E.164
     pub telefono: Option<String>,
+    /// Tipo de línea: movil, fijo o fijo_o_movil
+    pub tipo_telefono: Option<String>,
+    /// Máximo adeudado a crédito; None = sin control de crédito
+    pub limite_credito: Option<Decimal>,
+    /// Plazo de pago de las facturas, en días
+    pub dias_credito: Option<i32>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
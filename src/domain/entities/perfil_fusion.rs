@@ -0,0 +1,84 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Entidad PerfilFusion - Registro de un perfil absorbido por otro al fusionar duplicados
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PerfilFusion {
+    /// Perfil eliminado
+    pub id_perfil_origen: Uuid,
+    /// Perfil que lo absorbió (siempre uno vigente)
+    pub id_perfil_destino: Uuid,
+    pub id_usuario_origen: Option<Uuid>,
+    pub documento_origen: Option<String>,
+    pub telefono_origen: Option<String>,
+    pub direcciones_movidas: i32,
+    pub pedidos_movidos: i32,
+    pub miembros_movidos: i32,
+    pub fusionado_por: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Límite y plazo de crédito de un perfil (`limite_credito`, `dias_credito`)
+pub type TerminosCredito = (Option<Decimal>, Option<i32>);
+
+/// Términos de crédito con los que queda el destino de una fusión
+///
+/// Si solo uno de los perfiles tiene límite, el destino queda con los términos
+/// de ese. Si ambos tienen límite y los términos difieren devuelve `None`: la
+/// fusión no elige entre dos créditos aprobados por separado.
+pub fn terminos_credito_fusion(destino: TerminosCredito, origen: TerminosCredito) -> Option<TerminosCredito> {
+    match (destino.0, origen.0) {
+        (_, None) => Some(destino),
+        (None, Some(_)) => Some(origen),
+        (Some(_), Some(_)) if destino == origen => Some(destino),
+        (Some(_), Some(_)) => None,
+    }
+}
+
+/// Perfil que comparte una clave normalizada (`criterio`/`clave`) con otro
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PerfilDuplicado {
+    /// documento, telefono o email
+    pub criterio: String,
+    /// Valor normalizado que coincide
+    pub clave: String,
+    pub id_perfil: Uuid,
+    pub id_usuario: Uuid,
+    pub email: Option<String>,
+    pub documento_identidad: Option<String>,
+    pub telefono: Option<String>,
+    pub direcciones: i64,
+    pub pedidos: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sin_credito_en_el_origen_conserva_el_destino() {
+        assert_eq!(terminos_credito_fusion((Some(Decimal::from(500)), Some(30)), (None, None)), Some((Some(Decimal::from(500)), Some(30))));
+        assert_eq!(terminos_credito_fusion((None, None), (None, None)), Some((None, None)));
+    }
+
+    #[test]
+    fn el_destino_sin_limite_toma_el_del_origen() {
+        assert_eq!(terminos_credito_fusion((None, None), (Some(Decimal::from(800)), Some(15))), Some((Some(Decimal::from(800)), Some(15))));
+        assert_eq!(terminos_credito_fusion((None, None), (Some(Decimal::from(0)), None)), Some((Some(Decimal::from(0)), None)));
+    }
+
+    #[test]
+    fn terminos_iguales_se_conservan() {
+        assert_eq!(terminos_credito_fusion((Some(Decimal::from(500)), Some(30)), (Some(Decimal::from(500)), Some(30))), Some((Some(Decimal::from(500)), Some(30))));
+    }
+
+    #[test]
+    fn terminos_distintos_son_conflicto() {
+        assert_eq!(terminos_credito_fusion((Some(Decimal::from(500)), Some(30)), (Some(Decimal::from(800)), Some(30))), None);
+        assert_eq!(terminos_credito_fusion((Some(Decimal::from(500)), Some(30)), (Some(Decimal::from(500)), Some(60))), None);
+        assert_eq!(terminos_credito_fusion((Some(Decimal::from(500)), None), (Some(Decimal::from(500)), Some(30))), None);
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Entidad PerfilMiembro - Usuario con acceso a un perfil de cliente de empresa
+///
+/// Incluye email y nombre del usuario (join con `users`) para mostrar la lista de miembros.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PerfilMiembro {
+    pub id_perfil: Uuid,
+    pub id_usuario: Uuid,
+    /// owner, shipper o viewer
+    pub rol: String,
+    pub invitado_por: Option<Uuid>,
+    pub email: Option<String>,
+    pub nombre: Option<String>,
+    pub apellido: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
@@ -16,8 +16,10 @@ pub struct Producto {
     pub precio: Decimal,
     /// Stock disponible (CHECK: stock >= 0)
     pub stock: i32,
-    /// Categoría del producto
+    /// Nombre de la categoría (copia de `categorias.nombre`)
     pub categoria: Option<String>,
+    /// Categoría del producto
+    pub id_categoria: Option<Uuid>,
     /// Código SKU único
     pub sku: Option<String>,
     /// Estado activo/inactivo
@@ -27,3 +29,22 @@ pub struct Producto {
     /// Fecha de última actualización
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
+
+/// Marcas de inicio y fin de un fragmento resaltado en `ProductoBusqueda`.
+/// Son caracteres de control para que no choquen con el texto del producto;
+/// la capa de aplicación las convierte a HTML después de escapar el texto.
+pub const INICIO_RESALTADO: char = '\u{2}';
+pub const FIN_RESALTADO: char = '\u{3}';
+
+/// Resultado de búsqueda: el producto con su relevancia y fragmentos resaltados
+#[derive(Debug, Clone, FromRow)]
+pub struct ProductoBusqueda {
+    #[sqlx(flatten)]
+    pub producto: Producto,
+    /// Relevancia (mayor es mejor)
+    pub score: f64,
+    /// Nombre con los términos encontrados entre marcas de resaltado
+    pub nombre_resaltado: String,
+    /// Fragmentos de la descripción con los términos resaltados
+    pub descripcion_resaltada: Option<String>,
+}
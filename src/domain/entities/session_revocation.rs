@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Revocación de sesiones de un usuario
+///
+/// Todo token con `iat` anterior al segundo de `revocado_antes` deja de ser válido.
+///
+/// `iat` tiene resolución de segundos: un token emitido en el mismo segundo de la
+/// revocación sigue siendo válido. Así la sesión que se abre justo después de
+/// revocar (cambio de contraseña, re-login) no se rechaza por error; a cambio, un
+/// token emitido en ese mismo segundo, antes de revocar, sobrevive a la revocación.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SessionRevocation {
+    pub id_usuario: Uuid,
+    pub revocado_antes: DateTime<Utc>,
+    pub motivo: Option<String>,
+    pub revocado_por: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SessionRevocation {
+    /// Indica si un token emitido en `iat` (Unix timestamp) quedó revocado
+    pub fn revoca(&self, iat: i64) -> bool {
+        iat < self.revocado_antes.timestamp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn revocacion(revocado_antes: DateTime<Utc>) -> SessionRevocation {
+        SessionRevocation {
+            id_usuario: Uuid::new_v4(),
+            revocado_antes,
+            motivo: None,
+            revocado_por: None,
+            updated_at: revocado_antes,
+        }
+    }
+
+    #[test]
+    fn revoca_tokens_de_segundos_anteriores() {
+        let instante = Utc.with_ymd_and_hms(2024, 12, 8, 10, 0, 0).unwrap() + chrono::Duration::milliseconds(700);
+        let r = revocacion(instante);
+        assert!(r.revoca(instante.timestamp() - 1));
+        assert!(r.revoca(instante.timestamp() - 3600));
+    }
+
+    #[test]
+    fn el_mismo_segundo_sigue_siendo_valido() {
+        let instante = Utc.with_ymd_and_hms(2024, 12, 8, 10, 0, 0).unwrap() + chrono::Duration::milliseconds(700);
+        let r = revocacion(instante);
+        assert!(!r.revoca(instante.timestamp()));
+        assert!(!r.revoca(instante.timestamp() + 1));
+    }
+}
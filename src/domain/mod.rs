@@ -1,3 +1,5 @@
 pub mod entities;
 pub mod repositories;
 pub mod auth;
+pub mod storage;
+pub mod validation;
@@ -0,0 +1,22 @@
+use crate::domain::entities::ApiKey;
+use crate::shared::AppResult;
+use uuid::Uuid;
+
+/// Trait que define las operaciones de persistencia para API keys
+#[async_trait::async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    /// Lista todas las API keys (incluye revocadas y expiradas)
+    async fn find_all(&self) -> AppResult<Vec<ApiKey>>;
+
+    /// Busca una API key por su prefijo público
+    async fn find_by_prefijo(&self, prefijo: &str) -> AppResult<Option<ApiKey>>;
+
+    /// Crea una nueva API key
+    async fn create(&self, api_key: &ApiKey) -> AppResult<ApiKey>;
+
+    /// Marca la API key como revocada
+    async fn revoke(&self, id: Uuid) -> AppResult<Option<ApiKey>>;
+
+    /// Registra el último uso (como máximo una escritura por minuto)
+    async fn touch(&self, id: Uuid) -> AppResult<()>;
+}
@@ -0,0 +1,9 @@
+use crate::domain::entities::AuditLog;
+use crate::shared::AppResult;
+
+/// Trait que define las operaciones de persistencia para la bitácora de auditoría
+#[async_trait::async_trait]
+pub trait AuditLogRepository: Send + Sync {
+    /// Registra una entrada (la bitácora es solo de inserción)
+    async fn create(&self, entry: &AuditLog) -> AppResult<()>;
+}
@@ -0,0 +1,49 @@
+use crate::domain::entities::Categoria;
+use crate::shared::AppResult;
+use uuid::Uuid;
+
+/// Datos de una categoría nueva (ya validados)
+#[derive(Debug, Clone)]
+pub struct NuevaCategoria {
+    pub nombre: String,
+    pub slug: String,
+    pub id_padre: Option<Uuid>,
+    pub orden: i32,
+    pub activo: bool,
+}
+
+/// Cambios sobre una categoría; `None` deja el campo como está
+#[derive(Debug, Clone, Default)]
+pub struct CategoriaCambios {
+    pub nombre: Option<String>,
+    pub slug: Option<String>,
+    /// `Some(None)` la convierte en raíz
+    pub id_padre: Option<Option<Uuid>>,
+    pub orden: Option<i32>,
+    pub activo: Option<bool>,
+}
+
+/// Árbol de categorías de productos
+#[async_trait::async_trait]
+pub trait CategoriaRepository: Send + Sync {
+    /// Todas las categorías (incluye inactivas), por orden y nombre
+    async fn find_all(&self) -> AppResult<Vec<Categoria>>;
+
+    async fn find_by_id(&self, id: Uuid) -> AppResult<Option<Categoria>>;
+
+    async fn find_by_slug(&self, slug: &str) -> AppResult<Option<Categoria>>;
+
+    /// Categoría de un texto libre registrado en `categorias_legado` (antes de la migración 014)
+    async fn find_by_texto_legado(&self, texto: &str) -> AppResult<Option<Categoria>>;
+
+    /// Productos asignados directamente a cada categoría (sin contar subcategorías)
+    async fn count_productos(&self, solo_activos: bool) -> AppResult<Vec<(Uuid, i64)>>;
+
+    async fn create(&self, nueva: &NuevaCategoria) -> AppResult<Categoria>;
+
+    /// Aplica los cambios. Un nuevo padre no puede ser la categoría ni una de sus
+    /// subcategorías. Si cambia el nombre se actualiza la copia en `productos.categoria`.
+    async fn update(&self, id: Uuid, cambios: &CategoriaCambios) -> AppResult<Categoria>;
+
+    async fn delete(&self, id: Uuid) -> AppResult<()>;
+}
@@ -0,0 +1,23 @@
+use crate::domain::entities::EstadoCredito;
+use crate::shared::AppResult;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// Configuración y saldo de crédito de los perfiles de cliente
+#[async_trait::async_trait]
+pub trait CreditoRepository: Send + Sync {
+    /// Situación de crédito del perfil (None si no existe)
+    async fn find_estado(&self, id_perfil: Uuid) -> AppResult<Option<EstadoCredito>>;
+
+    /// Perfiles con límite cuyo monto comprometido es mayor a 0 y llega a
+    /// `umbral` veces su límite, de mayor a menor uso
+    async fn find_sobre_umbral(&self, umbral: f64) -> AppResult<Vec<EstadoCredito>>;
+
+    /// Cambia límite y plazo (None en el límite quita el control de crédito)
+    async fn update_config(
+        &self,
+        id_perfil: Uuid,
+        limite_credito: Option<Decimal>,
+        dias_credito: Option<i32>,
+    ) -> AppResult<EstadoCredito>;
+}
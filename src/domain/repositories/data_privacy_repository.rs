@@ -0,0 +1,20 @@
+use crate::domain::entities::AuditLog;
+use crate::shared::AppResult;
+use uuid::Uuid;
+
+/// Registros afectados por una anonimización
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnonymizationSummary {
+    pub perfiles: u64,
+    pub direcciones: u64,
+}
+
+/// Operaciones de privacidad que abarcan varias tablas en una sola transacción
+#[async_trait::async_trait]
+pub trait DataPrivacyRepository: Send + Sync {
+    /// Borra los datos personales del usuario en `users`, `perfiles_cliente` y
+    /// `direcciones`, y registra `audit` (con el resumen en `detalle`) en la misma
+    /// transacción. Pedidos y facturas no se modifican. Si el usuario es titular de
+    /// un perfil con otros miembros no cambia nada y devuelve `BadRequest`.
+    async fn anonymize_user(&self, id_usuario: Uuid, audit: &AuditLog) -> AppResult<AnonymizationSummary>;
+}
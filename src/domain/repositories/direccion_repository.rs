@@ -1,4 +1,4 @@
-use crate::domain::entities::Direccion;
+use crate::domain::entities::{AlmacenCercano, Direccion};
 use crate::shared::error::AppResult;
 use rust_decimal::Decimal;
 use uuid::Uuid;
@@ -20,6 +20,12 @@ pub trait DireccionRepository: Send + Sync {
     
     /// Busca todas las direcciones de tipo almacén (incluye inactivas) - Admin
     async fn find_all_almacenes(&self) -> AppResult<Vec<Direccion>>;
+
+    /// Almacén activo más cercano a unas coordenadas
+    async fn find_almacen_cercano(&self, latitud: f64, longitud: f64) -> AppResult<Option<AlmacenCercano>>;
+
+    /// Indica si un almacén tiene o tuvo inventario (stock o movimientos de kardex)
+    async fn tiene_inventario(&self, id: Uuid) -> AppResult<bool>;
     
     /// Obtiene la dirección predeterminada de un perfil
     async fn find_predeterminada(&self, id_perfil: Uuid) -> AppResult<Option<Direccion>>;
@@ -3,9 +3,30 @@ pub mod producto_repository;
 pub mod pedido_repository;
 pub mod perfil_cliente_repository;
 pub mod direccion_repository;
+pub mod transportista_repository;
+pub mod session_revocation_repository;
+pub mod api_key_repository;
+pub mod audit_log_repository;
+pub mod data_privacy_repository;
+pub mod perfil_miembro_repository;
+pub mod credito_repository;
+pub mod perfil_fusion_repository;
+pub mod categoria_repository;
 
 pub use user_repository::*;
 pub use pedido_repository::PedidoRepository;
 pub use perfil_cliente_repository::PerfilClienteRepository;
-pub use producto_repository::ProductoRepository;
+pub use producto_repository::{
+    MovimientosStockQuery, NuevaTransferenciaStock, NuevoMovimientoStock, ProductoFilter, ProductoPageQuery,
+    ProductoRepository, ProductoSortField,
+};
 pub use direccion_repository::DireccionRepository;
+pub use transportista_repository::TransportistaRepository;
+pub use session_revocation_repository::SessionRevocationRepository;
+pub use api_key_repository::ApiKeyRepository;
+pub use audit_log_repository::AuditLogRepository;
+pub use data_privacy_repository::{AnonymizationSummary, DataPrivacyRepository};
+pub use perfil_miembro_repository::PerfilMiembroRepository;
+pub use credito_repository::CreditoRepository;
+pub use perfil_fusion_repository::PerfilFusionRepository;
+pub use categoria_repository::{CategoriaCambios, CategoriaRepository, NuevaCategoria};
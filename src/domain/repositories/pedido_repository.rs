@@ -1,4 +1,4 @@
-use crate::domain::entities::Pedido;
+use crate::domain::entities::{AuditLog, Pedido};
 use crate::shared::AppResult;
 use uuid::Uuid;
 
@@ -12,11 +12,19 @@ pub trait PedidoRepository: Send + Sync {
     /// Listar todos los pedidos de un perfil/usuario
     async fn find_by_perfil(&self, id_perfil: Uuid) -> AppResult<Vec<Pedido>>;
     
+    /// Cantidad de pedidos del perfil agrupados por estado (solo estados con pedidos)
+    async fn count_by_estado(&self, id_perfil: Uuid) -> AppResult<Vec<(String, i64)>>;
+    
     /// Listar pedidos asignados a un transportista
     async fn find_by_transportista(&self, id_transportista: Uuid) -> AppResult<Vec<Pedido>>;
     
     /// Crear nuevo pedido
-    async fn create(&self, pedido: &Pedido) -> AppResult<Pedido>;
+    ///
+    /// Bloquea el perfil mientras verifica su límite de crédito, para que dos
+    /// pedidos simultáneos no lo superen; devuelve `CreditLimitExceeded` si lo
+    /// superaría. Con `omision` (registro de la omisión del límite) no se verifica
+    /// y el registro se inserta en la misma transacción que el pedido.
+    async fn create(&self, pedido: &Pedido, omision: Option<&AuditLog>) -> AppResult<Pedido>;
     
     /// Actualizar estado del pedido
     async fn update_estado(&self, id: Uuid, estado: &str) -> AppResult<Pedido>;
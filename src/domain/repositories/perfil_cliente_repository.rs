@@ -1,4 +1,5 @@
 use crate::domain::entities::PerfilCliente;
+use crate::domain::validation::{Telefono, TipoDocumento};
 use uuid::Uuid;
 
 /// Trait que define las operaciones del repositorio de perfiles de cliente
@@ -13,11 +14,11 @@ pub trait PerfilClienteRepository: Send + Sync {
     /// Obtiene todos los perfiles (admin)
     async fn find_all(&self) -> Result<Vec<PerfilCliente>, sqlx::Error>;
     
-    /// Crea un nuevo perfil
-    async fn create(&self, id_usuario: Uuid, documento: Option<&str>, telefono: Option<&str>) -> Result<PerfilCliente, sqlx::Error>;
+    /// Crea un nuevo perfil (documento y teléfono ya validados y normalizados)
+    async fn create(&self, id_usuario: Uuid, documento: Option<(&str, TipoDocumento)>, telefono: Option<&Telefono>) -> Result<PerfilCliente, sqlx::Error>;
     
-    /// Actualiza un perfil existente
-    async fn update(&self, id: Uuid, documento: Option<&str>, telefono: Option<&str>) -> Result<PerfilCliente, sqlx::Error>;
+    /// Actualiza un perfil existente; los campos en `None` no cambian
+    async fn update(&self, id: Uuid, documento: Option<(&str, TipoDocumento)>, telefono: Option<&Telefono>) -> Result<PerfilCliente, sqlx::Error>;
     
     /// Elimina un perfil (soft delete en el futuro)
     async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
@@ -0,0 +1,26 @@
+use crate::domain::entities::{AuditLog, PerfilCliente, PerfilDuplicado, PerfilFusion};
+use crate::shared::AppResult;
+use uuid::Uuid;
+
+/// Detección y fusión de perfiles de cliente duplicados
+#[async_trait::async_trait]
+pub trait PerfilFusionRepository: Send + Sync {
+    /// Perfiles que comparten documento, teléfono o email normalizado con otro,
+    /// ordenados por criterio, clave y antigüedad
+    async fn find_duplicados(&self) -> AppResult<Vec<PerfilDuplicado>>;
+
+    /// Mueve direcciones, pedidos y miembros de `id_origen` a `id_destino`,
+    /// elimina el origen y deja el registro de la fusión y `audit` (con lo movido
+    /// como detalle) en la misma transacción. Con `rol_miembro_origen`, el usuario del origen queda como
+    /// miembro del destino con ese rol.
+    async fn fusionar(
+        &self,
+        id_destino: Uuid,
+        id_origen: Uuid,
+        rol_miembro_origen: Option<&str>,
+        audit: &AuditLog,
+    ) -> AppResult<(PerfilCliente, PerfilFusion)>;
+
+    /// Fusiones realizadas, de la más reciente a la más antigua
+    async fn find_fusiones(&self) -> AppResult<Vec<PerfilFusion>>;
+}
@@ -0,0 +1,25 @@
+use crate::domain::entities::PerfilMiembro;
+use crate::shared::AppResult;
+use uuid::Uuid;
+
+/// Trait que define las operaciones del repositorio de miembros de perfiles
+#[async_trait::async_trait]
+pub trait PerfilMiembroRepository: Send + Sync {
+    /// Miembros de un perfil (sin el titular)
+    async fn find_by_perfil(&self, id_perfil: Uuid) -> AppResult<Vec<PerfilMiembro>>;
+
+    /// Membresías de un usuario en perfiles ajenos
+    async fn find_by_usuario(&self, id_usuario: Uuid) -> AppResult<Vec<PerfilMiembro>>;
+
+    /// Membresía de un usuario en un perfil concreto
+    async fn find(&self, id_perfil: Uuid, id_usuario: Uuid) -> AppResult<Option<PerfilMiembro>>;
+
+    /// Agrega un miembro
+    async fn create(&self, id_perfil: Uuid, id_usuario: Uuid, rol: &str, invitado_por: Uuid) -> AppResult<PerfilMiembro>;
+
+    /// Cambia el rol de un miembro
+    async fn update_rol(&self, id_perfil: Uuid, id_usuario: Uuid, rol: &str) -> AppResult<PerfilMiembro>;
+
+    /// Quita un miembro
+    async fn delete(&self, id_perfil: Uuid, id_usuario: Uuid) -> AppResult<()>;
+}
@@ -1,7 +1,108 @@
-use crate::domain::entities::Producto;
+use crate::domain::entities::{
+    ActorMovimiento, CategoriaConteo, DescuadreStock, InventarioAlmacen, MovimientoStock, Producto, ProductoBusqueda,
+};
 use crate::shared::error::AppResult;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rust_decimal::Decimal;
 use uuid::Uuid;
 
+/// Columnas por las que se puede ordenar el catálogo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProductoSortField {
+    Precio,
+    Nombre,
+    CreatedAt,
+}
+
+impl ProductoSortField {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "precio" => Some(Self::Precio),
+            "nombre" => Some(Self::Nombre),
+            "created_at" => Some(Self::CreatedAt),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Precio => "precio",
+            Self::Nombre => "nombre",
+            Self::CreatedAt => "created_at",
+        }
+    }
+
+    /// Valor de la columna de orden para un producto, tal como se guarda en el cursor
+    pub fn key(&self, producto: &Producto) -> String {
+        match self {
+            Self::Precio => producto.precio.to_string(),
+            Self::Nombre => producto.nombre_producto.clone(),
+            Self::CreatedAt => producto.created_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+        }
+    }
+}
+
+/// Filtros del catálogo público (siempre solo productos activos)
+#[derive(Debug, Clone, Default)]
+pub struct ProductoFilter {
+    /// Slugs de categorías aceptadas, incluidas sus subcategorías activas (vacío = todas)
+    pub categorias: Vec<String>,
+    pub precio_min: Option<Decimal>,
+    pub precio_max: Option<Decimal>,
+    /// Solo productos disponibles (stock > 0 en algún almacén activo, o en `id_almacen`)
+    pub solo_en_stock: bool,
+    /// Almacén con el que se calcula la disponibilidad (None = todos los activos)
+    pub id_almacen: Option<Uuid>,
+}
+
+/// Página del catálogo: filtros, orden y posición desde la que continuar
+#[derive(Debug, Clone)]
+pub struct ProductoPageQuery {
+    pub filter: ProductoFilter,
+    pub sort: ProductoSortField,
+    pub descending: bool,
+    /// Valor de orden e id del último producto de la página anterior (keyset)
+    pub after: Option<(String, Uuid)>,
+    /// Productos a saltar (paginación por número de página)
+    pub offset: i64,
+    pub limit: i64,
+}
+
+/// Movimiento de stock a registrar junto con el cambio de `productos.stock`
+#[derive(Debug, Clone)]
+pub struct NuevoMovimientoStock {
+    pub id_almacen: Uuid,
+    /// Positiva = entrada, negativa = salida (distinta de 0)
+    pub cantidad: i32,
+    pub motivo: String,
+    /// None = proceso interno
+    pub actor: Option<ActorMovimiento>,
+    pub id_pedido: Option<Uuid>,
+}
+
+/// Traslado de stock de un producto entre dos almacenes
+#[derive(Debug, Clone)]
+pub struct NuevaTransferenciaStock {
+    pub id_almacen_origen: Uuid,
+    pub id_almacen_destino: Uuid,
+    /// Unidades a trasladar (> 0)
+    pub cantidad: i32,
+    pub motivo: String,
+    pub actor: Option<ActorMovimiento>,
+}
+
+/// Página del kardex de un producto, del más reciente al más antiguo
+#[derive(Debug, Clone)]
+pub struct MovimientosStockQuery {
+    pub id_producto: Uuid,
+    pub id_almacen: Option<Uuid>,
+    pub desde: Option<DateTime<Utc>>,
+    pub hasta: Option<DateTime<Utc>>,
+    /// Fecha e id del último movimiento de la página anterior (keyset)
+    pub after: Option<(DateTime<Utc>, Uuid)>,
+    pub limit: i64,
+}
+
 /// Trait que define las operaciones del repositorio de productos
 #[async_trait::async_trait]
 pub trait ProductoRepository: Send + Sync {
@@ -11,48 +112,92 @@ pub trait ProductoRepository: Send + Sync {
     /// Busca un producto por su SKU
     async fn find_by_sku(&self, sku: &str) -> AppResult<Option<Producto>>;
     
-    /// Lista todos los productos activos
-    async fn find_activos(&self) -> AppResult<Vec<Producto>>;
+    /// Obtiene una página del catálogo de productos activos
+    async fn find_page(&self, query: &ProductoPageQuery) -> AppResult<Vec<Producto>>;
+
+    /// Cuenta los productos activos que cumplen los filtros
+    async fn count(&self, filter: &ProductoFilter) -> AppResult<i64>;
+
+    /// Cuenta productos activos por categoría (directa) aplicando los filtros, excepto el de categoría
+    async fn count_by_categoria(&self, filter: &ProductoFilter) -> AppResult<Vec<CategoriaConteo>>;
     
     /// Lista todos los productos (incluye inactivos)
     async fn find_all(&self) -> AppResult<Vec<Producto>>;
     
-    /// Busca productos por categoría
-    async fn find_by_categoria(&self, categoria: &str) -> AppResult<Vec<Producto>>;
-    
-    /// Busca productos por nombre (ILIKE)
-    async fn search(&self, query: &str) -> AppResult<Vec<Producto>>;
+    /// Búsqueda de texto completo (nombre, SKU, categoría, descripción) más
+    /// prefijo de SKU, ordenada por relevancia
+    async fn search(&self, query: &str, limit: i64) -> AppResult<Vec<ProductoBusqueda>>;
+
+    /// Búsqueda por similitud de trigramas sobre el nombre (tolera errores de tipeo)
+    async fn search_similares(&self, query: &str, limit: i64) -> AppResult<Vec<ProductoBusqueda>>;
     
-    /// Crea un nuevo producto
+    /// Crea un nuevo producto; el stock inicial (en un almacén) queda como primer
+    /// movimiento del kardex
     async fn create(
         &self,
         nombre: &str,
         descripcion: Option<&str>,
         precio: rust_decimal::Decimal,
-        stock: i32,
-        categoria: Option<&str>,
+        id_categoria: Option<Uuid>,
         sku: Option<&str>,
+        stock_inicial: Option<&NuevoMovimientoStock>,
     ) -> AppResult<Producto>;
     
-    /// Actualiza un producto existente
+    /// Actualiza un producto existente; `id_categoria = Some(None)` quita la categoría
     async fn update(
         &self,
         id: Uuid,
         nombre: Option<&str>,
         descripcion: Option<&str>,
         precio: Option<rust_decimal::Decimal>,
-        categoria: Option<&str>,
+        id_categoria: Option<Option<Uuid>>,
         sku: Option<&str>,
     ) -> AppResult<Producto>;
     
-    /// Actualiza el stock de un producto
-    async fn update_stock(&self, id: Uuid, cantidad: i32) -> AppResult<Producto>;
+    /// Ajusta el stock de un producto en un almacén (y su total) y registra el
+    /// movimiento en la misma transacción
+    async fn update_stock(
+        &self,
+        id: Uuid,
+        movimiento: &NuevoMovimientoStock,
+    ) -> AppResult<(Producto, MovimientoStock)>;
+
+    /// Traslada stock entre almacenes; registra la salida y la entrada en el kardex
+    async fn transferir_stock(
+        &self,
+        id: Uuid,
+        transferencia: &NuevaTransferenciaStock,
+    ) -> AppResult<(Producto, Vec<MovimientoStock>)>;
+
+    /// Pasa al almacén las unidades del producto que no están en ningún almacén
+    /// (`stock` menos la suma de sus almacenes), registradas como una
+    /// transferencia desde "sin almacén". Devuelve las unidades asignadas.
+    async fn asignar_stock_sin_almacen(&self, id: Uuid, id_almacen: Uuid, motivo: &str) -> AppResult<i32>;
+
+    /// Stock por almacén de los productos dados
+    async fn find_inventario(&self, ids: &[Uuid]) -> AppResult<Vec<InventarioAlmacen>>;
+
+    /// Stock disponible por producto: en almacenes activos, o solo en `id_almacen`
+    async fn find_disponibilidad(&self, ids: &[Uuid], id_almacen: Option<Uuid>) -> AppResult<Vec<(Uuid, i64)>>;
+
+    /// Movimientos del kardex de un producto (página)
+    async fn find_movimientos(&self, query: &MovimientosStockQuery) -> AppResult<Vec<MovimientoStock>>;
+
+    /// Cuenta los movimientos que cumplen el rango de fechas (ignora cursor y límite)
+    async fn count_movimientos(&self, query: &MovimientosStockQuery) -> AppResult<i64>;
+
+    /// Productos cuyo stock no coincide con la suma de sus movimientos
+    async fn find_descuadres_stock(&self) -> AppResult<Vec<DescuadreStock>>;
+
+    /// Cantidad total de productos (incluye inactivos)
+    async fn count_all(&self) -> AppResult<i64>;
     
     /// Cambia el estado (activo/inactivo) de un producto
     async fn update_estado(&self, id: Uuid, estado: bool) -> AppResult<Producto>;
     
-    /// Elimina un producto (hard delete)
-    async fn delete(&self, id: Uuid) -> AppResult<()>;
+    /// Elimina un producto (hard delete); si tiene movimientos en el kardex solo
+    /// lo desactiva, para conservarlos. Devuelve `false` si lo desactivó.
+    async fn delete(&self, id: Uuid) -> AppResult<bool>;
     
     /// Verifica si existe un producto con el SKU dado
     async fn exists_sku(&self, sku: &str) -> AppResult<bool>;
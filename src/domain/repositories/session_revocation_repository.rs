@@ -0,0 +1,18 @@
+use crate::domain::entities::SessionRevocation;
+use crate::shared::AppResult;
+use uuid::Uuid;
+
+/// Trait que define las operaciones de persistencia para revocaciones de sesión
+#[async_trait::async_trait]
+pub trait SessionRevocationRepository: Send + Sync {
+    /// Obtiene la revocación vigente de un usuario
+    async fn find_by_usuario(&self, id_usuario: Uuid) -> AppResult<Option<SessionRevocation>>;
+
+    /// Revoca todas las sesiones emitidas hasta ahora para el usuario
+    async fn revoke_all(
+        &self,
+        id_usuario: Uuid,
+        motivo: Option<&str>,
+        revocado_por: Option<Uuid>,
+    ) -> AppResult<SessionRevocation>;
+}
@@ -0,0 +1,10 @@
+use crate::domain::entities::Transportista;
+use crate::shared::AppResult;
+use uuid::Uuid;
+
+/// Trait que define las operaciones de persistencia para Transportistas
+#[async_trait::async_trait]
+pub trait TransportistaRepository: Send + Sync {
+    /// Busca el transportista asociado a un usuario
+    async fn find_by_usuario(&self, id_usuario: Uuid) -> AppResult<Option<Transportista>>;
+}
@@ -1,6 +1,85 @@
-use crate::domain::entities::User;
+use chrono::{DateTime, SecondsFormat, Utc};
 use uuid::Uuid;
 
+use crate::domain::entities::User;
+
+/// Columnas por las que se puede ordenar el directorio de usuarios
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserSortField {
+    Email,
+    Nombre,
+    Apellido,
+    Rol,
+    Activo,
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl UserSortField {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "email" => Some(Self::Email),
+            "nombre" => Some(Self::Nombre),
+            "apellido" => Some(Self::Apellido),
+            "rol" => Some(Self::Rol),
+            "activo" => Some(Self::Activo),
+            "created_at" => Some(Self::CreatedAt),
+            "updated_at" => Some(Self::UpdatedAt),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::Nombre => "nombre",
+            Self::Apellido => "apellido",
+            Self::Rol => "rol",
+            Self::Activo => "activo",
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+        }
+    }
+
+    /// Valor de la columna de orden para un usuario, tal como se guarda en el cursor
+    ///
+    /// Los textos nulos se ordenan como cadena vacía.
+    pub fn key(&self, user: &User) -> String {
+        let timestamp = |t: &DateTime<Utc>| t.to_rfc3339_opts(SecondsFormat::Micros, true);
+        match self {
+            Self::Email => user.email.clone().unwrap_or_default(),
+            Self::Nombre => user.nombre.clone().unwrap_or_default(),
+            Self::Apellido => user.apellido.clone().unwrap_or_default(),
+            Self::Rol => user.rol.clone(),
+            Self::Activo => user.activo.to_string(),
+            Self::CreatedAt => timestamp(&user.created_at),
+            Self::UpdatedAt => timestamp(&user.updated_at),
+        }
+    }
+}
+
+/// Filtros del directorio de usuarios
+#[derive(Debug, Clone, Default)]
+pub struct UserFilter {
+    pub rol: Option<String>,
+    pub activo: Option<bool>,
+    pub created_desde: Option<DateTime<Utc>>,
+    pub created_hasta: Option<DateTime<Utc>>,
+    /// Subcadena de email, nombre o apellido (sin distinguir mayúsculas)
+    pub texto: Option<String>,
+}
+
+/// Página del directorio: filtros, orden y posición (keyset) desde la que continuar
+#[derive(Debug, Clone)]
+pub struct UserPageQuery {
+    pub filter: UserFilter,
+    pub sort: UserSortField,
+    pub descending: bool,
+    /// Valor de orden e id del último usuario de la página anterior
+    pub after: Option<(String, Uuid)>,
+    pub limit: i64,
+}
+
 #[async_trait::async_trait]
 pub trait UserRepository: Send + Sync {
     /// Obtiene un usuario por ID
@@ -9,11 +88,11 @@ pub trait UserRepository: Send + Sync {
     /// Obtiene un usuario por email
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, sqlx::Error>;
     
-    /// Obtiene todos los usuarios
-    async fn find_all(&self) -> Result<Vec<User>, sqlx::Error>;
-    
-    /// Obtiene todos los usuarios con paginación
-    async fn find_all_paginated(&self, limit: i64, offset: i64) -> Result<(Vec<User>, i64), sqlx::Error>;
+    /// Obtiene una página del directorio de usuarios (paginación por cursor)
+    async fn find_page(&self, query: &UserPageQuery) -> Result<Vec<User>, sqlx::Error>;
+
+    /// Cuenta usuarios por rol aplicando los filtros, excepto el de rol
+    async fn count_by_rol(&self, filter: &UserFilter) -> Result<Vec<(String, i64)>, sqlx::Error>;
     
     /// Crea un nuevo usuario
     async fn create(&self, user: &User) -> Result<User, sqlx::Error>;
@@ -27,6 +106,15 @@ pub trait UserRepository: Send + Sync {
     /// Actualiza el estado de un usuario
     async fn update_status(&self, id: Uuid, activo: bool) -> Result<User, sqlx::Error>;
     
+    /// Crea el usuario si no existe (aprovisionamiento JIT) y devuelve la fila actual
+    async fn provision(&self, id: Uuid, email: Option<&str>, rol: &str) -> Result<User, sqlx::Error>;
+
+    /// Actualiza el email sincronizado desde Supabase
+    async fn update_email(&self, id: Uuid, email: &str) -> Result<User, sqlx::Error>;
+
+    /// Actualiza la foto de perfil (clave en el almacenamiento o `None` para quitarla)
+    async fn update_foto_perfil(&self, id: Uuid, foto_perfil: Option<&str>) -> Result<User, sqlx::Error>;
+    
     /// Elimina lógicamente un usuario
     async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
 }
@@ -0,0 +1,37 @@
+use crate::shared::AppResult;
+
+/// Objeto leído del almacenamiento
+#[derive(Debug, Clone)]
+pub struct StoredBlob {
+    pub data: Vec<u8>,
+    pub content_type: String,
+}
+
+/// Almacenamiento de archivos por clave (ej: `avatars/{id_usuario}/{uuid}.png`)
+///
+/// Las claves usan `/` como separador y solo letras, números, `-`, `_` y `.`.
+/// Implementaciones: sistema de archivos local; la interfaz es compatible con S3.
+#[async_trait::async_trait]
+pub trait BlobStorage: Send + Sync {
+    /// Guarda (o reemplaza) el objeto
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> AppResult<()>;
+
+    /// Lee el objeto; `None` si no existe
+    async fn get(&self, key: &str) -> AppResult<Option<StoredBlob>>;
+
+    /// Borra el objeto (no falla si no existe)
+    async fn delete(&self, key: &str) -> AppResult<()>;
+}
+
+/// Verifica que la clave sea relativa y no salga de su prefijo
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        })
+}
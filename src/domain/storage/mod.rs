@@ -0,0 +1,3 @@
+pub mod blob_storage;
+
+pub use blob_storage::*;
@@ -0,0 +1,243 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+/// Tipo de documento de identidad detectado al validar
+///
+/// Se guarda en `perfiles_cliente.tipo_documento`; facturación lo usa para
+/// identificar al comprador.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TipoDocumento {
+    /// Cédula ecuatoriana (10 dígitos)
+    Cedula,
+    /// RUC de persona natural: cédula + establecimiento
+    RucPersonaNatural,
+    /// RUC de sociedad privada (tercer dígito 9)
+    RucSociedadPrivada,
+    /// RUC de entidad pública (tercer dígito 6)
+    RucEntidadPublica,
+    /// Pasaporte u otro documento extranjero (sin dígito verificador)
+    Pasaporte,
+}
+
+impl TipoDocumento {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cedula => "cedula",
+            Self::RucPersonaNatural => "ruc_persona_natural",
+            Self::RucSociedadPrivada => "ruc_sociedad_privada",
+            Self::RucEntidadPublica => "ruc_entidad_publica",
+            Self::Pasaporte => "pasaporte",
+        }
+    }
+}
+
+/// Motivo por el que un documento no es válido
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocumentoError {
+    Formato,
+    Longitud,
+    Provincia,
+    TercerDigito,
+    DigitoVerificador,
+    Establecimiento,
+}
+
+impl fmt::Display for DocumentoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Formato => "El documento solo puede contener letras y dígitos",
+            Self::Longitud => "La cédula debe tener 10 dígitos, el RUC 13 y el pasaporte entre 5 y 20 caracteres",
+            Self::Provincia => "El código de provincia del documento no es válido",
+            Self::TercerDigito => "El tercer dígito del documento no corresponde a ningún tipo de contribuyente",
+            Self::DigitoVerificador => "El dígito verificador del documento no es válido",
+            Self::Establecimiento => "El código de establecimiento del RUC no puede ser cero",
+        };
+        f.write_str(msg)
+    }
+}
+
+/// Coeficientes del módulo 11 para RUC de sociedades privadas (9 dígitos)
+const COEF_SOCIEDAD_PRIVADA: [u32; 9] = [4, 3, 2, 7, 6, 5, 4, 3, 2];
+
+/// Coeficientes del módulo 11 para RUC de entidades públicas (8 dígitos)
+const COEF_ENTIDAD_PUBLICA: [u32; 8] = [3, 2, 7, 6, 5, 4, 3, 2];
+
+/// Valida un documento de identidad y devuelve el tipo detectado
+///
+/// Los documentos solo numéricos deben ser una cédula (10 dígitos) o un RUC
+/// (13 dígitos) con su dígito verificador; los alfanuméricos de 5 a 20
+/// caracteres se aceptan como pasaporte. El valor debe venir sin espacios.
+pub fn validar_documento(documento: &str) -> Result<TipoDocumento, DocumentoError> {
+    if !documento.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(DocumentoError::Formato);
+    }
+
+    if !documento.chars().all(|c| c.is_ascii_digit()) {
+        return if (5..=20).contains(&documento.len()) {
+            Ok(TipoDocumento::Pasaporte)
+        } else {
+            Err(DocumentoError::Longitud)
+        };
+    }
+
+    let digitos: Vec<u32> = documento.chars().filter_map(|c| c.to_digit(10)).collect();
+    match digitos.len() {
+        10 => validar_cedula(&digitos).map(|_| TipoDocumento::Cedula),
+        13 => validar_ruc(&digitos),
+        _ => Err(DocumentoError::Longitud),
+    }
+}
+
+/// Provincias 01-24, más 30 para ecuatorianos registrados en el exterior
+fn validar_provincia(digitos: &[u32]) -> Result<(), DocumentoError> {
+    let provincia = digitos[0] * 10 + digitos[1];
+    if (1..=24).contains(&provincia) || provincia == 30 {
+        Ok(())
+    } else {
+        Err(DocumentoError::Provincia)
+    }
+}
+
+/// Cédula: provincia, tercer dígito menor a 6 y dígito verificador módulo 10
+fn validar_cedula(digitos: &[u32]) -> Result<(), DocumentoError> {
+    validar_provincia(digitos)?;
+    if digitos[2] >= 6 {
+        return Err(DocumentoError::TercerDigito);
+    }
+
+    // Coeficientes 2,1,2,1...; los productos mayores a 9 se reducen restando 9
+    let suma: u32 = digitos[..9]
+        .iter()
+        .enumerate()
+        .map(|(i, d)| {
+            let p = if i % 2 == 0 { d * 2 } else { *d };
+            if p > 9 { p - 9 } else { p }
+        })
+        .sum();
+    let verificador = (10 - suma % 10) % 10;
+
+    if verificador == digitos[9] {
+        Ok(())
+    } else {
+        Err(DocumentoError::DigitoVerificador)
+    }
+}
+
+/// RUC: el tercer dígito determina el tipo de contribuyente
+fn validar_ruc(digitos: &[u32]) -> Result<TipoDocumento, DocumentoError> {
+    validar_provincia(digitos)?;
+
+    let (tipo, fin_verificador) = match digitos[2] {
+        0..=5 => {
+            validar_cedula(&digitos[..10])?;
+            (TipoDocumento::RucPersonaNatural, 10)
+        }
+        9 => {
+            validar_modulo_11(&digitos[..9], &COEF_SOCIEDAD_PRIVADA, digitos[9])?;
+            (TipoDocumento::RucSociedadPrivada, 10)
+        }
+        6 => {
+            validar_modulo_11(&digitos[..8], &COEF_ENTIDAD_PUBLICA, digitos[8])?;
+            (TipoDocumento::RucEntidadPublica, 9)
+        }
+        _ => return Err(DocumentoError::TercerDigito),
+    };
+
+    // Los dígitos después del verificador son el establecimiento (001, 0001...)
+    if digitos[fin_verificador..].iter().all(|d| *d == 0) {
+        return Err(DocumentoError::Establecimiento);
+    }
+
+    Ok(tipo)
+}
+
+/// Dígito verificador módulo 11: 11 - (suma % 11), con residuo 0 => 0
+fn validar_modulo_11(digitos: &[u32], coeficientes: &[u32], esperado: u32) -> Result<(), DocumentoError> {
+    let suma: u32 = digitos.iter().zip(coeficientes).map(|(d, c)| d * c).sum();
+    let verificador = match suma % 11 {
+        0 => 0,
+        residuo => 11 - residuo,
+    };
+
+    if verificador == esperado {
+        Ok(())
+    } else {
+        Err(DocumentoError::DigitoVerificador)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn acepta_cedulas_validas() {
+        for cedula in ["1710034065", "0926687856", "1713175071"] {
+            assert_eq!(validar_documento(cedula), Ok(TipoDocumento::Cedula), "{}", cedula);
+        }
+    }
+
+    #[test]
+    fn acepta_ruc_de_persona_natural() {
+        assert_eq!(validar_documento("1710034065001"), Ok(TipoDocumento::RucPersonaNatural));
+        assert_eq!(validar_documento("0926687856002"), Ok(TipoDocumento::RucPersonaNatural));
+    }
+
+    #[test]
+    fn acepta_ruc_de_sociedad_privada() {
+        assert_eq!(validar_documento("1790016919001"), Ok(TipoDocumento::RucSociedadPrivada));
+        assert_eq!(validar_documento("0992339411001"), Ok(TipoDocumento::RucSociedadPrivada));
+    }
+
+    #[test]
+    fn acepta_ruc_de_entidad_publica() {
+        assert_eq!(validar_documento("1760013210001"), Ok(TipoDocumento::RucEntidadPublica));
+    }
+
+    #[test]
+    fn acepta_pasaportes_alfanumericos() {
+        assert_eq!(validar_documento("AB123456"), Ok(TipoDocumento::Pasaporte));
+        assert_eq!(validar_documento("X1234"), Ok(TipoDocumento::Pasaporte));
+    }
+
+    #[test]
+    fn rechaza_provincia_invalida() {
+        // 00, 25 y 29 no son provincias; 30 (exterior) sí
+        assert_eq!(validar_documento("0010034065"), Err(DocumentoError::Provincia));
+        assert_eq!(validar_documento("2510034065"), Err(DocumentoError::Provincia));
+        assert_eq!(validar_documento("2990016919001"), Err(DocumentoError::Provincia));
+        assert_ne!(validar_documento("3010034060"), Err(DocumentoError::Provincia));
+    }
+
+    #[test]
+    fn rechaza_digito_verificador_invalido() {
+        assert_eq!(validar_documento("1710034066"), Err(DocumentoError::DigitoVerificador));
+        assert_eq!(validar_documento("1710034066001"), Err(DocumentoError::DigitoVerificador));
+        assert_eq!(validar_documento("1790016918001"), Err(DocumentoError::DigitoVerificador));
+        assert_eq!(validar_documento("1760013220001"), Err(DocumentoError::DigitoVerificador));
+    }
+
+    #[test]
+    fn rechaza_longitud_invalida() {
+        assert_eq!(validar_documento("171003406"), Err(DocumentoError::Longitud));
+        assert_eq!(validar_documento("17100340650"), Err(DocumentoError::Longitud));
+        assert_eq!(validar_documento("17100340650010"), Err(DocumentoError::Longitud));
+        assert_eq!(validar_documento("AB12"), Err(DocumentoError::Longitud));
+    }
+
+    #[test]
+    fn rechaza_tercer_digito_y_establecimiento() {
+        assert_eq!(validar_documento("1780016919001"), Err(DocumentoError::TercerDigito));
+        assert_eq!(validar_documento("1760034065"), Err(DocumentoError::TercerDigito));
+        assert_eq!(validar_documento("1710034065000"), Err(DocumentoError::Establecimiento));
+        assert_eq!(validar_documento("1760013210000"), Err(DocumentoError::Establecimiento));
+    }
+
+    #[test]
+    fn rechaza_caracteres_no_alfanumericos() {
+        assert_eq!(validar_documento("171003406-5"), Err(DocumentoError::Formato));
+        assert_eq!(validar_documento("1710034065 "), Err(DocumentoError::Formato));
+    }
+}
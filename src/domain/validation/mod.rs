@@ -0,0 +1,7 @@
+pub mod documento_identidad;
+pub mod telefono;
+pub mod slug;
+
+pub use documento_identidad::*;
+pub use telefono::*;
+pub use slug::*;
@@ -0,0 +1,70 @@
+use unicode_normalization::UnicodeNormalization;
+
+/// Slug en minúsculas, sin acentos y con guiones (ej: "Teléfonos Móviles" → "telefonos-moviles")
+///
+/// Devuelve una cadena vacía si el texto no tiene letras ni dígitos. No replica el
+/// slug de la migración 014, hecho con `f_unaccent`: las letras que unaccent
+/// translitera y NFD no descompone (ß → ss, æ → ae) aquí separan palabras. Los
+/// slugs se guardan al crear la categoría y no se recalculan.
+pub fn slugify(texto: &str) -> String {
+    let mut slug = String::with_capacity(texto.len());
+    let mut guion = false;
+
+    // NFD separa la letra base de su acento; solo se conservan letras y dígitos ASCII
+    for c in texto.nfd().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() {
+            if guion && !slug.is_empty() {
+                slug.push('-');
+            }
+            slug.push(c);
+            guion = false;
+        } else if !is_combining_mark(c) {
+            guion = true;
+        }
+    }
+
+    slug
+}
+
+/// Marcas diacríticas combinables (acentos, diéresis, virgulilla de la ñ)
+fn is_combining_mark(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036F}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quita_acentos_y_separa_con_guiones() {
+        assert_eq!(slugify("Teléfonos Móviles"), "telefonos-moviles");
+        assert_eq!(slugify("Niños y Bebés"), "ninos-y-bebes");
+        assert_eq!(slugify("Pingüino"), "pinguino");
+    }
+
+    #[test]
+    fn colapsa_separadores_y_recorta_los_extremos() {
+        assert_eq!(slugify("  Audio / Video -- HD  "), "audio-video-hd");
+        assert_eq!(slugify("TV_4K"), "tv-4k");
+        assert_eq!(slugify("¡Ofertas!"), "ofertas");
+    }
+
+    #[test]
+    fn es_idempotente_sobre_un_slug() {
+        assert_eq!(slugify("telefonos-moviles"), "telefonos-moviles");
+    }
+
+    #[test]
+    fn sin_letras_ni_digitos_devuelve_vacio() {
+        assert_eq!(slugify(""), "");
+        assert_eq!(slugify(" -_/ "), "");
+        assert_eq!(slugify("¿?"), "");
+    }
+
+    #[test]
+    fn letras_sin_descomposicion_nfd_separan_palabras() {
+        // f_unaccent daría "strasse" y "aeroflot"; ver el comentario de slugify
+        assert_eq!(slugify("Straße"), "stra-e");
+        assert_eq!(slugify("Æroflot"), "roflot");
+    }
+}
@@ -0,0 +1,125 @@
+use phonenumber::{country, metadata::DATABASE, Mode, Type};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+/// Región usada para números sin prefijo internacional (ej: "0991234567")
+const REGION_POR_DEFECTO: country::Id = country::Id::EC;
+
+/// Tipo de línea del teléfono normalizado
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TipoTelefono {
+    Movil,
+    Fijo,
+    /// Regiones donde el número no distingue fijo de móvil (ej: EE.UU.)
+    FijoOMovil,
+}
+
+impl TipoTelefono {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Movil => "movil",
+            Self::Fijo => "fijo",
+            Self::FijoOMovil => "fijo_o_movil",
+        }
+    }
+}
+
+/// Teléfono normalizado a E.164 (ej: "+593991234567")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Telefono {
+    pub e164: String,
+    pub tipo: TipoTelefono,
+}
+
+/// Motivo por el que un teléfono no es válido
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TelefonoError {
+    Formato,
+    Invalido,
+    TipoNoPermitido,
+}
+
+impl fmt::Display for TelefonoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Formato => "No se reconoce un número de teléfono. Use formato local (0991234567) o internacional (+593991234567)",
+            Self::Invalido => "El número de teléfono no existe en el plan de numeración de su país",
+            Self::TipoNoPermitido => "El número debe ser un teléfono fijo o móvil",
+        };
+        f.write_str(msg)
+    }
+}
+
+/// Normaliza un teléfono a E.164 y detecta si es fijo o móvil
+///
+/// Acepta espacios, guiones y paréntesis; los números sin `+` se interpretan
+/// como ecuatorianos. Se rechazan los números imposibles y las líneas
+/// especiales (gratuitas, tarificación adicional, etc.).
+pub fn normalizar_telefono(telefono: &str) -> Result<Telefono, TelefonoError> {
+    let numero = phonenumber::parse(Some(REGION_POR_DEFECTO), telefono.trim())
+        .map_err(|_| TelefonoError::Formato)?;
+
+    if !numero.is_valid() {
+        return Err(TelefonoError::Invalido);
+    }
+
+    let tipo = match numero.number_type(&DATABASE) {
+        Type::Mobile => TipoTelefono::Movil,
+        Type::FixedLine => TipoTelefono::Fijo,
+        Type::FixedLineOrMobile => TipoTelefono::FijoOMovil,
+        _ => return Err(TelefonoError::TipoNoPermitido),
+    };
+
+    Ok(Telefono {
+        e164: numero.format().mode(Mode::E164).to_string(),
+        tipo,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normaliza_formatos_locales_e_internacionales() {
+        for telefono in ["0991234567", "+593 99 123 4567", "099-123-4567", "(099) 123 4567", " 0991234567 "] {
+            let normalizado = normalizar_telefono(telefono).unwrap();
+            assert_eq!(normalizado.e164, "+593991234567", "{}", telefono);
+            assert_eq!(normalizado.tipo, TipoTelefono::Movil);
+        }
+    }
+
+    #[test]
+    fn detecta_telefonos_fijos() {
+        let normalizado = normalizar_telefono("02-234-5678").unwrap();
+        assert_eq!(normalizado.e164, "+59322345678");
+        assert_eq!(normalizado.tipo, TipoTelefono::Fijo);
+    }
+
+    #[test]
+    fn acepta_numeros_extranjeros_con_prefijo() {
+        let normalizado = normalizar_telefono("+1 202 555 0143").unwrap();
+        assert_eq!(normalizado.e164, "+12025550143");
+        assert_eq!(normalizado.tipo, TipoTelefono::FijoOMovil);
+    }
+
+    #[test]
+    fn rechaza_texto_que_no_es_un_numero() {
+        assert_eq!(normalizar_telefono("abc"), Err(TelefonoError::Formato));
+        assert_eq!(normalizar_telefono(""), Err(TelefonoError::Formato));
+    }
+
+    #[test]
+    fn rechaza_numeros_imposibles() {
+        assert_eq!(normalizar_telefono("099123"), Err(TelefonoError::Invalido));
+        assert_eq!(normalizar_telefono("09912345678901"), Err(TelefonoError::Invalido));
+    }
+
+    #[test]
+    fn rechaza_lineas_especiales() {
+        // 1800: línea gratuita
+        assert_eq!(normalizar_telefono("1800123456"), Err(TelefonoError::TipoNoPermitido));
+    }
+}
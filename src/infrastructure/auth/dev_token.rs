@@ -0,0 +1,127 @@
+use jsonwebtoken::{encode, EncodingKey, Header};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::domain::auth::{Claims, ROLES_VALIDOS};
+use crate::domain::repositories::UserRepository;
+use crate::shared::error::{AppError, AppResult};
+
+/// Único entorno en el que se permite emitir tokens locales
+pub const DEV_ENVIRONMENT: &str = "development";
+
+/// Duración por defecto de un token de desarrollo (1 hora)
+pub const DEV_TOKEN_DEFAULT_TTL: i64 = 3600;
+
+/// Token emitido para desarrollo local
+#[derive(Debug, Clone)]
+pub struct DevToken {
+    pub access_token: String,
+    pub user_id: Uuid,
+    pub rol: String,
+    pub expires_in: i64,
+}
+
+/// Emisor de JWT HS256 para desarrollo local y tests de integración
+///
+/// Firma con `SUPABASE_JWT_SECRET`, así `require_auth` acepta los tokens sin
+/// necesidad de un proyecto de Supabase. Solo se puede construir con
+/// `ENVIRONMENT=development` y `DEV_TOKENS_ENABLED=true`.
+pub struct DevTokenIssuer {
+    secret: String,
+    issuer: String,
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl DevTokenIssuer {
+    /// Verifica que el entorno permita emitir tokens locales
+    pub fn ensure_allowed(config: &AppConfig) -> Result<(), String> {
+        if !config.dev_tokens_enabled {
+            return Err("Los tokens de desarrollo están deshabilitados (DEV_TOKENS_ENABLED=true para habilitarlos)".to_string());
+        }
+        if config.environment != DEV_ENVIRONMENT {
+            return Err(format!(
+                "Los tokens de desarrollo solo están disponibles con ENVIRONMENT={} (actual: {})",
+                DEV_ENVIRONMENT, config.environment
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn from_config(config: &AppConfig, user_repo: Arc<dyn UserRepository>) -> Result<Self, String> {
+        Self::ensure_allowed(config)?;
+
+        let secret = config
+            .supabase_jwt_secret
+            .clone()
+            .ok_or_else(|| "SUPABASE_JWT_SECRET es necesario para firmar tokens de desarrollo".to_string())?;
+
+        Ok(Self {
+            secret,
+            issuer: config.jwt_issuer.clone(),
+            user_repo,
+        })
+    }
+
+    /// Emite un token para el usuario, creándolo en public.users con el rol indicado
+    ///
+    /// Nunca cambia el rol de un usuario existente: solo se emite si el rol pedido
+    /// coincide con el que ya tiene.
+    pub async fn issue(
+        &self,
+        user_id: Uuid,
+        email: Option<&str>,
+        rol: &str,
+        expires_in: i64,
+    ) -> AppResult<DevToken> {
+        if !ROLES_VALIDOS.contains(&rol) {
+            return Err(AppError::BadRequest(format!(
+                "Rol inválido. Roles válidos: {:?}",
+                ROLES_VALIDOS
+            )));
+        }
+        if expires_in <= 0 {
+            return Err(AppError::BadRequest("expires_in debe ser mayor a 0".to_string()));
+        }
+
+        // El rol de la app vive en public.users, no en el token
+        let user = self
+            .user_repo
+            .provision(user_id, email, rol)
+            .await
+            .map_err(|_| AppError::Internal("Error al registrar usuario".to_string()))?;
+        if user.rol != rol {
+            return Err(AppError::BadRequest(format!(
+                "El usuario {} ya existe con rol {}; los tokens de desarrollo no cambian roles",
+                user_id, user.rol
+            )));
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let claims = Claims {
+            aud: "authenticated".to_string(),
+            exp: now + expires_in,
+            iat: now,
+            iss: self.issuer.clone(),
+            sub: user_id,
+            email: email.map(str::to_string),
+            role: Some("authenticated".to_string()),
+        };
+
+        let access_token = encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(self.secret.as_bytes()),
+        )
+        .map_err(|e| AppError::Internal(format!("Error al firmar token: {}", e)))?;
+
+        tracing::warn!("Token de desarrollo emitido para {} (rol {})", user_id, rol);
+
+        Ok(DevToken {
+            access_token,
+            user_id,
+            rol: rol.to_string(),
+            expires_in,
+        })
+    }
+}
@@ -0,0 +1,335 @@
+use jsonwebtoken::{jwk::JwkSet, Algorithm, DecodingKey, Header};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::config::AppConfig;
+
+/// Tiempo que se considera vigente un JWKS descargado
+const JWKS_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Intervalo mínimo entre intentos de recarga (exitosos o no), para que un
+/// `kid` desconocido o un endpoint caído no generen una descarga por request
+const JWKS_REFRESH_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Algoritmos asimétricos aceptados (verificados con claves del JWKS)
+const ALGORITMOS_ASIMETRICOS: [Algorithm; 5] = [
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+];
+
+/// Origen del documento JWKS
+#[derive(Debug, Clone)]
+pub enum JwksSource {
+    /// URL remota (ej: `{SUPABASE_URL}/auth/v1/.well-known/jwks.json`)
+    Url(String),
+    /// Archivo local, útil para desarrollo sin red
+    File(String),
+}
+
+/// Errores al resolver la clave de verificación de un token
+#[derive(Debug)]
+pub enum KeyError {
+    /// El algoritmo del header no está permitido
+    UnsupportedAlgorithm(Algorithm),
+    /// Token HS256 pero no hay secret configurado
+    MissingSecret,
+    /// Token asimétrico pero no hay JWKS configurado
+    MissingJwks,
+    /// El token no trae `kid` o no existe en el JWKS
+    UnknownKid(Option<String>),
+    /// No se pudo obtener o interpretar el JWKS
+    Jwks(String),
+}
+
+impl std::fmt::Display for KeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyError::UnsupportedAlgorithm(alg) => write!(f, "algoritmo no permitido: {:?}", alg),
+            KeyError::MissingSecret => write!(f, "SUPABASE_JWT_SECRET no configurado"),
+            KeyError::MissingJwks => write!(f, "no hay JWKS configurado para tokens asimétricos"),
+            KeyError::UnknownKid(kid) => write!(f, "kid desconocido: {:?}", kid),
+            KeyError::Jwks(msg) => write!(f, "error de JWKS: {}", msg),
+        }
+    }
+}
+
+#[derive(Default)]
+struct JwksCache {
+    keys: Option<JwkSet>,
+    /// Última descarga exitosa
+    fetched_at: Option<Instant>,
+    /// Último intento de descarga
+    attempted_at: Option<Instant>,
+}
+
+impl JwksCache {
+    /// Indica si corresponde descargar el JWKS. `forced` = apareció un `kid` desconocido.
+    fn debe_recargar(&self, forced: bool, cooldown: Duration) -> bool {
+        if self.attempted_at.is_some_and(|t| t.elapsed() < cooldown) {
+            return false;
+        }
+        forced || self.fetched_at.is_none_or(|t| t.elapsed() >= JWKS_CACHE_TTL)
+    }
+}
+
+/// Proveedor de claves para validar JWT de Supabase
+///
+/// - HS256: usa `SUPABASE_JWT_SECRET`
+/// - RS*/ES*: usa el JWKS (remoto o archivo local), cacheado en memoria,
+///   seleccionando la clave por `kid` y recargando si aparece un `kid` desconocido
+///
+/// La descarga se hace sin tomar el lock de la caché: las verificaciones con
+/// claves conocidas no esperan a un JWKS lento.
+pub struct KeyProvider {
+    hs256_secret: Option<String>,
+    jwks_source: Option<JwksSource>,
+    cache: RwLock<JwksCache>,
+    /// Una sola descarga a la vez
+    refresh_lock: Mutex<()>,
+    refresh_cooldown: Duration,
+    http: reqwest::Client,
+}
+
+impl KeyProvider {
+    pub fn new(hs256_secret: Option<String>, jwks_source: Option<JwksSource>) -> Self {
+        Self {
+            hs256_secret,
+            jwks_source,
+            cache: RwLock::new(JwksCache::default()),
+            refresh_lock: Mutex::new(()),
+            refresh_cooldown: JWKS_REFRESH_COOLDOWN,
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(5))
+                .build()
+                .expect("No se pudo crear el cliente HTTP para JWKS"),
+        }
+    }
+
+    /// Construye el proveedor desde la configuración (el archivo local tiene prioridad)
+    pub fn from_config(config: &AppConfig) -> Self {
+        let jwks_source = config
+            .supabase_jwks_file
+            .clone()
+            .map(JwksSource::File)
+            .or_else(|| config.jwks_url().map(JwksSource::Url));
+
+        Self::new(config.supabase_jwt_secret.clone(), jwks_source)
+    }
+
+    /// Devuelve la clave con la que se debe verificar un token según su header
+    pub async fn decoding_key(&self, header: &Header) -> Result<DecodingKey, KeyError> {
+        if header.alg == Algorithm::HS256 {
+            return self
+                .hs256_secret
+                .as_ref()
+                .map(|secret| DecodingKey::from_secret(secret.as_bytes()))
+                .ok_or(KeyError::MissingSecret);
+        }
+
+        if !ALGORITMOS_ASIMETRICOS.contains(&header.alg) {
+            return Err(KeyError::UnsupportedAlgorithm(header.alg));
+        }
+
+        let kid = header.kid.as_deref().ok_or(KeyError::UnknownKid(None))?;
+
+        if let Some(key) = self.cached_key(kid, false).await? {
+            return Ok(key);
+        }
+
+        // kid desconocido: puede ser una rotación reciente, forzar recarga
+        self.refresh(true).await?;
+        self.cached_key(kid, true)
+            .await?
+            .ok_or_else(|| KeyError::UnknownKid(Some(kid.to_string())))
+    }
+
+    /// Busca el kid en caché, recargando si el JWKS expiró
+    async fn cached_key(&self, kid: &str, skip_refresh: bool) -> Result<Option<DecodingKey>, KeyError> {
+        if !skip_refresh {
+            self.refresh(false).await?;
+        }
+
+        let cache = self.cache.read().await;
+        match cache.keys.as_ref().and_then(|set| set.find(kid)) {
+            Some(jwk) => DecodingKey::from_jwk(jwk)
+                .map(Some)
+                .map_err(|e| KeyError::Jwks(format!("clave {} inválida: {}", kid, e))),
+            None => Ok(None),
+        }
+    }
+
+    /// Recarga el JWKS si expiró o, con `forced`, si pasó el intervalo mínimo
+    async fn refresh(&self, forced: bool) -> Result<(), KeyError> {
+        let source = self.jwks_source.as_ref().ok_or(KeyError::MissingJwks)?;
+
+        let (debe_recargar, hay_claves) = {
+            let cache = self.cache.read().await;
+            (cache.debe_recargar(forced, self.refresh_cooldown), cache.keys.is_some())
+        };
+        if !debe_recargar {
+            return Ok(());
+        }
+
+        // Si otro request ya está descargando, con claves en caché no se espera
+        let _descarga = match self.refresh_lock.try_lock() {
+            Ok(guard) => guard,
+            Err(_) if hay_claves => return Ok(()),
+            Err(_) => self.refresh_lock.lock().await,
+        };
+
+        // Otro request pudo haber recargado mientras esperábamos
+        if !self.cache.read().await.debe_recargar(forced, self.refresh_cooldown) {
+            return Ok(());
+        }
+        self.cache.write().await.attempted_at = Some(Instant::now());
+
+        let resultado = self.load(source).await;
+
+        let mut cache = self.cache.write().await;
+        match resultado {
+            Ok(keys) => {
+                tracing::info!("JWKS cargado ({} claves)", keys.keys.len());
+                cache.keys = Some(keys);
+                cache.fetched_at = Some(Instant::now());
+                Ok(())
+            }
+            Err(e) if cache.keys.is_some() => {
+                // Mantener las claves anteriores si la recarga falla
+                tracing::warn!("No se pudo recargar el JWKS, se usan las claves en caché: {}", e);
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn load(&self, source: &JwksSource) -> Result<JwkSet, KeyError> {
+        match source {
+            JwksSource::Url(url) => self
+                .http
+                .get(url)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|e| KeyError::Jwks(format!("GET {}: {}", url, e)))?
+                .json::<JwkSet>()
+                .await
+                .map_err(|e| KeyError::Jwks(format!("respuesta de {} inválida: {}", url, e))),
+            JwksSource::File(path) => {
+                let contents = tokio::fs::read_to_string(path)
+                    .await
+                    .map_err(|e| KeyError::Jwks(format!("no se pudo leer {}: {}", path, e)))?;
+                serde_json::from_str(&contents)
+                    .map_err(|e| KeyError::Jwks(format!("{} no es un JWKS válido: {}", path, e)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jwks(kids: &[&str]) -> String {
+        let keys: Vec<String> = kids
+            .iter()
+            .map(|kid| {
+                format!(
+                    r#"{{"kty":"RSA","kid":"{}","alg":"RS256","use":"sig","n":"sXchDaQebHnPiGvyDOAT4saGEUetSyo9MKLOoWFsueri23bOdgWp4Dy1WlUzewbgBHod5pcM9H95GQRV3JDXboIRROSBigeC5yjU1hGzHHyXss8UDprecbAYxknTcQkhslANGRUZmdTOQ5qTRsLAt6BTYuyvVRdhS8exSZEy_c4gs_7svlJJQ4H9_NxsiIoLwAEk7-Q3UXERGYw_75IDrGA84-lA_-Ct4eTlXHBIY2EaV7t7LjJaynVJCpkv4LKjTTAumiGUIuQhrNhZLuF_RJLqHpM2kgWFLU7-VTdL1VbC2tejvcI2BlMkEpk1BzBZI0KQB0GaDWFLN-aEAw3vRw","e":"AQAB"}}"#,
+                    kid
+                )
+            })
+            .collect();
+        format!(r#"{{"keys":[{}]}}"#, keys.join(","))
+    }
+
+    fn header(kid: &str) -> Header {
+        Header {
+            kid: Some(kid.to_string()),
+            ..Header::new(Algorithm::RS256)
+        }
+    }
+
+    /// Proveedor sobre un JWKS en un archivo temporal
+    fn provider(kids: &[&str], cooldown: Duration) -> (KeyProvider, std::path::PathBuf) {
+        let path = std::env::temp_dir().join(format!("jwks-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&path, jwks(kids)).unwrap();
+        let mut provider = KeyProvider::new(None, Some(JwksSource::File(path.display().to_string())));
+        provider.refresh_cooldown = cooldown;
+        (provider, path)
+    }
+
+    #[tokio::test]
+    async fn resuelve_un_kid_conocido() {
+        let (provider, path) = provider(&["a"], JWKS_REFRESH_COOLDOWN);
+        assert!(provider.decoding_key(&header("a")).await.is_ok());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn kid_desconocido_no_recarga_antes_del_intervalo() {
+        let (provider, path) = provider(&["a"], JWKS_REFRESH_COOLDOWN);
+        assert!(provider.decoding_key(&header("a")).await.is_ok());
+
+        // La clave nueva existe, pero el intento reciente impide otra descarga
+        std::fs::write(&path, jwks(&["a", "b"])).unwrap();
+        assert!(matches!(
+            provider.decoding_key(&header("b")).await,
+            Err(KeyError::UnknownKid(Some(kid))) if kid == "b"
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn rotacion_recarga_el_jwks() {
+        let (provider, path) = provider(&["a"], Duration::ZERO);
+        assert!(provider.decoding_key(&header("a")).await.is_ok());
+
+        std::fs::write(&path, jwks(&["b"])).unwrap();
+        assert!(provider.decoding_key(&header("b")).await.is_ok());
+        assert!(matches!(
+            provider.decoding_key(&header("a")).await,
+            Err(KeyError::UnknownKid(_))
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn recarga_fallida_conserva_las_claves() {
+        let (provider, path) = provider(&["a"], Duration::ZERO);
+        assert!(provider.decoding_key(&header("a")).await.is_ok());
+
+        std::fs::write(&path, "no es json").unwrap();
+        assert!(matches!(
+            provider.decoding_key(&header("b")).await,
+            Err(KeyError::UnknownKid(_))
+        ));
+        assert!(provider.decoding_key(&header("a")).await.is_ok());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn intento_fallido_cuenta_para_el_intervalo() {
+        let cache = JwksCache {
+            keys: None,
+            fetched_at: None,
+            attempted_at: Some(Instant::now()),
+        };
+        assert!(!cache.debe_recargar(true, JWKS_REFRESH_COOLDOWN));
+        assert!(!cache.debe_recargar(false, JWKS_REFRESH_COOLDOWN));
+        assert!(cache.debe_recargar(false, Duration::ZERO));
+    }
+
+    #[test]
+    fn jwks_vigente_solo_se_recarga_forzado() {
+        let cache = JwksCache {
+            keys: None,
+            fetched_at: Some(Instant::now()),
+            attempted_at: None,
+        };
+        assert!(!cache.debe_recargar(false, Duration::ZERO));
+        assert!(cache.debe_recargar(true, Duration::ZERO));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod dev_token;
+pub mod key_provider;
+
+pub use dev_token::{DevTokenIssuer, DEV_ENVIRONMENT, DEV_TOKEN_DEFAULT_TTL};
+pub use key_provider::KeyProvider;
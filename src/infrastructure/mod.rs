@@ -1,2 +1,4 @@
+pub mod auth;
 pub mod database;
 pub mod repositories;
+pub mod storage;
@@ -0,0 +1,107 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::ApiKey;
+use crate::domain::repositories::ApiKeyRepository;
+use crate::shared::AppResult;
+
+/// Implementación del repositorio de API keys con SQLx
+pub struct ApiKeyRepositoryImpl {
+    pool: PgPool,
+}
+
+impl ApiKeyRepositoryImpl {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiKeyRepository for ApiKeyRepositoryImpl {
+    async fn find_all(&self) -> AppResult<Vec<ApiKey>> {
+        let api_keys = sqlx::query_as::<_, ApiKey>(
+            r#"
+            SELECT id, nombre, prefijo, hash, scopes, expira_en, ultimo_uso,
+                   revocada_en, creada_por, created_at
+            FROM api_keys
+            ORDER BY created_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(api_keys)
+    }
+
+    async fn find_by_prefijo(&self, prefijo: &str) -> AppResult<Option<ApiKey>> {
+        let api_key = sqlx::query_as::<_, ApiKey>(
+            r#"
+            SELECT id, nombre, prefijo, hash, scopes, expira_en, ultimo_uso,
+                   revocada_en, creada_por, created_at
+            FROM api_keys
+            WHERE prefijo = $1
+            "#,
+        )
+        .bind(prefijo)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(api_key)
+    }
+
+    async fn create(&self, api_key: &ApiKey) -> AppResult<ApiKey> {
+        let created = sqlx::query_as::<_, ApiKey>(
+            r#"
+            INSERT INTO api_keys (id, nombre, prefijo, hash, scopes, expira_en, creada_por, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, nombre, prefijo, hash, scopes, expira_en, ultimo_uso,
+                      revocada_en, creada_por, created_at
+            "#,
+        )
+        .bind(api_key.id)
+        .bind(&api_key.nombre)
+        .bind(&api_key.prefijo)
+        .bind(&api_key.hash)
+        .bind(&api_key.scopes)
+        .bind(api_key.expira_en)
+        .bind(api_key.creada_por)
+        .bind(api_key.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    async fn revoke(&self, id: Uuid) -> AppResult<Option<ApiKey>> {
+        let revoked = sqlx::query_as::<_, ApiKey>(
+            r#"
+            UPDATE api_keys
+            SET revocada_en = COALESCE(revocada_en, NOW())
+            WHERE id = $1
+            RETURNING id, nombre, prefijo, hash, scopes, expira_en, ultimo_uso,
+                      revocada_en, creada_por, created_at
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(revoked)
+    }
+
+    async fn touch(&self, id: Uuid) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET ultimo_uso = NOW()
+            WHERE id = $1
+              AND (ultimo_uso IS NULL OR ultimo_uso < NOW() - INTERVAL '1 minute')
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
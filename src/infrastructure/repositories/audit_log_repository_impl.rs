@@ -0,0 +1,44 @@
+use sqlx::{PgExecutor, PgPool};
+
+use crate::domain::entities::AuditLog;
+use crate::domain::repositories::AuditLogRepository;
+use crate::shared::AppResult;
+
+/// Inserta un registro con cualquier executor (pool o transacción)
+pub(crate) async fn insert_audit<'e, E: PgExecutor<'e>>(executor: E, entry: &AuditLog) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (id, id_actor, id_usuario, accion, recurso, detalle, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(entry.id)
+    .bind(entry.id_actor)
+    .bind(entry.id_usuario)
+    .bind(&entry.accion)
+    .bind(&entry.recurso)
+    .bind(&entry.detalle)
+    .bind(entry.created_at)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Implementación del repositorio de auditoría con SQLx
+pub struct AuditLogRepositoryImpl {
+    pool: PgPool,
+}
+
+impl AuditLogRepositoryImpl {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditLogRepository for AuditLogRepositoryImpl {
+    async fn create(&self, entry: &AuditLog) -> AppResult<()> {
+        Ok(insert_audit(&self.pool, entry).await?)
+    }
+}
@@ -0,0 +1,207 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::Categoria;
+use crate::domain::repositories::{CategoriaCambios, CategoriaRepository, NuevaCategoria};
+use crate::shared::{AppError, AppResult};
+
+/// Implementación del repositorio de categorías con SQLx
+pub struct CategoriaRepositoryImpl {
+    pool: PgPool,
+}
+
+impl CategoriaRepositoryImpl {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl CategoriaRepository for CategoriaRepositoryImpl {
+    async fn find_all(&self) -> AppResult<Vec<Categoria>> {
+        let categorias = sqlx::query_as::<_, Categoria>(
+            r#"
+            SELECT id_categoria, nombre, slug, id_padre, orden, activo, created_at, updated_at
+            FROM categorias
+            ORDER BY orden ASC, nombre ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(categorias)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> AppResult<Option<Categoria>> {
+        let categoria = sqlx::query_as::<_, Categoria>(
+            r#"
+            SELECT id_categoria, nombre, slug, id_padre, orden, activo, created_at, updated_at
+            FROM categorias
+            WHERE id_categoria = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(categoria)
+    }
+
+    async fn find_by_slug(&self, slug: &str) -> AppResult<Option<Categoria>> {
+        let categoria = sqlx::query_as::<_, Categoria>(
+            r#"
+            SELECT id_categoria, nombre, slug, id_padre, orden, activo, created_at, updated_at
+            FROM categorias
+            WHERE slug = $1
+            "#,
+        )
+        .bind(slug)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(categoria)
+    }
+
+    async fn find_by_texto_legado(&self, texto: &str) -> AppResult<Option<Categoria>> {
+        let categoria = sqlx::query_as::<_, Categoria>(
+            r#"
+            SELECT c.id_categoria, c.nombre, c.slug, c.id_padre, c.orden, c.activo, c.created_at, c.updated_at
+            FROM categorias_legado l
+            JOIN categorias c ON c.id_categoria = l.id_categoria
+            WHERE l.texto = trim($1)
+            "#,
+        )
+        .bind(texto)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(categoria)
+    }
+
+    async fn count_productos(&self, solo_activos: bool) -> AppResult<Vec<(Uuid, i64)>> {
+        let counts = sqlx::query_as::<_, (Uuid, i64)>(
+            r#"
+            SELECT id_categoria, COUNT(*)
+            FROM productos
+            WHERE id_categoria IS NOT NULL AND (estado OR NOT $1)
+            GROUP BY id_categoria
+            "#,
+        )
+        .bind(solo_activos)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    async fn create(&self, nueva: &NuevaCategoria) -> AppResult<Categoria> {
+        let categoria = sqlx::query_as::<_, Categoria>(
+            r#"
+            INSERT INTO categorias (nombre, slug, id_padre, orden, activo)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id_categoria, nombre, slug, id_padre, orden, activo, created_at, updated_at
+            "#,
+        )
+        .bind(&nueva.nombre)
+        .bind(&nueva.slug)
+        .bind(nueva.id_padre)
+        .bind(nueva.orden)
+        .bind(nueva.activo)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(categoria)
+    }
+
+    async fn update(&self, id: Uuid, cambios: &CategoriaCambios) -> AppResult<Categoria> {
+        let mut tx = self.pool.begin().await?;
+
+        // Los movimientos dentro del árbol se serializan: dos cambios de padre
+        // simultáneos podrían formar un ciclo aunque cada uno por separado no lo haga
+        if let Some(Some(id_padre)) = cambios.id_padre {
+            sqlx::query("SELECT pg_advisory_xact_lock(hashtext('categorias_arbol'))")
+                .execute(&mut *tx)
+                .await?;
+
+            let ciclo: bool = sqlx::query_scalar(
+                r#"
+                WITH RECURSIVE subarbol AS (
+                    SELECT id_categoria FROM categorias WHERE id_categoria = $1
+                    UNION ALL
+                    SELECT c.id_categoria FROM categorias c JOIN subarbol s ON c.id_padre = s.id_categoria
+                )
+                SELECT EXISTS (SELECT 1 FROM subarbol WHERE id_categoria = $2)
+                "#,
+            )
+            .bind(id)
+            .bind(id_padre)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            if ciclo {
+                return Err(AppError::InvalidField {
+                    field: "id_padre",
+                    message: "La categoría padre no puede ser la misma categoría ni una de sus subcategorías"
+                        .to_string(),
+                });
+            }
+        }
+
+        let categoria = sqlx::query_as::<_, Categoria>(
+            r#"
+            UPDATE categorias
+            SET nombre = COALESCE($2, nombre),
+                slug = COALESCE($3, slug),
+                id_padre = CASE WHEN $4 THEN $5 ELSE id_padre END,
+                orden = COALESCE($6, orden),
+                activo = COALESCE($7, activo),
+                updated_at = NOW()
+            WHERE id_categoria = $1
+            RETURNING id_categoria, nombre, slug, id_padre, orden, activo, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(&cambios.nombre)
+        .bind(&cambios.slug)
+        .bind(cambios.id_padre.is_some())
+        .bind(cambios.id_padre.flatten())
+        .bind(cambios.orden)
+        .bind(cambios.activo)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Categoría {} no encontrada", id)))?;
+
+        if cambios.nombre.is_some() {
+            sqlx::query("UPDATE productos SET categoria = $2 WHERE id_categoria = $1")
+                .bind(id)
+                .bind(&categoria.nombre)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(categoria)
+    }
+
+    async fn delete(&self, id: Uuid) -> AppResult<()> {
+        // La verificación previa del servicio no cubre un producto o subcategoría
+        // asignados entre medias; las FK con RESTRICT lo rechazan aquí
+        let result = sqlx::query("DELETE FROM categorias WHERE id_categoria = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(db) if db.is_foreign_key_violation() => AppError::BadRequest(
+                    "La categoría tiene subcategorías o productos asignados; reasígnelos primero".to_string(),
+                ),
+                e => AppError::Database(e),
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Categoría {} no encontrada", id)));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use rust_decimal::Decimal;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::EstadoCredito;
+use crate::domain::repositories::CreditoRepository;
+use crate::shared::{AppError, AppResult};
+
+/// Situación de crédito por perfil (sin WHERE; cada consulta agrega su filtro)
+///
+/// Una factura vence al estar marcada `vencida` o al cumplirse `dias_credito`
+/// desde su emisión (sin plazo configurado, solo por su estado). Los montos de
+/// facturas, pagos y pedidos son DOUBLE PRECISION: las sumas se redondean a
+/// centavos como `limite_credito` (NUMERIC).
+const ESTADO_CREDITO_SELECT: &str = r#"
+    SELECT p.id_perfil, p.documento_identidad, p.limite_credito, p.dias_credito,
+           COALESCE(fac.saldo, 0)::numeric(14, 2) AS saldo_facturas,
+           COALESCE(fac.vencido, 0)::numeric(14, 2) AS saldo_vencido,
+           COALESCE(pen.monto, 0)::numeric(14, 2) AS pedidos_por_facturar
+    FROM perfiles_cliente p
+    LEFT JOIN LATERAL (
+        SELECT SUM(imp.saldo) AS saldo,
+               SUM(imp.saldo) FILTER (
+                   WHERE imp.estado = 'vencida'
+                      OR imp.fecha_emision + make_interval(days => p.dias_credito) < NOW()
+               ) AS vencido
+        FROM (
+            SELECT f.estado::text AS estado, f.fecha_emision,
+                   COALESCE(f.total, f.subtotal + f.impuestos) - COALESCE((
+                       SELECT SUM(pg.monto) FROM pagos pg
+                       WHERE pg.id_factura = f.id_factura AND pg.estado::text = 'completado'
+                   ), 0) AS saldo
+            FROM facturas f
+            JOIN pedidos pe ON pe.id_pedido = f.id_pedido
+            WHERE pe.id_perfil = p.id_perfil AND f.estado::text IN ('pendiente', 'vencida')
+        ) imp
+    ) fac ON true
+    LEFT JOIN LATERAL (
+        SELECT SUM(pe.monto_total) AS monto
+        FROM pedidos pe
+        WHERE pe.id_perfil = p.id_perfil AND pe.estado <> 'cancelado'
+          AND NOT EXISTS (SELECT 1 FROM facturas f WHERE f.id_pedido = pe.id_pedido)
+    ) pen ON true
+"#;
+
+/// Situación de crédito de un perfil con cualquier executor (pool o transacción)
+pub(crate) async fn estado_credito<'e, E: PgExecutor<'e>>(
+    executor: E,
+    id_perfil: Uuid,
+) -> Result<Option<EstadoCredito>, sqlx::Error> {
+    let sql = format!("{} WHERE p.id_perfil = $1", ESTADO_CREDITO_SELECT);
+    sqlx::query_as::<_, EstadoCredito>(&sql)
+        .bind(id_perfil)
+        .fetch_optional(executor)
+        .await
+}
+
+/// Implementación del repositorio de crédito con SQLx
+pub struct CreditoRepositoryImpl {
+    pool: PgPool,
+}
+
+impl CreditoRepositoryImpl {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl CreditoRepository for CreditoRepositoryImpl {
+    async fn find_estado(&self, id_perfil: Uuid) -> AppResult<Option<EstadoCredito>> {
+        Ok(estado_credito(&self.pool, id_perfil).await?)
+    }
+
+    async fn find_sobre_umbral(&self, umbral: f64) -> AppResult<Vec<EstadoCredito>> {
+        let sql = format!(
+            r#"
+            SELECT * FROM ({} WHERE p.limite_credito IS NOT NULL) e
+            WHERE e.saldo_facturas + e.pedidos_por_facturar > 0
+              AND e.saldo_facturas + e.pedidos_por_facturar >= $1::numeric * e.limite_credito
+            ORDER BY (e.saldo_facturas + e.pedidos_por_facturar) / NULLIF(e.limite_credito, 0) DESC NULLS FIRST
+            "#,
+            ESTADO_CREDITO_SELECT
+        );
+        let perfiles = sqlx::query_as::<_, EstadoCredito>(&sql)
+            .bind(umbral)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(perfiles)
+    }
+
+    async fn update_config(
+        &self,
+        id_perfil: Uuid,
+        limite_credito: Option<Decimal>,
+        dias_credito: Option<i32>,
+    ) -> AppResult<EstadoCredito> {
+        let mut tx = self.pool.begin().await?;
+
+        let updated = sqlx::query(
+            r#"
+            UPDATE perfiles_cliente
+            SET limite_credito = $2, dias_credito = $3, updated_at = NOW()
+            WHERE id_perfil = $1
+            "#,
+        )
+        .bind(id_perfil)
+        .bind(limite_credito)
+        .bind(dias_credito)
+        .execute(&mut *tx)
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(AppError::NotFound("Perfil no encontrado".to_string()));
+        }
+
+        let estado = estado_credito(&mut *tx, id_perfil)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Perfil no encontrado".to_string()))?;
+        tx.commit().await?;
+
+        Ok(estado)
+    }
+}
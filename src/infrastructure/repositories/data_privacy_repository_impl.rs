@@ -0,0 +1,175 @@
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::AuditLog;
+use crate::domain::repositories::{AnonymizationSummary, DataPrivacyRepository};
+use crate::infrastructure::repositories::audit_log_repository_impl::insert_audit;
+use crate::shared::{AppError, AppResult};
+
+/// Texto que reemplaza la calle de las direcciones anonimizadas
+const CALLE_ANONIMIZADA: &str = "[anonimizado]";
+
+/// Implementación del repositorio de privacidad con SQLx
+pub struct DataPrivacyRepositoryImpl {
+    pool: PgPool,
+}
+
+impl DataPrivacyRepositoryImpl {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl DataPrivacyRepository for DataPrivacyRepositoryImpl {
+    async fn anonymize_user(&self, id_usuario: Uuid, audit: &AuditLog) -> AppResult<AnonymizationSummary> {
+        let mut tx = self.pool.begin().await?;
+
+        let updated = sqlx::query(
+            r#"
+            UPDATE public.users
+            SET email = NULL, nombre = NULL, apellido = NULL, foto_perfil = NULL,
+                activo = false, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id_usuario)
+        .execute(&mut *tx)
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(AppError::NotFound("Usuario no encontrado".to_string()));
+        }
+
+        // Se bloquean primero sus perfiles; la consulta siguiente ya ve los miembros
+        // agregados por transacciones que terminaron mientras tanto
+        sqlx::query("SELECT id_perfil FROM perfiles_cliente WHERE id_usuario = $1 FOR UPDATE")
+            .bind(id_usuario)
+            .execute(&mut *tx)
+            .await?;
+
+        let perfiles: Vec<(Uuid, bool)> = sqlx::query_as(
+            r#"
+            SELECT p.id_perfil,
+                EXISTS (
+                    SELECT 1 FROM perfil_miembros m
+                    WHERE m.id_perfil = p.id_perfil AND m.id_usuario <> $1
+                ) AS compartido
+            FROM perfiles_cliente p
+            WHERE p.id_usuario = $1
+            "#,
+        )
+        .bind(id_usuario)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // El RUC y las direcciones de un perfil de empresa con otros miembros son de
+        // la empresa, no datos personales del titular
+        let (propios, compartidos) = perfiles_por_miembros(&perfiles);
+        if !compartidos.is_empty() {
+            return Err(AppError::BadRequest(format!(
+                "El usuario es titular de perfiles con otros miembros ({}); quite a los miembros antes de anonimizarlo",
+                compartidos.iter().map(Uuid::to_string).collect::<Vec<_>>().join(", ")
+            )));
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE perfiles_cliente
+            SET documento_identidad = NULL, tipo_documento = NULL,
+                telefono = NULL, tipo_telefono = NULL, updated_at = NOW()
+            WHERE id_perfil = ANY($1)
+            "#,
+        )
+        .bind(&propios)
+        .execute(&mut *tx)
+        .await?;
+
+        // Datos de sus perfiles absorbidos en fusiones de duplicados
+        sqlx::query(
+            "UPDATE perfil_fusiones SET documento_origen = NULL, telefono_origen = NULL WHERE id_usuario_origen = $1",
+        )
+        .bind(id_usuario)
+        .execute(&mut *tx)
+        .await?;
+
+        // Deja de ser miembro de perfiles de empresa ajenos
+        sqlx::query("DELETE FROM perfil_miembros WHERE id_usuario = $1")
+            .bind(id_usuario)
+            .execute(&mut *tx)
+            .await?;
+
+        // Las filas se conservan porque los pedidos las referencian (origen/destino);
+        // se quita la calle y las referencias y las coordenadas quedan a ~10 km
+        let direcciones = sqlx::query(
+            r#"
+            UPDATE direcciones
+            SET calle = $2, referencias_adicionales = NULL,
+                latitud = ROUND(latitud, 1), longitud = ROUND(longitud, 1),
+                es_predeterminada = false, activo = false, updated_at = NOW()
+            WHERE id_perfil = ANY($1)
+            "#,
+        )
+        .bind(&propios)
+        .bind(CALLE_ANONIMIZADA)
+        .execute(&mut *tx)
+        .await?;
+
+        let summary = AnonymizationSummary {
+            perfiles: propios.len() as u64,
+            direcciones: direcciones.rows_affected(),
+        };
+
+        let entry = audit.clone().detalle(json!({
+            "perfiles": summary.perfiles,
+            "direcciones": summary.direcciones,
+        }));
+        insert_audit(&mut *tx, &entry).await?;
+
+        tx.commit().await?;
+
+        Ok(summary)
+    }
+}
+
+/// Separa los perfiles del usuario en propios y compartidos (con otros miembros)
+///
+/// Solo los propios se anonimizan; los compartidos son datos de la empresa.
+fn perfiles_por_miembros(perfiles: &[(Uuid, bool)]) -> (Vec<Uuid>, Vec<Uuid>) {
+    let (compartidos, propios): (Vec<_>, Vec<_>) = perfiles.iter().partition(|(_, compartido)| *compartido);
+    let ids = |perfiles: Vec<&(Uuid, bool)>| perfiles.into_iter().map(|(id, _)| *id).collect();
+    (ids(propios), ids(compartidos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separa_los_perfiles_compartidos() {
+        let propio = Uuid::new_v4();
+        let empresa = Uuid::new_v4();
+
+        let (propios, compartidos) = perfiles_por_miembros(&[(propio, false), (empresa, true)]);
+
+        assert_eq!(propios, vec![propio]);
+        assert_eq!(compartidos, vec![empresa]);
+    }
+
+    #[test]
+    fn sin_miembros_todos_son_propios() {
+        let perfiles = [(Uuid::new_v4(), false), (Uuid::new_v4(), false)];
+
+        let (propios, compartidos) = perfiles_por_miembros(&perfiles);
+
+        assert_eq!(propios.len(), 2);
+        assert!(compartidos.is_empty());
+    }
+
+    #[test]
+    fn sin_perfiles_no_hay_nada_que_anonimizar() {
+        let (propios, compartidos) = perfiles_por_miembros(&[]);
+        assert!(propios.is_empty() && compartidos.is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::domain::entities::Direccion;
+use crate::domain::entities::{AlmacenCercano, Direccion};
 use crate::domain::repositories::DireccionRepository;
 use crate::shared::error::{AppError, AppResult};
 use rust_decimal::Decimal;
@@ -105,6 +105,44 @@ impl DireccionRepository for DireccionRepositoryImpl {
         Ok(direcciones)
     }
 
+    async fn find_almacen_cercano(&self, latitud: f64, longitud: f64) -> AppResult<Option<AlmacenCercano>> {
+        // Distancia por fórmula de haversine (radio terrestre medio: 6371 km)
+        let almacen = sqlx::query_as::<_, AlmacenCercano>(
+            r#"
+            SELECT id_direccion AS id_almacen, calle, ciudad,
+                   6371.0 * 2 * asin(sqrt(
+                       power(sin(radians(latitud::float8 - $1) / 2), 2) +
+                       cos(radians($1)) * cos(radians(latitud::float8)) *
+                       power(sin(radians(longitud::float8 - $2) / 2), 2)
+                   )) AS distancia_km
+            FROM direcciones
+            WHERE tipo = 'almacen' AND activo = true
+            ORDER BY distancia_km ASC, id_direccion ASC
+            LIMIT 1
+            "#,
+        )
+        .bind(latitud)
+        .bind(longitud)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(almacen)
+    }
+
+    async fn tiene_inventario(&self, id: Uuid) -> AppResult<bool> {
+        let tiene: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (SELECT 1 FROM inventario_almacen WHERE id_almacen = $1)
+                OR EXISTS (SELECT 1 FROM movimientos_stock WHERE id_almacen = $1)
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(tiene)
+    }
+
     async fn find_predeterminada(&self, id_perfil: Uuid) -> AppResult<Option<Direccion>> {
         let direccion = sqlx::query_as::<_, Direccion>(
             r#"
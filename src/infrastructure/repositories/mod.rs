@@ -3,9 +3,27 @@ pub mod producto_repository_impl;
 pub mod pedido_repository_impl;
 pub mod perfil_cliente_repository_impl;
 pub mod direccion_repository_impl;
+pub mod transportista_repository_impl;
+pub mod session_revocation_repository_impl;
+pub mod api_key_repository_impl;
+pub mod audit_log_repository_impl;
+pub mod data_privacy_repository_impl;
+pub mod perfil_miembro_repository_impl;
+pub mod credito_repository_impl;
+pub mod perfil_fusion_repository_impl;
+pub mod categoria_repository_impl;
 
 pub use pedido_repository_impl::PedidoRepositoryImpl;
 pub use perfil_cliente_repository_impl::PerfilClienteRepositoryImpl;
 pub use producto_repository_impl::ProductoRepositoryImpl;
 pub use direccion_repository_impl::DireccionRepositoryImpl;
-pub use user_repository_impl::UserRepositoryImpl;
\ No newline at end of file
+pub use user_repository_impl::UserRepositoryImpl;
+pub use transportista_repository_impl::TransportistaRepositoryImpl;
+pub use session_revocation_repository_impl::SessionRevocationRepositoryImpl;
+pub use api_key_repository_impl::ApiKeyRepositoryImpl;
+pub use audit_log_repository_impl::AuditLogRepositoryImpl;
+pub use data_privacy_repository_impl::DataPrivacyRepositoryImpl;
+pub use perfil_miembro_repository_impl::PerfilMiembroRepositoryImpl;
+pub use credito_repository_impl::CreditoRepositoryImpl;
+pub use perfil_fusion_repository_impl::PerfilFusionRepositoryImpl;
+pub use categoria_repository_impl::CategoriaRepositoryImpl;
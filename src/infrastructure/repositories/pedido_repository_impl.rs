@@ -2,8 +2,10 @@ use sqlx::PgPool;
 use uuid::Uuid;
 use async_trait::async_trait;
 
-use crate::domain::entities::Pedido;
+use crate::domain::entities::{monto_en_centavos, AuditLog, Pedido};
 use crate::domain::repositories::PedidoRepository;
+use crate::infrastructure::repositories::audit_log_repository_impl::insert_audit;
+use crate::infrastructure::repositories::credito_repository_impl::estado_credito;
 use crate::shared::{AppError, AppResult};
 
 /// Implementación concreta del repositorio de pedidos usando SQLx
@@ -57,6 +59,22 @@ impl PedidoRepository for PedidoRepositoryImpl {
         Ok(pedidos)
     }
 
+    async fn count_by_estado(&self, id_perfil: Uuid) -> AppResult<Vec<(String, i64)>> {
+        let counts = sqlx::query_as::<_, (String, i64)>(
+            r#"
+            SELECT estado, COUNT(*)
+            FROM pedidos
+            WHERE id_perfil = $1
+            GROUP BY estado
+            "#
+        )
+        .bind(id_perfil)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(counts)
+    }
+
     async fn find_by_transportista(&self, id_transportista: Uuid) -> AppResult<Vec<Pedido>> {
         let pedidos = sqlx::query_as::<_, Pedido>(
             r#"
@@ -76,28 +94,56 @@ impl PedidoRepository for PedidoRepositoryImpl {
         Ok(pedidos)
     }
 
-    async fn create(&self, pedido: &Pedido) -> AppResult<Pedido> {
+    async fn create(&self, pedido: &Pedido, omision: Option<&AuditLog>) -> AppResult<Pedido> {
+        let mut tx = self.pool.begin().await?;
+
+        // Serializa la creación de pedidos del perfil hasta el commit
+        sqlx::query("SELECT 1 FROM perfiles_cliente WHERE id_perfil = $1 FOR UPDATE")
+            .bind(pedido.id_perfil)
+            .execute(&mut *tx)
+            .await?;
+
+        if omision.is_none()
+            && let Some(estado) = estado_credito(&mut *tx, pedido.id_perfil).await?
+            && estado.excede_con(
+                monto_en_centavos(pedido.monto_total)
+                    .ok_or_else(|| AppError::BadRequest("Monto del pedido inválido".to_string()))?,
+            )
+        {
+            return Err(AppError::CreditLimitExceeded {
+                limite: estado.limite_credito.unwrap_or_default(),
+                disponible: estado.disponible().unwrap_or_default(),
+            });
+        }
+
         let created = sqlx::query_as::<_, Pedido>(
             r#"
             INSERT INTO pedidos (
-                id_perfil, id_direccion_origen, id_direccion_destino,
+                id_pedido, id_perfil, id_direccion_origen, id_direccion_destino,
                 estado, monto_total
             )
-            VALUES ($1, $2, $3, $4, $5)
+            VALUES ($1, $2, $3, $4, $5, $6)
             RETURNING id_pedido, numero_tracking, id_perfil, id_transportista,
                       id_direccion_origen, id_direccion_destino, estado,
                       fecha_entrega_estimada, fecha_entrega_real, monto_total,
                       created_at, updated_at
             "#
         )
+        .bind(pedido.id_pedido)
         .bind(&pedido.id_perfil)
         .bind(&pedido.id_direccion_origen)
         .bind(&pedido.id_direccion_destino)
         .bind(&pedido.estado)
         .bind(&pedido.monto_total)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await?;
 
+        if let Some(entry) = omision {
+            insert_audit(&mut *tx, entry).await?;
+        }
+
+        tx.commit().await?;
+
         Ok(created)
     }
 
@@ -1,5 +1,6 @@
 use crate::domain::entities::PerfilCliente;
 use crate::domain::repositories::PerfilClienteRepository;
+use crate::domain::validation::{Telefono, TipoDocumento};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -19,7 +20,8 @@ impl PerfilClienteRepository for PerfilClienteRepositoryImpl {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<PerfilCliente>, sqlx::Error> {
         sqlx::query_as::<_, PerfilCliente>(
             r#"
-            SELECT id_perfil, id_usuario, documento_identidad, telefono, created_at, updated_at
+            SELECT id_perfil, id_usuario, documento_identidad, tipo_documento, telefono, tipo_telefono,
+                   limite_credito, dias_credito, created_at, updated_at
             FROM perfiles_cliente
             WHERE id_perfil = $1
             "#,
@@ -32,7 +34,8 @@ impl PerfilClienteRepository for PerfilClienteRepositoryImpl {
     async fn find_by_usuario(&self, id_usuario: Uuid) -> Result<Option<PerfilCliente>, sqlx::Error> {
         sqlx::query_as::<_, PerfilCliente>(
             r#"
-            SELECT id_perfil, id_usuario, documento_identidad, telefono, created_at, updated_at
+            SELECT id_perfil, id_usuario, documento_identidad, tipo_documento, telefono, tipo_telefono,
+                   limite_credito, dias_credito, created_at, updated_at
             FROM perfiles_cliente
             WHERE id_usuario = $1
             "#,
@@ -45,7 +48,8 @@ impl PerfilClienteRepository for PerfilClienteRepositoryImpl {
     async fn find_all(&self) -> Result<Vec<PerfilCliente>, sqlx::Error> {
         sqlx::query_as::<_, PerfilCliente>(
             r#"
-            SELECT id_perfil, id_usuario, documento_identidad, telefono, created_at, updated_at
+            SELECT id_perfil, id_usuario, documento_identidad, tipo_documento, telefono, tipo_telefono,
+                   limite_credito, dias_credito, created_at, updated_at
             FROM perfiles_cliente
             ORDER BY created_at DESC
             "#,
@@ -57,19 +61,22 @@ impl PerfilClienteRepository for PerfilClienteRepositoryImpl {
     async fn create(
         &self,
         id_usuario: Uuid,
-        documento: Option<&str>,
-        telefono: Option<&str>,
+        documento: Option<(&str, TipoDocumento)>,
+        telefono: Option<&Telefono>,
     ) -> Result<PerfilCliente, sqlx::Error> {
         sqlx::query_as::<_, PerfilCliente>(
             r#"
-            INSERT INTO perfiles_cliente (id_usuario, documento_identidad, telefono)
-            VALUES ($1, $2, $3)
-            RETURNING id_perfil, id_usuario, documento_identidad, telefono, created_at, updated_at
+            INSERT INTO perfiles_cliente (id_usuario, documento_identidad, tipo_documento, telefono, tipo_telefono)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id_perfil, id_usuario, documento_identidad, tipo_documento, telefono, tipo_telefono,
+                      limite_credito, dias_credito, created_at, updated_at
             "#,
         )
         .bind(id_usuario)
-        .bind(documento)
-        .bind(telefono)
+        .bind(documento.map(|(doc, _)| doc))
+        .bind(documento.map(|(_, tipo)| tipo.as_str()))
+        .bind(telefono.map(|tel| tel.e164.as_str()))
+        .bind(telefono.map(|tel| tel.tipo.as_str()))
         .fetch_one(&self.pool)
         .await
     }
@@ -77,22 +84,27 @@ impl PerfilClienteRepository for PerfilClienteRepositoryImpl {
     async fn update(
         &self,
         id: Uuid,
-        documento: Option<&str>,
-        telefono: Option<&str>,
+        documento: Option<(&str, TipoDocumento)>,
+        telefono: Option<&Telefono>,
     ) -> Result<PerfilCliente, sqlx::Error> {
         sqlx::query_as::<_, PerfilCliente>(
             r#"
             UPDATE perfiles_cliente
             SET documento_identidad = COALESCE($2, documento_identidad),
-                telefono = COALESCE($3, telefono),
+                tipo_documento = COALESCE($3, tipo_documento),
+                telefono = COALESCE($4, telefono),
+                tipo_telefono = COALESCE($5, tipo_telefono),
                 updated_at = NOW()
             WHERE id_perfil = $1
-            RETURNING id_perfil, id_usuario, documento_identidad, telefono, created_at, updated_at
+            RETURNING id_perfil, id_usuario, documento_identidad, tipo_documento, telefono, tipo_telefono,
+                      limite_credito, dias_credito, created_at, updated_at
             "#,
         )
         .bind(id)
-        .bind(documento)
-        .bind(telefono)
+        .bind(documento.map(|(doc, _)| doc))
+        .bind(documento.map(|(_, tipo)| tipo.as_str()))
+        .bind(telefono.map(|tel| tel.e164.as_str()))
+        .bind(telefono.map(|tel| tel.tipo.as_str()))
         .fetch_one(&self.pool)
         .await
     }
@@ -119,7 +131,8 @@ impl PerfilClienteRepository for PerfilClienteRepositoryImpl {
     async fn find_by_documento(&self, documento: &str) -> Result<Option<PerfilCliente>, sqlx::Error> {
         sqlx::query_as::<_, PerfilCliente>(
             r#"
-            SELECT id_perfil, id_usuario, documento_identidad, telefono, created_at, updated_at
+            SELECT id_perfil, id_usuario, documento_identidad, tipo_documento, telefono, tipo_telefono,
+                   limite_credito, dias_credito, created_at, updated_at
             FROM perfiles_cliente
             WHERE documento_identidad = $1
             "#,
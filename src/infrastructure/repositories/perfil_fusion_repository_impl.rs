@@ -0,0 +1,299 @@
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::{terminos_credito_fusion, AuditLog, TerminosCredito, PerfilCliente, PerfilDuplicado, PerfilFusion};
+use crate::domain::repositories::PerfilFusionRepository;
+use crate::infrastructure::repositories::audit_log_repository_impl::insert_audit;
+use crate::shared::{AppError, AppResult};
+
+/// Implementación del repositorio de fusión de perfiles con SQLx
+pub struct PerfilFusionRepositoryImpl {
+    pool: PgPool,
+}
+
+impl PerfilFusionRepositoryImpl {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Datos del perfil origen que se conservan en el registro de la fusión
+#[derive(sqlx::FromRow)]
+struct PerfilOrigen {
+    id_usuario: Uuid,
+    documento_identidad: Option<String>,
+    tipo_documento: Option<String>,
+    telefono: Option<String>,
+    tipo_telefono: Option<String>,
+    limite_credito: Option<Decimal>,
+    dias_credito: Option<i32>,
+}
+
+#[async_trait::async_trait]
+impl PerfilFusionRepository for PerfilFusionRepositoryImpl {
+    async fn find_duplicados(&self) -> AppResult<Vec<PerfilDuplicado>> {
+        // Claves sobre los valores ya normalizados al guardar el perfil:
+        // - documento: el RUC de persona natural se reduce a su cédula
+        // - teléfono: E.164
+        // - email: minúsculas, sin espacios ni sufijo `+etiqueta`
+        let duplicados = sqlx::query_as::<_, PerfilDuplicado>(
+            r#"
+            WITH claves AS (
+                SELECT p.id_perfil, 'documento' AS criterio,
+                       CASE WHEN p.tipo_documento = 'ruc_persona_natural'
+                            THEN left(p.documento_identidad, 10) ELSE p.documento_identidad END AS clave
+                FROM perfiles_cliente p
+                WHERE p.documento_identidad IS NOT NULL
+                UNION ALL
+                SELECT p.id_perfil, 'telefono', p.telefono
+                FROM perfiles_cliente p
+                WHERE p.telefono IS NOT NULL
+                UNION ALL
+                SELECT p.id_perfil, 'email', regexp_replace(lower(trim(u.email)), '\+[^@]*@', '@')
+                FROM perfiles_cliente p
+                JOIN users u ON u.id = p.id_usuario
+                WHERE u.email IS NOT NULL
+            ),
+            grupos AS (
+                SELECT criterio, clave, array_agg(id_perfil) AS perfiles
+                FROM claves
+                WHERE clave <> ''
+                GROUP BY criterio, clave
+                HAVING COUNT(*) > 1
+            )
+            SELECT g.criterio, g.clave, p.id_perfil, p.id_usuario, u.email,
+                   p.documento_identidad, p.telefono,
+                   (SELECT COUNT(*) FROM direcciones d WHERE d.id_perfil = p.id_perfil) AS direcciones,
+                   (SELECT COUNT(*) FROM pedidos pe WHERE pe.id_perfil = p.id_perfil) AS pedidos,
+                   p.created_at
+            FROM grupos g
+            JOIN perfiles_cliente p ON p.id_perfil = ANY(g.perfiles)
+            LEFT JOIN users u ON u.id = p.id_usuario
+            ORDER BY g.criterio, g.clave, p.created_at
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(duplicados)
+    }
+
+    async fn fusionar(
+        &self,
+        id_destino: Uuid,
+        id_origen: Uuid,
+        rol_miembro_origen: Option<&str>,
+        audit: &AuditLog,
+    ) -> AppResult<(PerfilCliente, PerfilFusion)> {
+        let mut tx = self.pool.begin().await?;
+
+        // Bloquea ambos perfiles (en orden fijo para evitar deadlocks entre fusiones)
+        let bloqueados: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id_perfil FROM perfiles_cliente
+            WHERE id_perfil = ANY($1)
+            ORDER BY id_perfil
+            FOR UPDATE
+            "#,
+        )
+        .bind([id_destino, id_origen])
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for id in [id_destino, id_origen] {
+            if !bloqueados.contains(&id) {
+                return Err(AppError::NotFound(format!("Perfil {} no encontrado", id)));
+            }
+        }
+
+        let origen = sqlx::query_as::<_, PerfilOrigen>(
+            r#"
+            SELECT id_usuario, documento_identidad, tipo_documento, telefono, tipo_telefono,
+                   limite_credito, dias_credito
+            FROM perfiles_cliente
+            WHERE id_perfil = $1
+            "#,
+        )
+        .bind(id_origen)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let destino: TerminosCredito =
+            sqlx::query_as("SELECT limite_credito, dias_credito FROM perfiles_cliente WHERE id_perfil = $1")
+                .bind(id_destino)
+                .fetch_one(&mut *tx)
+                .await?;
+        let (limite_credito, dias_credito) =
+            terminos_credito_fusion(destino, (origen.limite_credito, origen.dias_credito)).ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "Los perfiles tienen términos de crédito distintos (destino: {}, origen: {}); iguálelos antes de fusionar",
+                    describir_credito(destino),
+                    describir_credito((origen.limite_credito, origen.dias_credito)),
+                ))
+            })?;
+
+        // La predeterminada del destino se conserva si ya tiene una
+        let direcciones = sqlx::query(
+            r#"
+            UPDATE direcciones
+            SET id_perfil = $1,
+                es_predeterminada = es_predeterminada AND NOT EXISTS (
+                    SELECT 1 FROM direcciones d WHERE d.id_perfil = $1 AND d.es_predeterminada
+                ),
+                updated_at = NOW()
+            WHERE id_perfil = $2
+            "#,
+        )
+        .bind(id_destino)
+        .bind(id_origen)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let pedidos = sqlx::query(
+            "UPDATE pedidos SET id_perfil = $1, updated_at = NOW() WHERE id_perfil = $2",
+        )
+        .bind(id_destino)
+        .bind(id_origen)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        // Miembros del origen (salvo el titular del destino y quienes ya son miembros)
+        let mut miembros = sqlx::query(
+            r#"
+            INSERT INTO perfil_miembros (id_perfil, id_usuario, rol, invitado_por, created_at, updated_at)
+            SELECT $1, m.id_usuario, m.rol, m.invitado_por, m.created_at, NOW()
+            FROM perfil_miembros m
+            WHERE m.id_perfil = $2
+              AND m.id_usuario <> (SELECT id_usuario FROM perfiles_cliente WHERE id_perfil = $1)
+            ON CONFLICT (id_perfil, id_usuario) DO NOTHING
+            "#,
+        )
+        .bind(id_destino)
+        .bind(id_origen)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if let Some(rol) = rol_miembro_origen {
+            miembros += sqlx::query(
+                r#"
+                INSERT INTO perfil_miembros (id_perfil, id_usuario, rol, invitado_por)
+                SELECT $1, $2, $3, $4
+                WHERE $2 <> (SELECT id_usuario FROM perfiles_cliente WHERE id_perfil = $1)
+                ON CONFLICT (id_perfil, id_usuario) DO UPDATE SET rol = EXCLUDED.rol, updated_at = NOW()
+                "#,
+            )
+            .bind(id_destino)
+            .bind(origen.id_usuario)
+            .bind(rol)
+            .bind(audit.id_actor)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+
+        // Las fusiones anteriores hacia el origen pasan a apuntar al destino
+        sqlx::query("UPDATE perfil_fusiones SET id_perfil_destino = $1 WHERE id_perfil_destino = $2")
+            .bind(id_destino)
+            .bind(id_origen)
+            .execute(&mut *tx)
+            .await?;
+
+        // Sus miembros restantes se borran en cascada
+        sqlx::query("DELETE FROM perfiles_cliente WHERE id_perfil = $1")
+            .bind(id_origen)
+            .execute(&mut *tx)
+            .await?;
+
+        // Documento y teléfono del origen completan los que falten en el destino;
+        // el crédito del origen pasa al destino si este no tenía
+        let perfil = sqlx::query_as::<_, PerfilCliente>(
+            r#"
+            UPDATE perfiles_cliente
+            SET tipo_documento = CASE WHEN documento_identidad IS NULL THEN $2 ELSE tipo_documento END,
+                documento_identidad = COALESCE(documento_identidad, $3),
+                tipo_telefono = CASE WHEN telefono IS NULL THEN $4 ELSE tipo_telefono END,
+                telefono = COALESCE(telefono, $5),
+                limite_credito = $6,
+                dias_credito = $7,
+                updated_at = NOW()
+            WHERE id_perfil = $1
+            RETURNING id_perfil, id_usuario, documento_identidad, tipo_documento, telefono, tipo_telefono,
+                      limite_credito, dias_credito, created_at, updated_at
+            "#,
+        )
+        .bind(id_destino)
+        .bind(&origen.tipo_documento)
+        .bind(&origen.documento_identidad)
+        .bind(&origen.tipo_telefono)
+        .bind(&origen.telefono)
+        .bind(limite_credito)
+        .bind(dias_credito)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let fusion = sqlx::query_as::<_, PerfilFusion>(
+            r#"
+            INSERT INTO perfil_fusiones (
+                id_perfil_origen, id_perfil_destino, id_usuario_origen, documento_origen, telefono_origen,
+                direcciones_movidas, pedidos_movidos, miembros_movidos, fusionado_por, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING id_perfil_origen, id_perfil_destino, id_usuario_origen, documento_origen, telefono_origen,
+                      direcciones_movidas, pedidos_movidos, miembros_movidos, fusionado_por, created_at
+            "#,
+        )
+        .bind(id_origen)
+        .bind(id_destino)
+        .bind(origen.id_usuario)
+        .bind(&origen.documento_identidad)
+        .bind(&origen.telefono)
+        .bind(direcciones as i32)
+        .bind(pedidos as i32)
+        .bind(miembros as i32)
+        .bind(audit.id_actor)
+        .bind(audit.created_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let entry = audit.clone().detalle(json!({
+            "id_perfil_origen": id_origen,
+            "id_perfil_destino": id_destino,
+            "direcciones": direcciones,
+            "pedidos": pedidos,
+            "miembros": miembros,
+        }));
+        insert_audit(&mut *tx, &entry).await?;
+
+        tx.commit().await?;
+
+        Ok((perfil, fusion))
+    }
+
+    async fn find_fusiones(&self) -> AppResult<Vec<PerfilFusion>> {
+        let fusiones = sqlx::query_as::<_, PerfilFusion>(
+            r#"
+            SELECT id_perfil_origen, id_perfil_destino, id_usuario_origen, documento_origen, telefono_origen,
+                   direcciones_movidas, pedidos_movidos, miembros_movidos, fusionado_por, created_at
+            FROM perfil_fusiones
+            ORDER BY created_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(fusiones)
+    }
+}
+
+fn describir_credito((limite, dias): TerminosCredito) -> String {
+    match (limite, dias) {
+        (None, _) => "sin límite".to_string(),
+        (Some(limite), None) => format!("{:.2}", limite),
+        (Some(limite), Some(dias)) => format!("{:.2} a {} días", limite, dias),
+    }
+}
@@ -0,0 +1,136 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::PerfilMiembro;
+use crate::domain::repositories::PerfilMiembroRepository;
+use crate::shared::{AppError, AppResult};
+
+/// Implementación del repositorio de miembros de perfiles con SQLx
+pub struct PerfilMiembroRepositoryImpl {
+    pool: PgPool,
+}
+
+impl PerfilMiembroRepositoryImpl {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl PerfilMiembroRepository for PerfilMiembroRepositoryImpl {
+    async fn find_by_perfil(&self, id_perfil: Uuid) -> AppResult<Vec<PerfilMiembro>> {
+        let miembros = sqlx::query_as::<_, PerfilMiembro>(
+            r#"
+            SELECT m.id_perfil, m.id_usuario, m.rol, m.invitado_por,
+                   u.email, u.nombre, u.apellido, m.created_at, m.updated_at
+            FROM perfil_miembros m
+            JOIN users u ON u.id = m.id_usuario
+            WHERE m.id_perfil = $1
+            ORDER BY m.created_at
+            "#,
+        )
+        .bind(id_perfil)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(miembros)
+    }
+
+    async fn find_by_usuario(&self, id_usuario: Uuid) -> AppResult<Vec<PerfilMiembro>> {
+        let miembros = sqlx::query_as::<_, PerfilMiembro>(
+            r#"
+            SELECT m.id_perfil, m.id_usuario, m.rol, m.invitado_por,
+                   u.email, u.nombre, u.apellido, m.created_at, m.updated_at
+            FROM perfil_miembros m
+            JOIN users u ON u.id = m.id_usuario
+            WHERE m.id_usuario = $1
+            ORDER BY m.created_at
+            "#,
+        )
+        .bind(id_usuario)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(miembros)
+    }
+
+    async fn find(&self, id_perfil: Uuid, id_usuario: Uuid) -> AppResult<Option<PerfilMiembro>> {
+        let miembro = sqlx::query_as::<_, PerfilMiembro>(
+            r#"
+            SELECT m.id_perfil, m.id_usuario, m.rol, m.invitado_por,
+                   u.email, u.nombre, u.apellido, m.created_at, m.updated_at
+            FROM perfil_miembros m
+            JOIN users u ON u.id = m.id_usuario
+            WHERE m.id_perfil = $1 AND m.id_usuario = $2
+            "#,
+        )
+        .bind(id_perfil)
+        .bind(id_usuario)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(miembro)
+    }
+
+    async fn create(&self, id_perfil: Uuid, id_usuario: Uuid, rol: &str, invitado_por: Uuid) -> AppResult<PerfilMiembro> {
+        let miembro = sqlx::query_as::<_, PerfilMiembro>(
+            r#"
+            WITH m AS (
+                INSERT INTO perfil_miembros (id_perfil, id_usuario, rol, invitado_por)
+                VALUES ($1, $2, $3, $4)
+                RETURNING *
+            )
+            SELECT m.id_perfil, m.id_usuario, m.rol, m.invitado_por,
+                   u.email, u.nombre, u.apellido, m.created_at, m.updated_at
+            FROM m
+            JOIN users u ON u.id = m.id_usuario
+            "#,
+        )
+        .bind(id_perfil)
+        .bind(id_usuario)
+        .bind(rol)
+        .bind(invitado_por)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(miembro)
+    }
+
+    async fn update_rol(&self, id_perfil: Uuid, id_usuario: Uuid, rol: &str) -> AppResult<PerfilMiembro> {
+        let miembro = sqlx::query_as::<_, PerfilMiembro>(
+            r#"
+            WITH m AS (
+                UPDATE perfil_miembros
+                SET rol = $3, updated_at = NOW()
+                WHERE id_perfil = $1 AND id_usuario = $2
+                RETURNING *
+            )
+            SELECT m.id_perfil, m.id_usuario, m.rol, m.invitado_por,
+                   u.email, u.nombre, u.apellido, m.created_at, m.updated_at
+            FROM m
+            JOIN users u ON u.id = m.id_usuario
+            "#,
+        )
+        .bind(id_perfil)
+        .bind(id_usuario)
+        .bind(rol)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        miembro.ok_or_else(|| AppError::NotFound("Miembro no encontrado".to_string()))
+    }
+
+    async fn delete(&self, id_perfil: Uuid, id_usuario: Uuid) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM perfil_miembros WHERE id_perfil = $1 AND id_usuario = $2")
+            .bind(id_perfil)
+            .bind(id_usuario)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Miembro no encontrado".to_string()));
+        }
+
+        Ok(())
+    }
+}
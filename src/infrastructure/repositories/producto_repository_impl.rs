@@ -1,10 +1,19 @@
-use crate::domain::entities::Producto;
-use crate::domain::repositories::ProductoRepository;
+use crate::domain::entities::{
+    CategoriaConteo, DescuadreStock, InventarioAlmacen, MovimientoStock, Producto, ProductoBusqueda, FIN_RESALTADO,
+    INICIO_RESALTADO,
+};
+use crate::domain::repositories::{
+    MovimientosStockQuery, NuevaTransferenciaStock, NuevoMovimientoStock, ProductoFilter, ProductoPageQuery,
+    ProductoRepository, ProductoSortField,
+};
 use crate::shared::error::{AppError, AppResult};
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
+/// Similitud mínima (pg_trgm.word_similarity_threshold) de la búsqueda tolerante
+const UMBRAL_SIMILITUD: &str = "0.5";
+
 /// Implementación del repositorio de productos con SQLx
 pub struct ProductoRepositoryImpl {
     pool: PgPool,
@@ -22,7 +31,7 @@ impl ProductoRepository for ProductoRepositoryImpl {
         let producto = sqlx::query_as::<_, Producto>(
             r#"
             SELECT id_producto, nombre_producto, descripcion, precio, stock,
-                   categoria, sku, estado, created_at, updated_at
+                   categoria, id_categoria, sku, estado, created_at, updated_at
             FROM productos
             WHERE id_producto = $1
             "#,
@@ -38,7 +47,7 @@ impl ProductoRepository for ProductoRepositoryImpl {
         let producto = sqlx::query_as::<_, Producto>(
             r#"
             SELECT id_producto, nombre_producto, descripcion, precio, stock,
-                   categoria, sku, estado, created_at, updated_at
+                   categoria, id_categoria, sku, estado, created_at, updated_at
             FROM productos
             WHERE sku = $1
             "#,
@@ -50,27 +59,74 @@ impl ProductoRepository for ProductoRepositoryImpl {
         Ok(producto)
     }
 
-    async fn find_activos(&self) -> AppResult<Vec<Producto>> {
-        let productos = sqlx::query_as::<_, Producto>(
-            r#"
-            SELECT id_producto, nombre_producto, descripcion, precio, stock,
-                   categoria, sku, estado, created_at, updated_at
-            FROM productos
-            WHERE estado = true
-            ORDER BY nombre_producto ASC
-            "#,
-        )
-        .fetch_all(&self.pool)
-        .await?;
+    async fn find_page(&self, query: &ProductoPageQuery) -> AppResult<Vec<Producto>> {
+        let (sort_expr, cast) = sort_column(query.sort);
+        let (cmp, direction) = if query.descending { ("<", "DESC") } else { (">", "ASC") };
+
+        let mut builder = QueryBuilder::<Postgres>::new(
+            "SELECT p.id_producto, p.nombre_producto, p.descripcion, p.precio, p.stock,
+                    p.categoria, p.id_categoria, p.sku, p.estado, p.created_at, p.updated_at
+             FROM productos p
+             WHERE p.estado = true",
+        );
+        push_filters(&mut builder, &query.filter, true);
+
+        // Keyset: continuar después de (valor de orden, id) del último producto entregado
+        if let Some((value, id)) = &query.after {
+            builder
+                .push(format!(" AND ({}, p.id_producto) {} (", sort_expr, cmp))
+                .push_bind(value.clone())
+                .push(cast)
+                .push(", ")
+                .push_bind(*id)
+                .push(")");
+        }
+
+        builder
+            .push(format!(" ORDER BY {} {}, p.id_producto {} LIMIT ", sort_expr, direction, direction))
+            .push_bind(query.limit)
+            .push(" OFFSET ")
+            .push_bind(query.offset);
+
+        let productos = builder.build_query_as::<Producto>().fetch_all(&self.pool).await?;
 
         Ok(productos)
     }
 
+    async fn count(&self, filter: &ProductoFilter) -> AppResult<i64> {
+        let mut builder = QueryBuilder::<Postgres>::new(
+            "SELECT COUNT(*) FROM productos p WHERE p.estado = true",
+        );
+        push_filters(&mut builder, filter, true);
+
+        let total = builder.build_query_scalar::<i64>().fetch_one(&self.pool).await?;
+
+        Ok(total)
+    }
+
+    async fn count_by_categoria(&self, filter: &ProductoFilter) -> AppResult<Vec<CategoriaConteo>> {
+        let mut builder = QueryBuilder::<Postgres>::new(
+            "SELECT c.id_categoria, c.slug, c.nombre, COUNT(*) AS total
+             FROM productos p
+             LEFT JOIN categorias c ON c.id_categoria = p.id_categoria
+             WHERE p.estado = true",
+        );
+        push_filters(&mut builder, filter, false);
+        builder.push(" GROUP BY c.id_categoria, c.slug, c.nombre ORDER BY c.nombre NULLS LAST");
+
+        let counts = builder
+            .build_query_as::<CategoriaConteo>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(counts)
+    }
+
     async fn find_all(&self) -> AppResult<Vec<Producto>> {
         let productos = sqlx::query_as::<_, Producto>(
             r#"
             SELECT id_producto, nombre_producto, descripcion, precio, stock,
-                   categoria, sku, estado, created_at, updated_at
+                   categoria, id_categoria, sku, estado, created_at, updated_at
             FROM productos
             ORDER BY created_at DESC
             "#,
@@ -81,39 +137,74 @@ impl ProductoRepository for ProductoRepositoryImpl {
         Ok(productos)
     }
 
-    async fn find_by_categoria(&self, categoria: &str) -> AppResult<Vec<Producto>> {
-        let productos = sqlx::query_as::<_, Producto>(
+    async fn search(&self, query: &str, limit: i64) -> AppResult<Vec<ProductoBusqueda>> {
+        // Coincidencia exacta de SKU pesa más que cualquier rango de texto
+        let productos = sqlx::query_as::<_, ProductoBusqueda>(
             r#"
-            SELECT id_producto, nombre_producto, descripcion, precio, stock,
-                   categoria, sku, estado, created_at, updated_at
-            FROM productos
-            WHERE categoria = $1 AND estado = true
-            ORDER BY nombre_producto ASC
+            WITH q AS (
+                SELECT websearch_to_tsquery('public.spanish_unaccent', $1) AS consulta,
+                       lower(trim($1)) AS termino
+            )
+            SELECT p.id_producto, p.nombre_producto, p.descripcion, p.precio, p.stock,
+                   p.categoria, p.id_categoria, p.sku, p.estado, p.created_at, p.updated_at,
+                   (ts_rank_cd(p.busqueda, q.consulta, 32)
+                    + CASE WHEN lower(p.sku) = q.termino THEN 1.0
+                           WHEN lower(p.sku) LIKE $2 THEN 0.5
+                           ELSE 0 END)::float8 AS score,
+                   ts_headline('public.spanish_unaccent', p.nombre_producto, q.consulta, $3) AS nombre_resaltado,
+                   ts_headline('public.spanish_unaccent', p.descripcion, q.consulta, $4) AS descripcion_resaltada
+            FROM productos p, q
+            WHERE p.estado = true
+              AND (p.busqueda @@ q.consulta OR lower(p.sku) LIKE $2)
+            ORDER BY score DESC, p.nombre_producto ASC
+            LIMIT $5
             "#,
         )
-        .bind(categoria)
+        .bind(query)
+        .bind(patron_prefijo(query))
+        .bind(format!("{}, HighlightAll=true", marcas_resaltado()))
+        .bind(format!(
+            "{}, MaxFragments=2, MaxWords=20, MinWords=8, FragmentDelimiter=\" … \"",
+            marcas_resaltado()
+        ))
+        .bind(limit)
         .fetch_all(&self.pool)
         .await?;
 
         Ok(productos)
     }
 
-    async fn search(&self, query: &str) -> AppResult<Vec<Producto>> {
-        let search_pattern = format!("%{}%", query);
-        let productos = sqlx::query_as::<_, Producto>(
+    async fn search_similares(&self, query: &str, limit: i64) -> AppResult<Vec<ProductoBusqueda>> {
+        // `<%` usa el índice trigram con pg_trgm.word_similarity_threshold; el 0.6
+        // por defecto descarta errores comunes en palabras cortas ("audifnos")
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SELECT set_config('pg_trgm.word_similarity_threshold', $1, true)")
+            .bind(UMBRAL_SIMILITUD)
+            .execute(&mut *tx)
+            .await?;
+
+        let productos = sqlx::query_as::<_, ProductoBusqueda>(
             r#"
-            SELECT id_producto, nombre_producto, descripcion, precio, stock,
-                   categoria, sku, estado, created_at, updated_at
-            FROM productos
-            WHERE (nombre_producto ILIKE $1 OR sku ILIKE $1 OR descripcion ILIKE $1)
-              AND estado = true
-            ORDER BY nombre_producto ASC
+            WITH q AS (SELECT public.f_unaccent(lower(trim($1))) AS termino)
+            SELECT p.id_producto, p.nombre_producto, p.descripcion, p.precio, p.stock,
+                   p.categoria, p.id_categoria, p.sku, p.estado, p.created_at, p.updated_at,
+                   word_similarity(q.termino, public.f_unaccent(lower(p.nombre_producto)))::float8 AS score,
+                   p.nombre_producto AS nombre_resaltado,
+                   NULL::text AS descripcion_resaltada
+            FROM productos p, q
+            WHERE p.estado = true
+              AND q.termino <% public.f_unaccent(lower(p.nombre_producto))
+            ORDER BY score DESC, p.nombre_producto ASC
+            LIMIT $2
             "#,
         )
-        .bind(&search_pattern)
-        .fetch_all(&self.pool)
+        .bind(query)
+        .bind(limit)
+        .fetch_all(&mut *tx)
         .await?;
 
+        tx.commit().await?;
+
         Ok(productos)
     }
 
@@ -122,27 +213,35 @@ impl ProductoRepository for ProductoRepositoryImpl {
         nombre: &str,
         descripcion: Option<&str>,
         precio: Decimal,
-        stock: i32,
-        categoria: Option<&str>,
+        id_categoria: Option<Uuid>,
         sku: Option<&str>,
+        stock_inicial: Option<&NuevoMovimientoStock>,
     ) -> AppResult<Producto> {
-        let producto = sqlx::query_as::<_, Producto>(
+        let mut tx = self.pool.begin().await?;
+
+        // Se crea sin stock; el inicial entra como movimiento en su almacén
+        let mut producto = sqlx::query_as::<_, Producto>(
             r#"
-            INSERT INTO productos (nombre_producto, descripcion, precio, stock, categoria, sku)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO productos (nombre_producto, descripcion, precio, stock, id_categoria, categoria, sku)
+            VALUES ($1, $2, $3, 0, $4, (SELECT nombre FROM categorias WHERE id_categoria = $4), $5)
             RETURNING id_producto, nombre_producto, descripcion, precio, stock,
-                      categoria, sku, estado, created_at, updated_at
+                      categoria, id_categoria, sku, estado, created_at, updated_at
             "#,
         )
         .bind(nombre)
         .bind(descripcion)
         .bind(precio)
-        .bind(stock)
-        .bind(categoria)
+        .bind(id_categoria)
         .bind(sku)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await?;
 
+        if let Some(movimiento) = stock_inicial {
+            producto = aplicar_movimiento(&mut tx, producto.id_producto, movimiento).await?.0;
+        }
+
+        tx.commit().await?;
+
         Ok(producto)
     }
 
@@ -152,7 +251,7 @@ impl ProductoRepository for ProductoRepositoryImpl {
         nombre: Option<&str>,
         descripcion: Option<&str>,
         precio: Option<Decimal>,
-        categoria: Option<&str>,
+        id_categoria: Option<Option<Uuid>>,
         sku: Option<&str>,
     ) -> AppResult<Producto> {
         let producto = sqlx::query_as::<_, Producto>(
@@ -161,20 +260,24 @@ impl ProductoRepository for ProductoRepositoryImpl {
             SET nombre_producto = COALESCE($2, nombre_producto),
                 descripcion = COALESCE($3, descripcion),
                 precio = COALESCE($4, precio),
-                categoria = COALESCE($5, categoria),
+                id_categoria = CASE WHEN $7 THEN $5 ELSE id_categoria END,
+                categoria = CASE WHEN $7
+                    THEN (SELECT nombre FROM categorias WHERE id_categoria = $5)
+                    ELSE categoria END,
                 sku = COALESCE($6, sku),
                 updated_at = NOW()
             WHERE id_producto = $1
             RETURNING id_producto, nombre_producto, descripcion, precio, stock,
-                      categoria, sku, estado, created_at, updated_at
+                      categoria, id_categoria, sku, estado, created_at, updated_at
             "#,
         )
         .bind(id)
         .bind(nombre)
         .bind(descripcion)
         .bind(precio)
-        .bind(categoria)
+        .bind(id_categoria.flatten())
         .bind(sku)
+        .bind(id_categoria.is_some())
         .fetch_one(&self.pool)
         .await
         .map_err(|e| match e {
@@ -185,29 +288,255 @@ impl ProductoRepository for ProductoRepositoryImpl {
         Ok(producto)
     }
 
-    async fn update_stock(&self, id: Uuid, cantidad: i32) -> AppResult<Producto> {
+    async fn update_stock(
+        &self,
+        id: Uuid,
+        movimiento: &NuevoMovimientoStock,
+    ) -> AppResult<(Producto, MovimientoStock)> {
+        let mut tx = self.pool.begin().await?;
+        let resultado = aplicar_movimiento(&mut tx, id, movimiento).await?;
+        tx.commit().await?;
+
+        Ok(resultado)
+    }
+
+    async fn transferir_stock(
+        &self,
+        id: Uuid,
+        transferencia: &NuevaTransferenciaStock,
+    ) -> AppResult<(Producto, Vec<MovimientoStock>)> {
+        let mut tx = self.pool.begin().await?;
+
+        // Mismo orden de bloqueo que los ajustes: primero el producto, luego sus almacenes
+        let producto = sqlx::query_as::<_, Producto>(
+            r#"
+            SELECT id_producto, nombre_producto, descripcion, precio, stock,
+                   categoria, id_categoria, sku, estado, created_at, updated_at
+            FROM productos
+            WHERE id_producto = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Producto con ID {} no encontrado", id)))?;
+
+        let id_transferencia = Uuid::new_v4();
+        let mut movimientos = Vec::with_capacity(2);
+        for (id_almacen, cantidad) in [
+            (transferencia.id_almacen_origen, -transferencia.cantidad),
+            (transferencia.id_almacen_destino, transferencia.cantidad),
+        ] {
+            let movimiento = NuevoMovimientoStock {
+                id_almacen,
+                cantidad,
+                motivo: transferencia.motivo.clone(),
+                actor: transferencia.actor,
+                id_pedido: None,
+            };
+            let saldo_almacen = ajustar_almacen(&mut tx, id, id_almacen, cantidad).await?;
+            movimientos.push(
+                insertar_movimiento(&mut tx, &producto, &movimiento, saldo_almacen, Some(id_transferencia)).await?,
+            );
+        }
+
+        tx.commit().await?;
+
+        Ok((producto, movimientos))
+    }
+
+    async fn asignar_stock_sin_almacen(&self, id: Uuid, id_almacen: Uuid, motivo: &str) -> AppResult<i32> {
+        let mut tx = self.pool.begin().await?;
+
         let producto = sqlx::query_as::<_, Producto>(
             r#"
-            UPDATE productos
-            SET stock = stock + $2,
-                updated_at = NOW()
-            WHERE id_producto = $1 AND (stock + $2) >= 0
-            RETURNING id_producto, nombre_producto, descripcion, precio, stock,
-                      categoria, sku, estado, created_at, updated_at
+            SELECT id_producto, nombre_producto, descripcion, precio, stock,
+                   categoria, id_categoria, sku, estado, created_at, updated_at
+            FROM productos
+            WHERE id_producto = $1
+            FOR UPDATE
             "#,
         )
         .bind(id)
-        .bind(cantidad)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| match e {
-            sqlx::Error::RowNotFound => AppError::BadRequest(
-                "Producto no encontrado o stock resultante sería negativo".to_string(),
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Producto con ID {} no encontrado", id)))?;
+
+        let en_almacenes: i64 =
+            sqlx::query_scalar("SELECT COALESCE(SUM(stock), 0)::bigint FROM inventario_almacen WHERE id_producto = $1")
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await?;
+        let Ok(cantidad) = i32::try_from(i64::from(producto.stock) - en_almacenes) else {
+            return Err(AppError::Internal(format!("Stock sin almacén fuera de rango en {}", id)));
+        };
+        if cantidad <= 0 {
+            return Ok(0);
+        }
+
+        // Igual que la migración 016: salida sin almacén y entrada en el almacén
+        let id_transferencia = Uuid::new_v4();
+        sqlx::query(
+            r#"
+            INSERT INTO movimientos_stock (id_producto, cantidad, saldo, motivo, id_transferencia)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(id)
+        .bind(-cantidad)
+        .bind(producto.stock)
+        .bind(motivo)
+        .bind(id_transferencia)
+        .execute(&mut *tx)
+        .await?;
+
+        let entrada = NuevoMovimientoStock {
+            id_almacen,
+            cantidad,
+            motivo: motivo.to_string(),
+            actor: None,
+            id_pedido: None,
+        };
+        let saldo_almacen = ajustar_almacen(&mut tx, id, id_almacen, cantidad).await?;
+        insertar_movimiento(&mut tx, &producto, &entrada, saldo_almacen, Some(id_transferencia)).await?;
+
+        tx.commit().await?;
+
+        Ok(cantidad)
+    }
+
+    async fn find_inventario(&self, ids: &[Uuid]) -> AppResult<Vec<InventarioAlmacen>> {
+        let inventario = sqlx::query_as::<_, InventarioAlmacen>(
+            r#"
+            SELECT i.id_producto, i.id_almacen, d.calle AS almacen, d.ciudad, d.activo, i.stock, i.updated_at
+            FROM inventario_almacen i
+            JOIN direcciones d ON d.id_direccion = i.id_almacen
+            WHERE i.id_producto = ANY($1)
+            ORDER BY d.activo DESC, d.ciudad ASC, d.calle ASC
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(inventario)
+    }
+
+    async fn find_disponibilidad(&self, ids: &[Uuid], id_almacen: Option<Uuid>) -> AppResult<Vec<(Uuid, i64)>> {
+        let disponibilidad = sqlx::query_as::<_, (Uuid, i64)>(
+            r#"
+            SELECT i.id_producto, SUM(i.stock)::bigint
+            FROM inventario_almacen i
+            JOIN direcciones d ON d.id_direccion = i.id_almacen AND d.activo = true
+            WHERE i.id_producto = ANY($1)
+              AND ($2::uuid IS NULL OR i.id_almacen = $2)
+            GROUP BY i.id_producto
+            "#,
+        )
+        .bind(ids)
+        .bind(id_almacen)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(disponibilidad)
+    }
+
+    async fn find_movimientos(&self, query: &MovimientosStockQuery) -> AppResult<Vec<MovimientoStock>> {
+        let mut builder = QueryBuilder::<Postgres>::new(
+            "SELECT id_movimiento, id_producto, cantidad, saldo, id_almacen, saldo_almacen, id_transferencia,
+                    motivo, id_actor, id_api_key, id_pedido, created_at
+             FROM movimientos_stock
+             WHERE id_producto = ",
+        );
+        builder.push_bind(query.id_producto);
+        push_rango_movimientos(&mut builder, query);
+
+        if let Some((created_at, id)) = query.after {
+            builder
+                .push(" AND (created_at, id_movimiento) < (")
+                .push_bind(created_at)
+                .push(", ")
+                .push_bind(id)
+                .push(")");
+        }
+
+        builder
+            .push(" ORDER BY created_at DESC, id_movimiento DESC LIMIT ")
+            .push_bind(query.limit);
+
+        let movimientos = builder
+            .build_query_as::<MovimientoStock>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(movimientos)
+    }
+
+    async fn count_movimientos(&self, query: &MovimientosStockQuery) -> AppResult<i64> {
+        let mut builder =
+            QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM movimientos_stock WHERE id_producto = ");
+        builder.push_bind(query.id_producto);
+        push_rango_movimientos(&mut builder, query);
+
+        let total = builder.build_query_scalar::<i64>().fetch_one(&self.pool).await?;
+
+        Ok(total)
+    }
+
+    async fn find_descuadres_stock(&self) -> AppResult<Vec<DescuadreStock>> {
+        let descuadres = sqlx::query_as::<_, DescuadreStock>(
+            r#"
+            WITH kardex AS (
+                SELECT id_producto, SUM(cantidad) AS suma, COUNT(*) AS movimientos
+                FROM movimientos_stock
+                GROUP BY id_producto
             ),
-            _ => AppError::Database(e),
-        })?;
+            almacenes AS (
+                SELECT id_producto, SUM(stock) AS suma
+                FROM inventario_almacen
+                GROUP BY id_producto
+            ),
+            -- Stock de cada almacén frente a la suma de sus movimientos
+            por_almacen AS (
+                SELECT COALESCE(i.id_producto, m.id_producto) AS id_producto, COUNT(*) AS descuadrados
+                FROM inventario_almacen i
+                FULL JOIN (
+                    SELECT id_producto, id_almacen, SUM(cantidad) AS suma
+                    FROM movimientos_stock
+                    WHERE id_almacen IS NOT NULL
+                    GROUP BY id_producto, id_almacen
+                ) m ON m.id_producto = i.id_producto AND m.id_almacen = i.id_almacen
+                WHERE COALESCE(i.stock, 0) <> COALESCE(m.suma, 0)
+                GROUP BY 1
+            )
+            SELECT p.id_producto, p.nombre_producto, p.sku, p.stock,
+                   COALESCE(k.suma, 0)::bigint AS suma_movimientos,
+                   COALESCE(k.movimientos, 0) AS movimientos,
+                   COALESCE(a.suma, 0)::bigint AS suma_almacenes,
+                   COALESCE(pa.descuadrados, 0) AS almacenes_descuadrados
+            FROM productos p
+            LEFT JOIN kardex k ON k.id_producto = p.id_producto
+            LEFT JOIN almacenes a ON a.id_producto = p.id_producto
+            LEFT JOIN por_almacen pa ON pa.id_producto = p.id_producto
+            WHERE p.stock <> COALESCE(k.suma, 0)
+               OR p.stock <> COALESCE(a.suma, 0)
+               OR pa.descuadrados IS NOT NULL
+            ORDER BY p.nombre_producto ASC, p.id_producto ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-        Ok(producto)
+        Ok(descuadres)
+    }
+
+    async fn count_all(&self) -> AppResult<i64> {
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM productos")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(total)
     }
 
     async fn update_estado(&self, id: Uuid, estado: bool) -> AppResult<Producto> {
@@ -218,7 +547,7 @@ impl ProductoRepository for ProductoRepositoryImpl {
                 updated_at = NOW()
             WHERE id_producto = $1
             RETURNING id_producto, nombre_producto, descripcion, precio, stock,
-                      categoria, sku, estado, created_at, updated_at
+                      categoria, id_categoria, sku, estado, created_at, updated_at
             "#,
         )
         .bind(id)
@@ -233,17 +562,39 @@ impl ProductoRepository for ProductoRepositoryImpl {
         Ok(producto)
     }
 
-    async fn delete(&self, id: Uuid) -> AppResult<()> {
-        let result = sqlx::query("DELETE FROM productos WHERE id_producto = $1")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+    async fn delete(&self, id: Uuid) -> AppResult<bool> {
+        let mut tx = self.pool.begin().await?;
 
-        if result.rows_affected() == 0 {
-            return Err(AppError::NotFound(format!("Producto {} no encontrado", id)));
+        // Los movimientos se registran con el producto bloqueado; con el bloqueo
+        // tomado aquí no puede aparecer uno entre la verificación y el DELETE
+        let (con_movimientos,): (bool,) = sqlx::query_as(
+            r#"
+            SELECT EXISTS (SELECT 1 FROM movimientos_stock WHERE id_producto = p.id_producto)
+            FROM productos p
+            WHERE p.id_producto = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Producto {} no encontrado", id)))?;
+
+        if con_movimientos {
+            sqlx::query("UPDATE productos SET estado = false, updated_at = NOW() WHERE id_producto = $1")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        } else {
+            sqlx::query("DELETE FROM productos WHERE id_producto = $1")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
         }
 
-        Ok(())
+        tx.commit().await?;
+
+        Ok(!con_movimientos)
     }
 
     async fn exists_sku(&self, sku: &str) -> AppResult<bool> {
@@ -257,3 +608,221 @@ impl ProductoRepository for ProductoRepositoryImpl {
         Ok(result.map(|(count,)| count > 0).unwrap_or(false))
     }
 }
+
+/// Ajusta el stock de un producto en un almacén y su total, y registra el movimiento.
+/// Debe ejecutarse dentro de una transacción.
+async fn aplicar_movimiento(
+    conn: &mut PgConnection,
+    id: Uuid,
+    movimiento: &NuevoMovimientoStock,
+) -> AppResult<(Producto, MovimientoStock)> {
+    if let Some(id_pedido) = movimiento.id_pedido {
+        let existe: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pedidos WHERE id_pedido = $1)")
+            .bind(id_pedido)
+            .fetch_one(&mut *conn)
+            .await?;
+        if !existe {
+            return Err(AppError::InvalidField {
+                field: "id_pedido",
+                message: format!("El pedido {} no existe", id_pedido),
+            });
+        }
+    }
+
+    // El UPDATE bloquea la fila del producto: los saldos del movimiento son los
+    // resultantes aunque haya ajustes concurrentes
+    let producto = sqlx::query_as::<_, Producto>(
+        r#"
+        UPDATE productos
+        SET stock = stock + $2,
+            updated_at = NOW()
+        WHERE id_producto = $1 AND (stock + $2) >= 0
+        RETURNING id_producto, nombre_producto, descripcion, precio, stock,
+                  categoria, id_categoria, sku, estado, created_at, updated_at
+        "#,
+    )
+    .bind(id)
+    .bind(movimiento.cantidad)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::BadRequest("Producto no encontrado o stock resultante sería negativo".to_string()))?;
+
+    let saldo_almacen = ajustar_almacen(&mut *conn, id, movimiento.id_almacen, movimiento.cantidad).await?;
+    let registrado = insertar_movimiento(&mut *conn, &producto, movimiento, saldo_almacen, None).await?;
+
+    Ok((producto, registrado))
+}
+
+/// Suma `cantidad` al stock del producto en el almacén y devuelve el saldo resultante
+async fn ajustar_almacen(conn: &mut PgConnection, id_producto: Uuid, id_almacen: Uuid, cantidad: i32) -> AppResult<i32> {
+    let saldo = if cantidad > 0 {
+        sqlx::query_scalar::<_, i32>(
+            r#"
+            INSERT INTO inventario_almacen (id_producto, id_almacen, stock)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (id_producto, id_almacen)
+            DO UPDATE SET stock = inventario_almacen.stock + EXCLUDED.stock, updated_at = NOW()
+            RETURNING stock
+            "#,
+        )
+        .bind(id_producto)
+        .bind(id_almacen)
+        .bind(cantidad)
+        .fetch_one(&mut *conn)
+        .await?
+    } else {
+        sqlx::query_scalar::<_, i32>(
+            r#"
+            UPDATE inventario_almacen
+            SET stock = stock + $3, updated_at = NOW()
+            WHERE id_producto = $1 AND id_almacen = $2 AND stock + $3 >= 0
+            RETURNING stock
+            "#,
+        )
+        .bind(id_producto)
+        .bind(id_almacen)
+        .bind(cantidad)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::BadRequest(format!("Stock insuficiente en el almacén {}", id_almacen)))?
+    };
+
+    Ok(saldo)
+}
+
+async fn insertar_movimiento(
+    conn: &mut PgConnection,
+    producto: &Producto,
+    movimiento: &NuevoMovimientoStock,
+    saldo_almacen: i32,
+    id_transferencia: Option<Uuid>,
+) -> AppResult<MovimientoStock> {
+    let registrado = sqlx::query_as::<_, MovimientoStock>(
+        r#"
+        INSERT INTO movimientos_stock
+            (id_producto, cantidad, saldo, id_almacen, saldo_almacen, id_transferencia, motivo, id_actor, id_api_key, id_pedido)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING id_movimiento, id_producto, cantidad, saldo, id_almacen, saldo_almacen, id_transferencia,
+                  motivo, id_actor, id_api_key, id_pedido, created_at
+        "#,
+    )
+    .bind(producto.id_producto)
+    .bind(movimiento.cantidad)
+    .bind(producto.stock)
+    .bind(movimiento.id_almacen)
+    .bind(saldo_almacen)
+    .bind(id_transferencia)
+    .bind(&movimiento.motivo)
+    .bind(movimiento.actor.and_then(|a| a.id_actor()))
+    .bind(movimiento.actor.and_then(|a| a.id_api_key()))
+    .bind(movimiento.id_pedido)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(registrado)
+}
+
+fn sort_column(sort: ProductoSortField) -> (&'static str, &'static str) {
+    match sort {
+        ProductoSortField::Precio => ("p.precio", "::numeric"),
+        ProductoSortField::Nombre => ("p.nombre_producto", "::text"),
+        ProductoSortField::CreatedAt => ("p.created_at", "::timestamptz"),
+    }
+}
+
+/// Agrega los filtros del catálogo a la cláusula WHERE (productos con alias `p`)
+fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, filter: &ProductoFilter, include_categoria: bool) {
+    if include_categoria && !filter.categorias.is_empty() {
+        // Las categorías pedidas y sus subcategorías activas
+        builder
+            .push(
+                " AND p.id_categoria IN (
+                    WITH RECURSIVE subarbol AS (
+                        SELECT id_categoria FROM categorias WHERE activo AND slug = ANY(",
+            )
+            .push_bind(filter.categorias.clone())
+            .push(
+                ")
+                        UNION ALL
+                        SELECT c.id_categoria FROM categorias c
+                        JOIN subarbol s ON c.id_padre = s.id_categoria
+                        WHERE c.activo
+                    )
+                    SELECT id_categoria FROM subarbol
+                )",
+            );
+    }
+    if let Some(min) = filter.precio_min {
+        builder.push(" AND p.precio >= ").push_bind(min);
+    }
+    if let Some(max) = filter.precio_max {
+        builder.push(" AND p.precio <= ").push_bind(max);
+    }
+    if filter.solo_en_stock {
+        builder.push(
+            " AND EXISTS (
+                SELECT 1 FROM inventario_almacen i
+                JOIN direcciones d ON d.id_direccion = i.id_almacen AND d.activo = true
+                WHERE i.id_producto = p.id_producto AND i.stock > 0",
+        );
+        if let Some(id_almacen) = filter.id_almacen {
+            builder.push(" AND i.id_almacen = ").push_bind(id_almacen);
+        }
+        builder.push(")");
+    }
+}
+
+/// Agrega el almacén y el rango de fechas (inclusive) del kardex a la cláusula WHERE
+fn push_rango_movimientos(builder: &mut QueryBuilder<'_, Postgres>, query: &MovimientosStockQuery) {
+    if let Some(id_almacen) = query.id_almacen {
+        builder.push(" AND id_almacen = ").push_bind(id_almacen);
+    }
+    if let Some(desde) = query.desde {
+        builder.push(" AND created_at >= ").push_bind(desde);
+    }
+    if let Some(hasta) = query.hasta {
+        builder.push(" AND created_at <= ").push_bind(hasta);
+    }
+}
+
+/// Patrón LIKE para `lower(sku)` que empieza con el término (comodines escapados)
+fn patron_prefijo(query: &str) -> String {
+    let mut patron = String::with_capacity(query.len() + 1);
+    for c in query.trim().to_lowercase().chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            patron.push('\\');
+        }
+        patron.push(c);
+    }
+    patron.push('%');
+    patron
+}
+
+/// Opciones StartSel/StopSel de `ts_headline` con las marcas del dominio
+fn marcas_resaltado() -> String {
+    format!("StartSel=\"{}\", StopSel=\"{}\"", INICIO_RESALTADO, FIN_RESALTADO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patron_prefijo_agrega_el_comodin_final() {
+        assert_eq!(patron_prefijo("ABC-12"), "abc-12%");
+        assert_eq!(patron_prefijo("  Sku  "), "sku%");
+    }
+
+    #[test]
+    fn patron_prefijo_escapa_los_comodines_de_like() {
+        assert_eq!(patron_prefijo("50%"), "50\\%%");
+        assert_eq!(patron_prefijo("a_b"), "a\\_b%");
+        assert_eq!(patron_prefijo("c:\\x"), "c:\\\\x%");
+        assert_eq!(patron_prefijo("%_\\"), "\\%\\_\\\\%");
+    }
+
+    #[test]
+    fn patron_prefijo_vacio_coincide_con_todo() {
+        assert_eq!(patron_prefijo("   "), "%");
+    }
+}
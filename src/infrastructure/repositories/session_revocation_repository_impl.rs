@@ -0,0 +1,62 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::SessionRevocation;
+use crate::domain::repositories::SessionRevocationRepository;
+use crate::shared::AppResult;
+
+/// Implementación del repositorio de revocaciones de sesión con SQLx
+pub struct SessionRevocationRepositoryImpl {
+    pool: PgPool,
+}
+
+impl SessionRevocationRepositoryImpl {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionRevocationRepository for SessionRevocationRepositoryImpl {
+    async fn find_by_usuario(&self, id_usuario: Uuid) -> AppResult<Option<SessionRevocation>> {
+        let revocation = sqlx::query_as::<_, SessionRevocation>(
+            r#"
+            SELECT id_usuario, revocado_antes, motivo, revocado_por, updated_at
+            FROM public.session_revocations
+            WHERE id_usuario = $1
+            "#,
+        )
+        .bind(id_usuario)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(revocation)
+    }
+
+    async fn revoke_all(
+        &self,
+        id_usuario: Uuid,
+        motivo: Option<&str>,
+        revocado_por: Option<Uuid>,
+    ) -> AppResult<SessionRevocation> {
+        let revocation = sqlx::query_as::<_, SessionRevocation>(
+            r#"
+            INSERT INTO public.session_revocations (id_usuario, revocado_antes, motivo, revocado_por, updated_at)
+            VALUES ($1, NOW(), $2, $3, NOW())
+            ON CONFLICT (id_usuario) DO UPDATE
+            SET revocado_antes = GREATEST(session_revocations.revocado_antes, EXCLUDED.revocado_antes),
+                motivo = EXCLUDED.motivo,
+                revocado_por = EXCLUDED.revocado_por,
+                updated_at = NOW()
+            RETURNING id_usuario, revocado_antes, motivo, revocado_por, updated_at
+            "#,
+        )
+        .bind(id_usuario)
+        .bind(motivo)
+        .bind(revocado_por)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(revocation)
+    }
+}
@@ -0,0 +1,37 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::Transportista;
+use crate::domain::repositories::TransportistaRepository;
+use crate::shared::AppResult;
+
+/// Implementación del repositorio de transportistas con SQLx
+pub struct TransportistaRepositoryImpl {
+    pool: PgPool,
+}
+
+impl TransportistaRepositoryImpl {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl TransportistaRepository for TransportistaRepositoryImpl {
+    async fn find_by_usuario(&self, id_usuario: Uuid) -> AppResult<Option<Transportista>> {
+        let transportista = sqlx::query_as::<_, Transportista>(
+            r#"
+            SELECT id_transportista, id_usuario, tipo_vehiculo, placa_vehiculo,
+                   capacidad_carga, estado::text AS estado, id_zona_asignada,
+                   calificacion_promedio, created_at, updated_at
+            FROM transportistas
+            WHERE id_usuario = $1
+            "#,
+        )
+        .bind(id_usuario)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(transportista)
+    }
+}
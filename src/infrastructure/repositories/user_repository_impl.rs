@@ -1,8 +1,8 @@
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
 use crate::domain::entities::User;
-use crate::domain::repositories::UserRepository;
+use crate::domain::repositories::{UserFilter, UserPageQuery, UserRepository, UserSortField};
 
 pub struct UserRepositoryImpl {
     pool: PgPool,
@@ -38,37 +38,43 @@ impl UserRepository for UserRepositoryImpl {
         .await
     }
 
-    async fn find_all(&self) -> Result<Vec<User>, sqlx::Error> {
-        sqlx::query_as::<_, User>(
-            "SELECT id, email, nombre, apellido, rol, foto_perfil, activo, created_at, updated_at 
-             FROM public.users 
-             WHERE activo = true
-             ORDER BY created_at DESC"
-        )
-        .fetch_all(&self.pool)
-        .await
-    }
+    async fn find_page(&self, query: &UserPageQuery) -> Result<Vec<User>, sqlx::Error> {
+        let (sort_expr, cast) = sort_column(query.sort);
+        let (cmp, direction) = if query.descending { ("<", "DESC") } else { (">", "ASC") };
 
-    async fn find_all_paginated(&self, limit: i64, offset: i64) -> Result<(Vec<User>, i64), sqlx::Error> {
-        let total: i64 = sqlx::query_scalar(
-            "SELECT COUNT(*) FROM public.users WHERE activo = true"
-        )
-        .fetch_one(&self.pool)
-        .await?;
+        let mut builder = QueryBuilder::<Postgres>::new(
+            "SELECT id, email, nombre, apellido, rol, foto_perfil, activo, created_at, updated_at
+             FROM public.users
+             WHERE true",
+        );
+        push_filters(&mut builder, &query.filter, true);
 
-        let users = sqlx::query_as::<_, User>(
-            "SELECT id, email, nombre, apellido, rol, foto_perfil, activo, created_at, updated_at 
-             FROM public.users 
-             WHERE activo = true
-             ORDER BY created_at DESC
-             LIMIT $1 OFFSET $2"
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await?;
+        // Keyset: continuar después de (valor de orden, id) del último usuario entregado
+        if let Some((value, id)) = &query.after {
+            builder
+                .push(format!(" AND ({}, id) {} (", sort_expr, cmp))
+                .push_bind(value.clone())
+                .push(cast)
+                .push(", ")
+                .push_bind(*id)
+                .push(")");
+        }
+
+        builder
+            .push(format!(" ORDER BY {} {}, id {} LIMIT ", sort_expr, direction, direction))
+            .push_bind(query.limit);
 
-        Ok((users, total))
+        builder.build_query_as::<User>().fetch_all(&self.pool).await
+    }
+
+    async fn count_by_rol(&self, filter: &UserFilter) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        let mut builder = QueryBuilder::<Postgres>::new(
+            "SELECT rol, COUNT(*) FROM public.users WHERE true",
+        );
+        push_filters(&mut builder, filter, false);
+        builder.push(" GROUP BY rol ORDER BY rol");
+
+        builder.build_query_as::<(String, i64)>().fetch_all(&self.pool).await
     }
 
     async fn create(&self, user: &User) -> Result<User, sqlx::Error> {
@@ -136,6 +142,54 @@ impl UserRepository for UserRepositoryImpl {
         .await
     }
 
+    async fn provision(&self, id: Uuid, email: Option<&str>, rol: &str) -> Result<User, sqlx::Error> {
+        // Si el email ya pertenece a otra fila se aprovisiona sin email (columna UNIQUE)
+        sqlx::query_as::<_, User>(
+            "INSERT INTO public.users (id, email, rol, activo, created_at, updated_at)
+             VALUES (
+                 $1,
+                 CASE WHEN EXISTS (SELECT 1 FROM public.users WHERE email = $2) THEN NULL ELSE $2 END,
+                 $3, true, $4, $4
+             )
+             ON CONFLICT (id) DO UPDATE SET id = EXCLUDED.id
+             RETURNING id, email, nombre, apellido, rol, foto_perfil, activo, created_at, updated_at"
+        )
+        .bind(id)
+        .bind(email)
+        .bind(rol)
+        .bind(chrono::Utc::now())
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn update_email(&self, id: Uuid, email: &str) -> Result<User, sqlx::Error> {
+        sqlx::query_as::<_, User>(
+            "UPDATE public.users 
+             SET email = $1, updated_at = $2
+             WHERE id = $3
+             RETURNING id, email, nombre, apellido, rol, foto_perfil, activo, created_at, updated_at"
+        )
+        .bind(email)
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn update_foto_perfil(&self, id: Uuid, foto_perfil: Option<&str>) -> Result<User, sqlx::Error> {
+        sqlx::query_as::<_, User>(
+            "UPDATE public.users 
+             SET foto_perfil = $1, updated_at = $2
+             WHERE id = $3
+             RETURNING id, email, nombre, apellido, rol, foto_perfil, activo, created_at, updated_at"
+        )
+        .bind(foto_perfil)
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await
+    }
+
     async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
         sqlx::query(
             "UPDATE public.users 
@@ -150,3 +204,43 @@ impl UserRepository for UserRepositoryImpl {
         Ok(())
     }
 }
+
+/// Expresión SQL de la columna de orden y el cast del valor guardado en el cursor
+fn sort_column(sort: UserSortField) -> (&'static str, &'static str) {
+    match sort {
+        UserSortField::Email => ("COALESCE(email, '')", "::text"),
+        UserSortField::Nombre => ("COALESCE(nombre, '')", "::text"),
+        UserSortField::Apellido => ("COALESCE(apellido, '')", "::text"),
+        UserSortField::Rol => ("rol", "::text"),
+        UserSortField::Activo => ("activo", "::boolean"),
+        UserSortField::CreatedAt => ("created_at", "::timestamptz"),
+        UserSortField::UpdatedAt => ("updated_at", "::timestamptz"),
+    }
+}
+
+/// Agrega los filtros del directorio a la cláusula WHERE
+fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, filter: &UserFilter, include_rol: bool) {
+    if include_rol && let Some(rol) = &filter.rol {
+        builder.push(" AND rol = ").push_bind(rol.clone());
+    }
+    if let Some(activo) = filter.activo {
+        builder.push(" AND activo = ").push_bind(activo);
+    }
+    if let Some(desde) = filter.created_desde {
+        builder.push(" AND created_at >= ").push_bind(desde);
+    }
+    if let Some(hasta) = filter.created_hasta {
+        builder.push(" AND created_at <= ").push_bind(hasta);
+    }
+    if let Some(texto) = &filter.texto {
+        let pattern = format!("%{}%", texto.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+        builder
+            .push(" AND (email ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR nombre ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR apellido ILIKE ")
+            .push_bind(pattern)
+            .push(")");
+    }
+}
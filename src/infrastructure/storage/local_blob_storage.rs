@@ -0,0 +1,76 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::domain::storage::{is_valid_key, BlobStorage, StoredBlob};
+use crate::shared::{AppError, AppResult};
+
+/// Almacenamiento en el sistema de archivos local
+///
+/// Cada clave es una ruta relativa a `root`. El content type se deduce de la extensión,
+/// así que las claves deben terminar en la extensión del formato.
+pub struct LocalBlobStorage {
+    root: PathBuf,
+}
+
+impl LocalBlobStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> AppResult<PathBuf> {
+        if !is_valid_key(key) {
+            return Err(AppError::BadRequest("Clave de archivo inválida".to_string()));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+fn io_error(e: std::io::Error) -> AppError {
+    tracing::error!("Error de almacenamiento local: {:?}", e);
+    AppError::Internal("Error de almacenamiento".to_string())
+}
+
+fn content_type_for(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobStorage for LocalBlobStorage {
+    async fn put(&self, key: &str, data: Vec<u8>, _content_type: &str) -> AppResult<()> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(io_error)?;
+        }
+
+        // Escribir a un temporal y renombrar: nunca se sirve un archivo a medio escribir
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, data).await.map_err(io_error)?;
+        tokio::fs::rename(&tmp, &path).await.map_err(io_error)
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Option<StoredBlob>> {
+        let path = self.path(key)?;
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(Some(StoredBlob {
+                data,
+                content_type: content_type_for(&path).to_string(),
+            })),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error(e)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        let path = self.path(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(io_error(e)),
+        }
+    }
+}
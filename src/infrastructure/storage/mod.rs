@@ -0,0 +1,3 @@
+pub mod local_blob_storage;
+
+pub use local_blob_storage::LocalBlobStorage;
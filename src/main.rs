@@ -6,6 +6,7 @@ mod presentation;
 mod shared;
 
 use axum::Router;
+use std::net::SocketAddr;
 use config::{AppConfig, create_pool};
 use presentation::create_routes;
 
@@ -14,11 +15,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Cargar configuración desde variables de entorno
     let config = AppConfig::from_env();
 
+    // Subcomando de desarrollo: `integrador dev-token ...`
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("dev-token") {
+        return presentation::cli::run_dev_token(&config, &args[2..]).await;
+    }
+
+    // Backfill de teléfonos anteriores a la migración 008: `integrador normalize-telefonos`
+    if args.get(1).map(String::as_str) == Some("normalize-telefonos") {
+        return presentation::cli::run_normalize_telefonos(&config, &args[2..]).await;
+    }
+
+    // Stock que la migración 016 dejó sin almacén: `integrador asignar-stock --almacen <uuid>`
+    if args.get(1).map(String::as_str) == Some("asignar-stock") {
+        return presentation::cli::run_asignar_stock(&config, &args[2..]).await;
+    }
+
     // Crear pool de conexiones a la base de datos
     let pool = create_pool(&config.database_url).await?;
 
     // Crear router con todas las rutas
-    let app: Router = create_routes(pool);
+    let app: Router = create_routes(pool, &config);
 
     // Crear dirección del servidor
     let addr = format!("{}:{}", config.server_host, config.server_port);
@@ -27,8 +44,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🚀 Servidor iniciado en {}", addr);
     println!("📦 Ambiente: {}", config.environment);
 
-    // Iniciar servidor
-    axum::serve(listener, app).await?;
+    // Iniciar servidor (con la IP del cliente disponible para el rate limiting)
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
 
     Ok(())
 }
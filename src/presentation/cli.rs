@@ -0,0 +1,162 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::services::ProductoService;
+use crate::config::{create_pool, AppConfig};
+use crate::domain::auth::ROL_CLIENTE;
+use crate::domain::repositories::{PerfilClienteRepository, UserRepository};
+use crate::domain::validation::normalizar_telefono;
+use crate::infrastructure::auth::{DevTokenIssuer, DEV_TOKEN_DEFAULT_TTL};
+use crate::infrastructure::repositories::{
+    CategoriaRepositoryImpl, DireccionRepositoryImpl, PerfilClienteRepositoryImpl, ProductoRepositoryImpl,
+    UserRepositoryImpl,
+};
+
+const NORMALIZE_TELEFONOS_USAGE: &str = "Uso: integrador normalize-telefonos [--dry-run]";
+
+const ASIGNAR_STOCK_USAGE: &str = "Uso: integrador asignar-stock --almacen <uuid> [--dry-run]";
+
+const DEV_TOKEN_USAGE: &str = "Uso: integrador dev-token [--user-id <uuid>] [--email <email>] [--rol <cliente|transportista|admin>] [--expires-in <segundos>]";
+
+/// Subcomando `dev-token`: imprime un JWT local en stdout
+///
+/// Ejemplo: `TOKEN=$(cargo run -- dev-token --rol admin --email admin@local.test)`
+pub async fn run_dev_token(config: &AppConfig, args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    // Rechazar antes de conectarse a la base de datos
+    DevTokenIssuer::ensure_allowed(config)?;
+
+    let mut user_id = None;
+    let mut email = None;
+    let mut rol = ROL_CLIENTE.to_string();
+    let mut expires_in = DEV_TOKEN_DEFAULT_TTL;
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        if flag == "--help" || flag == "-h" {
+            println!("{}", DEV_TOKEN_USAGE);
+            return Ok(());
+        }
+
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("Falta el valor de {}\n{}", flag, DEV_TOKEN_USAGE))?;
+        match flag.as_str() {
+            "--user-id" => user_id = Some(value.parse::<Uuid>()?),
+            "--email" => email = Some(value.clone()),
+            "--rol" => rol = value.clone(),
+            "--expires-in" => expires_in = value.parse()?,
+            _ => return Err(format!("Argumento desconocido: {}\n{}", flag, DEV_TOKEN_USAGE).into()),
+        }
+    }
+
+    let pool = create_pool(&config.database_url).await?;
+    let user_repo: Arc<dyn UserRepository> = Arc::new(UserRepositoryImpl::new(pool));
+    let issuer = DevTokenIssuer::from_config(config, user_repo)?;
+
+    let token = issuer
+        .issue(user_id.unwrap_or_else(Uuid::new_v4), email.as_deref(), &rol, expires_in)
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+
+    eprintln!("user_id: {}  rol: {}  expira en: {}s", token.user_id, token.rol, token.expires_in);
+    println!("{}", token.access_token);
+
+    Ok(())
+}
+
+/// Subcomando `normalize-telefonos`: lleva a E.164 los teléfonos guardados antes de la migración 008
+///
+/// La normalización usa el plan de numeración de libphonenumber, que no existe en
+/// SQL, por eso no hay migración de datos. Se procesan los perfiles con teléfono y
+/// `tipo_telefono` NULL; los que no se pueden normalizar se listan en stderr y
+/// quedan sin cambios. Con `--dry-run` solo informa.
+pub async fn run_normalize_telefonos(config: &AppConfig, args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let dry_run = match args {
+        [] => false,
+        [flag] if flag == "--dry-run" => true,
+        [flag] if flag == "--help" || flag == "-h" => {
+            println!("{}", NORMALIZE_TELEFONOS_USAGE);
+            return Ok(());
+        }
+        _ => return Err(NORMALIZE_TELEFONOS_USAGE.into()),
+    };
+
+    let pool = create_pool(&config.database_url).await?;
+    let perfil_repo = PerfilClienteRepositoryImpl::new(pool);
+
+    let (mut normalizados, mut invalidos) = (0, 0);
+    for perfil in perfil_repo.find_all().await? {
+        let Some(telefono) = perfil.telefono.as_deref().filter(|_| perfil.tipo_telefono.is_none()) else {
+            continue;
+        };
+
+        match normalizar_telefono(telefono) {
+            Ok(normalizado) => {
+                if !dry_run {
+                    perfil_repo.update(perfil.id_perfil, None, Some(&normalizado)).await?;
+                }
+                normalizados += 1;
+            }
+            Err(e) => {
+                eprintln!("{}: {:?} no se normalizó: {}", perfil.id_perfil, telefono, e);
+                invalidos += 1;
+            }
+        }
+    }
+
+    let accion = if dry_run { "a normalizar" } else { "normalizados" };
+    println!("{} {}, {} sin cambios por inválidos", normalizados, accion, invalidos);
+
+    Ok(())
+}
+
+/// Subcomando `asignar-stock`: pasa a un almacén el stock que quedó sin almacén
+///
+/// La migración 016 asigna el stock existente al almacén más antiguo; si no
+/// había ninguno, el stock queda sin asignar y la conciliación lo reporta como
+/// descuadre. Cree el almacén y ejecute este subcomando: cada producto recibe una
+/// transferencia desde "sin almacén" en el kardex, como en la migración. Con
+/// `--dry-run` solo informa.
+pub async fn run_asignar_stock(config: &AppConfig, args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut id_almacen = None;
+    let mut dry_run = false;
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        match flag.as_str() {
+            "--help" | "-h" => {
+                println!("{}", ASIGNAR_STOCK_USAGE);
+                return Ok(());
+            }
+            "--dry-run" => dry_run = true,
+            "--almacen" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| format!("Falta el valor de {}\n{}", flag, ASIGNAR_STOCK_USAGE))?;
+                id_almacen = Some(value.parse::<Uuid>()?);
+            }
+            _ => return Err(format!("Argumento desconocido: {}\n{}", flag, ASIGNAR_STOCK_USAGE).into()),
+        }
+    }
+    let id_almacen = id_almacen.ok_or(ASIGNAR_STOCK_USAGE)?;
+
+    let pool = create_pool(&config.database_url).await?;
+    let service = ProductoService::new(
+        Arc::new(ProductoRepositoryImpl::new(pool.clone())),
+        Arc::new(CategoriaRepositoryImpl::new(pool.clone())),
+        Arc::new(DireccionRepositoryImpl::new(pool)),
+    );
+
+    let asignados = service
+        .asignar_stock_sin_almacen(id_almacen, dry_run)
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+
+    for (producto, unidades) in &asignados {
+        println!("{}: {} unidad(es) ({})", producto.id_producto, unidades, producto.nombre_producto);
+    }
+    let accion = if dry_run { "a asignar" } else { "asignados" };
+    println!("{} producto(s) {} al almacén {}", asignados.len(), accion, id_almacen);
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::policies::RolMiembro;
+use crate::domain::auth::{AuthenticatedPrincipal, AuthenticatedUser};
+use crate::domain::entities::PerfilCliente;
+use crate::domain::repositories::{PerfilClienteRepository, PerfilMiembroRepository};
+use crate::shared::AppError;
+
+/// Header para elegir con qué perfil operar cuando el usuario es miembro de otros
+pub const PERFIL_ID_HEADER: &str = "x-perfil-id";
+
+/// Perfil de cliente con el que opera el usuario autenticado, y su rol en él
+///
+/// Sin header `X-Perfil-Id` se usa el perfil propio (rol owner) o, si no tiene,
+/// el único perfil de empresa del que es miembro. Requiere que `require_auth`
+/// haya insertado el `AuthenticatedUser` y que los repositorios de perfiles y
+/// miembros estén disponibles como `Extension`.
+///
+/// - 409 `PERFIL_REQUERIDO`: no tiene perfil propio ni membresías
+/// - 409 `PERFIL_AMBIGUO`: es miembro de varios perfiles y no envió `X-Perfil-Id`
+/// - 403 `PERFIL_SIN_ACCESO`: el `X-Perfil-Id` no es suyo ni es miembro
+#[derive(Debug, Clone)]
+pub struct CurrentPerfil(pub PerfilCliente, pub RolMiembro);
+
+fn error(status: StatusCode, code: &str, message: &str) -> Response {
+    (
+        status,
+        Json(json!({
+            "error": {
+                "code": code,
+                "message": message
+            }
+        })),
+    ).into_response()
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for CurrentPerfil
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        // Las API keys de servicio no tienen perfil de cliente
+        if let Some(AuthenticatedPrincipal::ApiKey(_)) = parts.extensions.get::<AuthenticatedPrincipal>() {
+            return Err(error(
+                StatusCode::FORBIDDEN,
+                "USER_REQUIRED",
+                "Esta operación requiere un usuario autenticado, no una API key",
+            ));
+        }
+
+        let user = parts
+            .extensions
+            .get::<AuthenticatedUser>()
+            .cloned()
+            .ok_or_else(|| error(StatusCode::UNAUTHORIZED, "TOKEN_MISSING", "Authorization header is required"))?;
+
+        let perfil_id = match parts.headers.get(PERFIL_ID_HEADER) {
+            Some(value) => Some(
+                value
+                    .to_str()
+                    .ok()
+                    .and_then(|v| Uuid::parse_str(v.trim()).ok())
+                    .ok_or_else(|| {
+                        error(StatusCode::BAD_REQUEST, "PERFIL_ID_INVALIDO", "X-Perfil-Id debe ser un UUID")
+                    })?,
+            ),
+            None => None,
+        };
+
+        let no_configurado =
+            || AppError::Internal("Repositorio de perfiles no configurado".to_string()).into_response();
+        let perfil_repo = parts
+            .extensions
+            .get::<Arc<dyn PerfilClienteRepository>>()
+            .cloned()
+            .ok_or_else(no_configurado)?;
+        let miembro_repo = parts
+            .extensions
+            .get::<Arc<dyn PerfilMiembroRepository>>()
+            .cloned()
+            .ok_or_else(no_configurado)?;
+
+        let propio = perfil_repo
+            .find_by_usuario(user.id)
+            .await
+            .map_err(|e| AppError::from(e).into_response())?;
+
+        let (id_perfil, rol) = match (perfil_id, propio) {
+            (None, Some(perfil)) => return Ok(CurrentPerfil(perfil, RolMiembro::Owner)),
+            (Some(id), Some(perfil)) if perfil.id_perfil == id => {
+                return Ok(CurrentPerfil(perfil, RolMiembro::Owner));
+            }
+            (Some(id), _) => {
+                let miembro = miembro_repo
+                    .find(id, user.id)
+                    .await
+                    .map_err(IntoResponse::into_response)?
+                    .ok_or_else(|| {
+                        error(StatusCode::FORBIDDEN, "PERFIL_SIN_ACCESO", "No es miembro del perfil indicado")
+                    })?;
+                (miembro.id_perfil, miembro.rol)
+            }
+            (None, None) => {
+                let mut membresias = miembro_repo
+                    .find_by_usuario(user.id)
+                    .await
+                    .map_err(IntoResponse::into_response)?;
+                match membresias.len() {
+                    0 => {
+                        return Err(error(
+                            StatusCode::CONFLICT,
+                            "PERFIL_REQUERIDO",
+                            "Perfil requerido: debe crear un perfil de cliente primero",
+                        ));
+                    }
+                    1 => {
+                        let miembro = membresias.remove(0);
+                        (miembro.id_perfil, miembro.rol)
+                    }
+                    _ => {
+                        return Err(error(
+                            StatusCode::CONFLICT,
+                            "PERFIL_AMBIGUO",
+                            "Es miembro de varios perfiles: indique cuál usar con el header X-Perfil-Id",
+                        ));
+                    }
+                }
+            }
+        };
+
+        let rol = RolMiembro::parse(&rol)
+            .ok_or_else(|| AppError::Internal(format!("Rol de miembro desconocido: {}", rol)).into_response())?;
+        let perfil = perfil_repo
+            .find_by_id(id_perfil)
+            .await
+            .map_err(|e| AppError::from(e).into_response())?
+            .ok_or_else(|| error(StatusCode::FORBIDDEN, "PERFIL_SIN_ACCESO", "No es miembro del perfil indicado"))?;
+
+        Ok(CurrentPerfil(perfil, rol))
+    }
+}
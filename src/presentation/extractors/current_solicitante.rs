@@ -0,0 +1,122 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::application::policies::{RolMiembro, Solicitante};
+use crate::domain::auth::{AuthenticatedPrincipal, AuthenticatedUser};
+use crate::domain::entities::User;
+use crate::domain::repositories::{PerfilClienteRepository, PerfilMiembroRepository, TransportistaRepository, UserRepository};
+use crate::shared::AppError;
+
+/// Solicitante de la operación (rol, perfil, membresías y transportista) para las políticas de acceso
+///
+/// Reutiliza el `User` que deja `require_role` en las extensiones si existe;
+/// si no, lo consulta en `public.users`. Los repositorios se obtienen de las
+/// extensiones del router.
+#[derive(Debug, Clone)]
+pub struct CurrentSolicitante(pub Solicitante);
+
+fn error(status: StatusCode, code: &str, message: &str) -> Response {
+    (
+        status,
+        Json(json!({
+            "error": {
+                "code": code,
+                "message": message
+            }
+        })),
+    ).into_response()
+}
+
+fn repositorio_no_configurado() -> Response {
+    AppError::Internal("Repositorio no configurado para el extractor".to_string()).into_response()
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for CurrentSolicitante
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(AuthenticatedPrincipal::ApiKey(api_key)) = parts.extensions.get::<AuthenticatedPrincipal>() {
+            return Ok(CurrentSolicitante(Solicitante::api_key(api_key)));
+        }
+
+        let auth_user = parts
+            .extensions
+            .get::<AuthenticatedUser>()
+            .cloned()
+            .ok_or_else(|| error(StatusCode::UNAUTHORIZED, "TOKEN_MISSING", "Authorization header is required"))?;
+
+        let user = match parts.extensions.get::<User>().cloned() {
+            Some(user) => user,
+            None => {
+                let user_repo = parts
+                    .extensions
+                    .get::<Arc<dyn UserRepository>>()
+                    .cloned()
+                    .ok_or_else(repositorio_no_configurado)?;
+                user_repo
+                    .find_by_id(auth_user.id)
+                    .await
+                    .map_err(|e| AppError::from(e).into_response())?
+                    .ok_or_else(|| {
+                        error(StatusCode::FORBIDDEN, "USER_NOT_REGISTERED", "El usuario no está registrado en el sistema")
+                    })?
+            }
+        };
+
+        if !user.activo {
+            return Err(error(StatusCode::FORBIDDEN, "USER_INACTIVE", "La cuenta del usuario está suspendida"));
+        }
+
+        let perfil_repo = parts
+            .extensions
+            .get::<Arc<dyn PerfilClienteRepository>>()
+            .cloned()
+            .ok_or_else(repositorio_no_configurado)?;
+        let miembro_repo = parts
+            .extensions
+            .get::<Arc<dyn PerfilMiembroRepository>>()
+            .cloned()
+            .ok_or_else(repositorio_no_configurado)?;
+        let transportista_repo = parts
+            .extensions
+            .get::<Arc<dyn TransportistaRepository>>()
+            .cloned()
+            .ok_or_else(repositorio_no_configurado)?;
+
+        let perfil = perfil_repo
+            .find_by_usuario(user.id)
+            .await
+            .map_err(|e| AppError::from(e).into_response())?;
+        let membresias = miembro_repo
+            .find_by_usuario(user.id)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        let transportista = transportista_repo
+            .find_by_usuario(user.id)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        Ok(CurrentSolicitante(Solicitante {
+            id_usuario: user.id,
+            rol: user.rol,
+            id_perfil: perfil.map(|p| p.id_perfil),
+            membresias: membresias
+                .into_iter()
+                .filter_map(|m| RolMiembro::parse(&m.rol).map(|rol| (m.id_perfil, rol)))
+                .collect(),
+            id_transportista: transportista.map(|t| t.id_transportista),
+            scopes: None,
+        }))
+    }
+}
@@ -0,0 +1,5 @@
+pub mod current_perfil;
+pub mod current_solicitante;
+
+pub use current_perfil::CurrentPerfil;
+pub use current_solicitante::CurrentSolicitante;
@@ -0,0 +1,86 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{ApiKeyCreatedResponseDTO, ApiKeysListResponseDTO, CreateApiKeyDTO};
+use crate::application::services::ApiKeyService;
+use crate::domain::auth::AuthenticatedUser;
+use crate::shared::error::AppResult;
+
+// ============================================================================
+// HANDLERS - ADMIN
+// ============================================================================
+
+/// Lista las API keys de servicio (admin)
+#[utoipa::path(
+    get,
+    path = "/api/admin/api-keys",
+    tag = "api-keys-admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Lista de API keys (sin secretos)", body = ApiKeysListResponseDTO),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador")
+    )
+)]
+pub async fn list_api_keys(
+    Extension(_user): Extension<AuthenticatedUser>,
+    State(service): State<Arc<ApiKeyService>>,
+) -> AppResult<Json<ApiKeysListResponseDTO>> {
+    let api_keys = service.list().await?;
+    Ok(Json(api_keys))
+}
+
+/// Crea una API key de servicio (admin)
+///
+/// El secreto se devuelve solo en esta respuesta; después no se puede recuperar.
+#[utoipa::path(
+    post,
+    path = "/api/admin/api-keys",
+    tag = "api-keys-admin",
+    security(("bearer_auth" = [])),
+    request_body = CreateApiKeyDTO,
+    responses(
+        (status = 201, description = "API key creada", body = ApiKeyCreatedResponseDTO),
+        (status = 400, description = "Nombre, scopes o expiración inválidos"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador")
+    )
+)]
+pub async fn create_api_key(
+    Extension(admin): Extension<AuthenticatedUser>,
+    State(service): State<Arc<ApiKeyService>>,
+    Json(dto): Json<CreateApiKeyDTO>,
+) -> AppResult<(StatusCode, Json<ApiKeyCreatedResponseDTO>)> {
+    let created = service.create(dto, admin.id).await?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// Revoca una API key de servicio (admin)
+#[utoipa::path(
+    delete,
+    path = "/api/admin/api-keys/{id}",
+    tag = "api-keys-admin",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID de la API key")
+    ),
+    responses(
+        (status = 204, description = "API key revocada"),
+        (status = 404, description = "API key no encontrada"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador")
+    )
+)]
+pub async fn revoke_api_key(
+    Extension(_user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    State(service): State<Arc<ApiKeyService>>,
+) -> AppResult<StatusCode> {
+    service.revoke(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
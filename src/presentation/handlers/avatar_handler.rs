@@ -0,0 +1,114 @@
+use axum::{
+    extract::{Multipart, Path, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::UserResponseDTO;
+use crate::application::services::{AvatarService, AVATAR_MAX_BYTES};
+use crate::domain::auth::AuthenticatedUser;
+use crate::shared::error::{AppError, AppResult};
+
+/// Nombre del campo multipart con la imagen
+const AVATAR_FIELD: &str = "file";
+
+/// Sube la foto de perfil del usuario autenticado
+///
+/// Multipart con el campo `file`: PNG, JPEG o WebP, hasta 2 MB y entre 32x32 y 2048x2048 px.
+#[utoipa::path(
+    put,
+    path = "/auth/me/avatar",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    request_body(content = String, content_type = "multipart/form-data", description = "Campo `file` con la imagen"),
+    responses(
+        (status = 200, description = "Foto actualizada; `foto_perfil` contiene la clave del archivo", body = UserResponseDTO),
+        (status = 400, description = "Falta el campo file, formato, tamaño o dimensiones inválidos"),
+        (status = 401, description = "No autenticado"),
+        (status = 413, description = "El request supera el tamaño máximo")
+    )
+)]
+pub async fn upload_my_avatar(
+    Extension(user): Extension<AuthenticatedUser>,
+    State(service): State<Arc<AvatarService>>,
+    mut multipart: Multipart,
+) -> AppResult<Json<UserResponseDTO>> {
+    let bad_multipart = |e: axum::extract::multipart::MultipartError| {
+        AppError::BadRequest(format!("Multipart inválido: {}", e))
+    };
+
+    while let Some(mut field) = multipart.next_field().await.map_err(bad_multipart)? {
+        if field.name() != Some(AVATAR_FIELD) {
+            continue;
+        }
+
+        let content_type = field.content_type().map(str::to_string);
+        let mut data = Vec::new();
+        while let Some(chunk) = field.chunk().await.map_err(bad_multipart)? {
+            if data.len() + chunk.len() > AVATAR_MAX_BYTES {
+                return Err(AppError::BadRequest(format!(
+                    "El archivo supera el máximo de {} bytes",
+                    AVATAR_MAX_BYTES
+                )));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        let updated = service.upload(user.id, content_type.as_deref(), data).await?;
+        return Ok(Json(updated));
+    }
+
+    Err(AppError::BadRequest(format!("Falta el campo '{}' con la imagen", AVATAR_FIELD)))
+}
+
+/// Quita la foto de perfil del usuario autenticado
+#[utoipa::path(
+    delete,
+    path = "/auth/me/avatar",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Foto eliminada", body = UserResponseDTO),
+        (status = 401, description = "No autenticado")
+    )
+)]
+pub async fn delete_my_avatar(
+    Extension(user): Extension<AuthenticatedUser>,
+    State(service): State<Arc<AvatarService>>,
+) -> AppResult<Json<UserResponseDTO>> {
+    let updated = service.remove(user.id).await?;
+    Ok(Json(updated))
+}
+
+/// Descarga la foto de perfil de un usuario
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/avatar",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID del usuario")
+    ),
+    responses(
+        (status = 200, description = "Imagen (PNG, JPEG o WebP)", content_type = "image/*"),
+        (status = 404, description = "Usuario sin foto de perfil almacenada"),
+        (status = 401, description = "No autenticado")
+    )
+)]
+pub async fn get_user_avatar(
+    Extension(_user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    State(service): State<Arc<AvatarService>>,
+) -> AppResult<Response> {
+    let blob = service.download(id).await?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, blob.content_type),
+            (header::CACHE_CONTROL, "private, max-age=300".to_string()),
+        ],
+        blob.data,
+    ).into_response())
+}
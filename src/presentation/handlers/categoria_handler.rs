@@ -0,0 +1,131 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    CategoriaResponseDTO, CategoriasArbolResponseDTO, CategoriasListResponseDTO, CreateCategoriaDTO,
+    UpdateCategoriaDTO,
+};
+use crate::application::services::CategoriaService;
+use crate::shared::error::AppResult;
+
+// ============================================================================
+// HANDLERS PÚBLICOS
+// ============================================================================
+
+/// Árbol de categorías activas
+///
+/// `productos` cuenta los productos activos de la categoría y sus subcategorías.
+#[utoipa::path(
+    get,
+    path = "/api/categorias",
+    tag = "categorias",
+    responses(
+        (status = 200, description = "Árbol de categorías", body = CategoriasArbolResponseDTO)
+    )
+)]
+pub async fn get_arbol_categorias(
+    State(service): State<Arc<CategoriaService>>,
+) -> AppResult<Json<CategoriasArbolResponseDTO>> {
+    let arbol = service.get_arbol().await?;
+    Ok(Json(arbol))
+}
+
+// ============================================================================
+// HANDLERS ADMIN (requieren autenticación)
+// ============================================================================
+
+/// Lista todas las categorías (incluye inactivas) - Admin
+#[utoipa::path(
+    get,
+    path = "/api/admin/categorias",
+    tag = "categorias-admin",
+    security(("bearer_auth" = []), ("api_key" = [])),
+    responses(
+        (status = 200, description = "Lista plana de categorías", body = CategoriasListResponseDTO),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "Sin permisos")
+    )
+)]
+pub async fn list_categorias(
+    State(service): State<Arc<CategoriaService>>,
+) -> AppResult<Json<CategoriasListResponseDTO>> {
+    let categorias = service.list_categorias().await?;
+    Ok(Json(categorias))
+}
+
+/// Crea una categoría - Admin
+#[utoipa::path(
+    post,
+    path = "/api/admin/categorias",
+    tag = "categorias-admin",
+    security(("bearer_auth" = []), ("api_key" = [])),
+    request_body = CreateCategoriaDTO,
+    responses(
+        (status = 201, description = "Categoría creada", body = CategoriaResponseDTO),
+        (status = 400, description = "Nombre, slug o padre inválido"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "Sin permisos")
+    )
+)]
+pub async fn create_categoria(
+    State(service): State<Arc<CategoriaService>>,
+    Json(dto): Json<CreateCategoriaDTO>,
+) -> AppResult<(StatusCode, Json<CategoriaResponseDTO>)> {
+    let categoria = service.create_categoria(dto).await?;
+    Ok((StatusCode::CREATED, Json(categoria)))
+}
+
+/// Actualiza una categoría (nombre, slug, padre, orden, estado) - Admin
+#[utoipa::path(
+    put,
+    path = "/api/admin/categorias/{id}",
+    tag = "categorias-admin",
+    security(("bearer_auth" = []), ("api_key" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID de la categoría")
+    ),
+    request_body = UpdateCategoriaDTO,
+    responses(
+        (status = 200, description = "Categoría actualizada", body = CategoriaResponseDTO),
+        (status = 400, description = "Datos inválidos o el padre es una subcategoría"),
+        (status = 404, description = "Categoría no encontrada"),
+        (status = 401, description = "No autenticado")
+    )
+)]
+pub async fn update_categoria(
+    Path(id): Path<Uuid>,
+    State(service): State<Arc<CategoriaService>>,
+    Json(dto): Json<UpdateCategoriaDTO>,
+) -> AppResult<Json<CategoriaResponseDTO>> {
+    let categoria = service.update_categoria(id, dto).await?;
+    Ok(Json(categoria))
+}
+
+/// Elimina una categoría sin subcategorías ni productos - Admin
+#[utoipa::path(
+    delete,
+    path = "/api/admin/categorias/{id}",
+    tag = "categorias-admin",
+    security(("bearer_auth" = []), ("api_key" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID de la categoría")
+    ),
+    responses(
+        (status = 204, description = "Categoría eliminada"),
+        (status = 400, description = "Tiene subcategorías o productos"),
+        (status = 404, description = "Categoría no encontrada"),
+        (status = 401, description = "No autenticado")
+    )
+)]
+pub async fn delete_categoria(
+    Path(id): Path<Uuid>,
+    State(service): State<Arc<CategoriaService>>,
+) -> AppResult<StatusCode> {
+    service.delete_categoria(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
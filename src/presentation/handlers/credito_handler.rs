@@ -0,0 +1,121 @@
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    ConfigurarCreditoDTO, CreditoAlertasQueryDTO, CreditoAlertasResponseDTO, EstadoCreditoResponseDTO,
+};
+use crate::application::services::CreditoService;
+use crate::domain::auth::AuthenticatedUser;
+use crate::presentation::extractors::CurrentPerfil;
+use crate::shared::error::AppResult;
+
+// ============================================================================
+// HANDLERS - CLIENTE
+// ============================================================================
+
+/// Situación de crédito del perfil actual
+#[utoipa::path(
+    get,
+    path = "/api/perfil/credito",
+    tag = "perfiles",
+    security(("bearer_auth" = [])),
+    params(
+        ("X-Perfil-Id" = Option<Uuid>, Header, description = "Perfil de empresa con el que se opera")
+    ),
+    responses(
+        (status = 200, description = "Límite, saldo y crédito disponible", body = EstadoCreditoResponseDTO),
+        (status = 401, description = "No autenticado"),
+        (status = 409, description = "Perfil de cliente requerido")
+    )
+)]
+pub async fn get_my_credito(
+    CurrentPerfil(perfil, _): CurrentPerfil,
+    State(service): State<Arc<CreditoService>>,
+) -> AppResult<Json<EstadoCreditoResponseDTO>> {
+    let estado = service.get_estado(perfil.id_perfil).await?;
+    Ok(Json(estado))
+}
+
+// ============================================================================
+// HANDLERS - ADMIN
+// ============================================================================
+
+/// Lista los perfiles cerca o sobre su límite de crédito (admin)
+#[utoipa::path(
+    get,
+    path = "/api/admin/perfiles/credito",
+    tag = "perfiles-admin",
+    security(("bearer_auth" = [])),
+    params(
+        ("umbral" = Option<f64>, Query, description = "Fracción del límite a partir de la cual se lista (default: 0.8)")
+    ),
+    responses(
+        (status = 200, description = "Perfiles de mayor a menor uso del límite", body = CreditoAlertasResponseDTO),
+        (status = 400, description = "Umbral inválido"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador")
+    )
+)]
+pub async fn list_credito_alertas(
+    State(service): State<Arc<CreditoService>>,
+    Query(query): Query<CreditoAlertasQueryDTO>,
+) -> AppResult<Json<CreditoAlertasResponseDTO>> {
+    let alertas = service.list_alertas(query).await?;
+    Ok(Json(alertas))
+}
+
+/// Situación de crédito de un perfil (admin)
+#[utoipa::path(
+    get,
+    path = "/api/admin/perfiles/{id}/credito",
+    tag = "perfiles-admin",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID del perfil")
+    ),
+    responses(
+        (status = 200, description = "Límite, saldo y crédito disponible", body = EstadoCreditoResponseDTO),
+        (status = 404, description = "Perfil no encontrado"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador")
+    )
+)]
+pub async fn get_credito_perfil(
+    Path(id): Path<Uuid>,
+    State(service): State<Arc<CreditoService>>,
+) -> AppResult<Json<EstadoCreditoResponseDTO>> {
+    let estado = service.get_estado(id).await?;
+    Ok(Json(estado))
+}
+
+/// Configura límite y plazo de crédito de un perfil (admin)
+#[utoipa::path(
+    put,
+    path = "/api/admin/perfiles/{id}/credito",
+    tag = "perfiles-admin",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID del perfil")
+    ),
+    request_body = ConfigurarCreditoDTO,
+    responses(
+        (status = 200, description = "Crédito configurado", body = EstadoCreditoResponseDTO),
+        (status = 400, description = "Límite o plazo inválido"),
+        (status = 404, description = "Perfil no encontrado"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador o la operación se hace suplantando a un usuario")
+    )
+)]
+pub async fn configurar_credito_perfil(
+    Extension(admin): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    State(service): State<Arc<CreditoService>>,
+    Json(dto): Json<ConfigurarCreditoDTO>,
+) -> AppResult<Json<EstadoCreditoResponseDTO>> {
+    let estado = service.configurar(id, admin.id, dto).await?;
+    Ok(Json(estado))
+}
@@ -0,0 +1,36 @@
+use axum::{extract::State, Json};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{DevTokenRequestDTO, DevTokenResponseDTO};
+use crate::infrastructure::auth::{DevTokenIssuer, DEV_TOKEN_DEFAULT_TTL};
+use crate::shared::error::AppResult;
+
+// ============================================================================
+// HANDLERS - DESARROLLO (solo con ENVIRONMENT=development y DEV_TOKENS_ENABLED=true)
+// ============================================================================
+
+/// Emite un JWT HS256 local para el usuario indicado
+///
+/// `POST /dev/token`. No se publica en Swagger.
+pub async fn issue_dev_token(
+    State(issuer): State<Arc<DevTokenIssuer>>,
+    Json(dto): Json<DevTokenRequestDTO>,
+) -> AppResult<Json<DevTokenResponseDTO>> {
+    let token = issuer
+        .issue(
+            dto.user_id.unwrap_or_else(Uuid::new_v4),
+            dto.email.as_deref(),
+            &dto.rol,
+            dto.expires_in.unwrap_or(DEV_TOKEN_DEFAULT_TTL),
+        )
+        .await?;
+
+    Ok(Json(DevTokenResponseDTO {
+        access_token: token.access_token,
+        token_type: "bearer".to_string(),
+        expires_in: token.expires_in,
+        user_id: token.user_id,
+        rol: token.rol,
+    }))
+}
@@ -1,7 +1,7 @@
 use axum::{
     extract::{Path, State},
     http::StatusCode,
-    Extension, Json,
+    Json,
 };
 use std::sync::Arc;
 use uuid::Uuid;
@@ -10,10 +10,10 @@ use crate::application::dto::{
     CreateDireccionDTO, UpdateDireccionDTO, CreateAlmacenDTO,
     DireccionResponseDTO, DireccionesListResponseDTO,
 };
+use crate::application::policies::{PerfilAccion, PerfilPolicy};
 use crate::application::services::DireccionService;
-use crate::domain::auth::AuthenticatedUser;
-use crate::domain::repositories::PerfilClienteRepository;
-use crate::shared::error::{AppError, AppResult};
+use crate::presentation::extractors::CurrentPerfil;
+use crate::shared::error::AppResult;
 
 // ============================================================================
 // HANDLERS - CLIENTE
@@ -27,15 +27,15 @@ use crate::shared::error::{AppError, AppResult};
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "Lista de direcciones", body = DireccionesListResponseDTO),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 409, description = "Perfil de cliente requerido")
     )
 )]
 pub async fn list_my_direcciones(
-    Extension(user): Extension<AuthenticatedUser>,
-    State((service, perfil_repo)): State<(Arc<DireccionService>, Arc<dyn PerfilClienteRepository>)>,
+    CurrentPerfil(perfil, _): CurrentPerfil,
+    State(service): State<Arc<DireccionService>>,
 ) -> AppResult<Json<DireccionesListResponseDTO>> {
-    let id_perfil = get_perfil_id(&user, &perfil_repo).await?;
-    let direcciones = service.list_my_direcciones(id_perfil).await?;
+    let direcciones = service.list_my_direcciones(perfil.id_perfil).await?;
     Ok(Json(direcciones))
 }
 
@@ -52,16 +52,16 @@ pub async fn list_my_direcciones(
         (status = 200, description = "Dirección encontrada", body = DireccionResponseDTO),
         (status = 404, description = "Dirección no encontrada"),
         (status = 403, description = "Sin acceso a esta dirección"),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 409, description = "Perfil de cliente requerido")
     )
 )]
 pub async fn get_direccion(
     Path(id): Path<Uuid>,
-    Extension(user): Extension<AuthenticatedUser>,
-    State((service, perfil_repo)): State<(Arc<DireccionService>, Arc<dyn PerfilClienteRepository>)>,
+    CurrentPerfil(perfil, _): CurrentPerfil,
+    State(service): State<Arc<DireccionService>>,
 ) -> AppResult<Json<DireccionResponseDTO>> {
-    let id_perfil = get_perfil_id(&user, &perfil_repo).await?;
-    let direccion = service.get_direccion(id, id_perfil).await?;
+    let direccion = service.get_direccion(id, perfil.id_perfil).await?;
     Ok(Json(direccion))
 }
 
@@ -74,15 +74,15 @@ pub async fn get_direccion(
     responses(
         (status = 200, description = "Dirección predeterminada", body = DireccionResponseDTO),
         (status = 404, description = "No tiene dirección predeterminada"),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 409, description = "Perfil de cliente requerido")
     )
 )]
 pub async fn get_predeterminada(
-    Extension(user): Extension<AuthenticatedUser>,
-    State((service, perfil_repo)): State<(Arc<DireccionService>, Arc<dyn PerfilClienteRepository>)>,
+    CurrentPerfil(perfil, _): CurrentPerfil,
+    State(service): State<Arc<DireccionService>>,
 ) -> AppResult<Json<DireccionResponseDTO>> {
-    let id_perfil = get_perfil_id(&user, &perfil_repo).await?;
-    let direccion = service.get_predeterminada(id_perfil).await?;
+    let direccion = service.get_predeterminada(perfil.id_perfil).await?;
     Ok(Json(direccion))
 }
 
@@ -96,16 +96,17 @@ pub async fn get_predeterminada(
     responses(
         (status = 201, description = "Dirección creada", body = DireccionResponseDTO),
         (status = 400, description = "Datos inválidos o límite alcanzado"),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 409, description = "Perfil de cliente requerido")
     )
 )]
 pub async fn create_direccion(
-    Extension(user): Extension<AuthenticatedUser>,
-    State((service, perfil_repo)): State<(Arc<DireccionService>, Arc<dyn PerfilClienteRepository>)>,
+    CurrentPerfil(perfil, rol): CurrentPerfil,
+    State(service): State<Arc<DireccionService>>,
     Json(dto): Json<CreateDireccionDTO>,
 ) -> AppResult<(StatusCode, Json<DireccionResponseDTO>)> {
-    let id_perfil = get_perfil_id(&user, &perfil_repo).await?;
-    let direccion = service.create_direccion(id_perfil, dto).await?;
+    PerfilPolicy::autorizar(rol, PerfilAccion::EditarDirecciones)?;
+    let direccion = service.create_direccion(perfil.id_perfil, dto).await?;
     Ok((StatusCode::CREATED, Json(direccion)))
 }
 
@@ -124,17 +125,18 @@ pub async fn create_direccion(
         (status = 400, description = "Datos inválidos"),
         (status = 403, description = "Sin acceso"),
         (status = 404, description = "No encontrada"),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 409, description = "Perfil de cliente requerido")
     )
 )]
 pub async fn update_direccion(
     Path(id): Path<Uuid>,
-    Extension(user): Extension<AuthenticatedUser>,
-    State((service, perfil_repo)): State<(Arc<DireccionService>, Arc<dyn PerfilClienteRepository>)>,
+    CurrentPerfil(perfil, rol): CurrentPerfil,
+    State(service): State<Arc<DireccionService>>,
     Json(dto): Json<UpdateDireccionDTO>,
 ) -> AppResult<Json<DireccionResponseDTO>> {
-    let id_perfil = get_perfil_id(&user, &perfil_repo).await?;
-    let direccion = service.update_direccion(id, id_perfil, dto).await?;
+    PerfilPolicy::autorizar(rol, PerfilAccion::EditarDirecciones)?;
+    let direccion = service.update_direccion(id, perfil.id_perfil, dto).await?;
     Ok(Json(direccion))
 }
 
@@ -152,16 +154,17 @@ pub async fn update_direccion(
         (status = 400, description = "No se puede establecer una dirección inactiva"),
         (status = 403, description = "Sin acceso"),
         (status = 404, description = "No encontrada"),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 409, description = "Perfil de cliente requerido")
     )
 )]
 pub async fn set_predeterminada(
     Path(id): Path<Uuid>,
-    Extension(user): Extension<AuthenticatedUser>,
-    State((service, perfil_repo)): State<(Arc<DireccionService>, Arc<dyn PerfilClienteRepository>)>,
+    CurrentPerfil(perfil, rol): CurrentPerfil,
+    State(service): State<Arc<DireccionService>>,
 ) -> AppResult<Json<DireccionResponseDTO>> {
-    let id_perfil = get_perfil_id(&user, &perfil_repo).await?;
-    let direccion = service.set_predeterminada(id, id_perfil).await?;
+    PerfilPolicy::autorizar(rol, PerfilAccion::EditarDirecciones)?;
+    let direccion = service.set_predeterminada(id, perfil.id_perfil).await?;
     Ok(Json(direccion))
 }
 
@@ -178,16 +181,17 @@ pub async fn set_predeterminada(
         (status = 204, description = "Dirección desactivada"),
         (status = 403, description = "Sin acceso"),
         (status = 404, description = "No encontrada"),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 409, description = "Perfil de cliente requerido")
     )
 )]
 pub async fn deactivate_direccion(
     Path(id): Path<Uuid>,
-    Extension(user): Extension<AuthenticatedUser>,
-    State((service, perfil_repo)): State<(Arc<DireccionService>, Arc<dyn PerfilClienteRepository>)>,
+    CurrentPerfil(perfil, rol): CurrentPerfil,
+    State(service): State<Arc<DireccionService>>,
 ) -> AppResult<StatusCode> {
-    let id_perfil = get_perfil_id(&user, &perfil_repo).await?;
-    service.deactivate_direccion(id, id_perfil).await?;
+    PerfilPolicy::autorizar(rol, PerfilAccion::EditarDirecciones)?;
+    service.deactivate_direccion(id, perfil.id_perfil).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -204,16 +208,17 @@ pub async fn deactivate_direccion(
         (status = 200, description = "Dirección reactivada", body = DireccionResponseDTO),
         (status = 403, description = "Sin acceso"),
         (status = 404, description = "No encontrada"),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 409, description = "Perfil de cliente requerido")
     )
 )]
 pub async fn activate_direccion(
     Path(id): Path<Uuid>,
-    Extension(user): Extension<AuthenticatedUser>,
-    State((service, perfil_repo)): State<(Arc<DireccionService>, Arc<dyn PerfilClienteRepository>)>,
+    CurrentPerfil(perfil, rol): CurrentPerfil,
+    State(service): State<Arc<DireccionService>>,
 ) -> AppResult<Json<DireccionResponseDTO>> {
-    let id_perfil = get_perfil_id(&user, &perfil_repo).await?;
-    let direccion = service.activate_direccion(id, id_perfil).await?;
+    PerfilPolicy::autorizar(rol, PerfilAccion::EditarDirecciones)?;
+    let direccion = service.activate_direccion(id, perfil.id_perfil).await?;
     Ok(Json(direccion))
 }
 
@@ -230,16 +235,17 @@ pub async fn activate_direccion(
         (status = 204, description = "Dirección eliminada permanentemente"),
         (status = 403, description = "Sin acceso"),
         (status = 404, description = "No encontrada"),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 409, description = "Perfil de cliente requerido")
     )
 )]
 pub async fn delete_direccion_permanente(
     Path(id): Path<Uuid>,
-    Extension(user): Extension<AuthenticatedUser>,
-    State((service, perfil_repo)): State<(Arc<DireccionService>, Arc<dyn PerfilClienteRepository>)>,
+    CurrentPerfil(perfil, rol): CurrentPerfil,
+    State(service): State<Arc<DireccionService>>,
 ) -> AppResult<StatusCode> {
-    let id_perfil = get_perfil_id(&user, &perfil_repo).await?;
-    service.delete_direccion(id, id_perfil).await?;
+    PerfilPolicy::autorizar(rol, PerfilAccion::EliminarDirecciones)?;
+    service.delete_direccion(id, perfil.id_perfil).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -379,20 +385,3 @@ pub async fn delete_almacen_permanente(
     service.delete_almacen(id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
-
-// ============================================================================
-// HELPERS
-// ============================================================================
-
-/// Obtiene el id_perfil del usuario autenticado
-async fn get_perfil_id(
-    user: &AuthenticatedUser,
-    perfil_repo: &Arc<dyn PerfilClienteRepository>,
-) -> AppResult<Uuid> {
-    let perfil = perfil_repo
-        .find_by_usuario(user.id)
-        .await?
-        .ok_or_else(|| AppError::NotFound("Debe crear un perfil primero".into()))?;
-    
-    Ok(perfil.id_perfil)
-}
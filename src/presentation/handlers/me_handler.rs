@@ -0,0 +1,34 @@
+use axum::{extract::State, Extension, Json};
+use std::sync::Arc;
+
+use crate::application::dto::MeResponseDTO;
+use crate::application::services::MeService;
+use crate::domain::auth::AuthenticatedUser;
+use crate::shared::error::AppResult;
+
+// ============================================================================
+// HANDLERS
+// ============================================================================
+
+/// Estado inicial del usuario autenticado: usuario, perfil, dirección predeterminada y pedidos
+///
+/// Si el usuario aún no tiene perfil responde 200 con `perfil: null` y
+/// `requiere_onboarding: true` (clientes).
+#[utoipa::path(
+    get,
+    path = "/api/me",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Resumen del usuario autenticado", body = MeResponseDTO),
+        (status = 404, description = "Usuario no encontrado en public.users"),
+        (status = 401, description = "No autenticado")
+    )
+)]
+pub async fn get_me(
+    Extension(user): Extension<AuthenticatedUser>,
+    State(service): State<Arc<MeService>>,
+) -> AppResult<Json<MeResponseDTO>> {
+    let me = service.get_me(user.id).await?;
+    Ok(Json(me))
+}
@@ -4,11 +4,21 @@ pub mod pedido_handler;
 pub mod auth_handler;
 pub mod perfil_cliente_handler;
 pub mod direccion_handler;
+pub mod dev_handler;
+pub mod api_key_handler;
+pub mod privacy_handler;
+pub mod avatar_handler;
+pub mod me_handler;
+pub mod perfil_miembro_handler;
+pub mod credito_handler;
+pub mod perfil_fusion_handler;
+pub mod categoria_handler;
 
 pub use auth_handler::{get_current_user, CurrentUserResponse, __path_get_current_user};
+pub use dev_handler::issue_dev_token;
 pub use user_handler::{
-    list_users, get_user, create_user, update_user, update_user_role, update_user_status, delete_user,
-    __path_list_users, __path_get_user, __path_create_user, __path_update_user, __path_update_user_role, __path_update_user_status, __path_delete_user,
+    list_users, import_users, export_users, get_user, create_user, update_user, update_user_role, update_user_status, delete_user, revoke_user_sessions,
+    __path_list_users, __path_import_users, __path_export_users, __path_get_user, __path_create_user, __path_update_user, __path_update_user_role, __path_update_user_status, __path_delete_user, __path_revoke_user_sessions,
 };
 pub use pedido_handler::{
     list_pedidos, get_pedido, create_pedido, 
@@ -25,11 +35,13 @@ pub use perfil_cliente_handler::{
 pub use producto_handler::{
     list_productos, get_producto, search_productos, get_by_categoria, get_by_sku,
     list_all_productos, create_producto, update_producto, update_stock, 
+    list_movimientos_stock, conciliar_stock, transferir_stock,
     update_estado_producto, delete_producto,
     __path_list_productos, __path_get_producto, __path_search_productos, 
     __path_get_by_categoria, __path_get_by_sku,
     __path_list_all_productos, __path_create_producto, __path_update_producto,
-    __path_update_stock, __path_update_estado_producto, __path_delete_producto,
+    __path_update_stock, __path_list_movimientos_stock, __path_conciliar_stock, __path_transferir_stock,
+    __path_update_estado_producto, __path_delete_producto,
 };
 pub use direccion_handler::{
     list_my_direcciones, get_direccion, get_predeterminada, create_direccion,
@@ -42,3 +54,35 @@ pub use direccion_handler::{
     __path_list_all_almacenes, __path_create_almacen, __path_deactivate_almacen, __path_activate_almacen,
     __path_delete_almacen_permanente,
 };
+pub use api_key_handler::{
+    list_api_keys, create_api_key, revoke_api_key,
+    __path_list_api_keys, __path_create_api_key, __path_revoke_api_key,
+};
+pub use privacy_handler::{
+    export_my_data, export_user_data, anonymize_user,
+    __path_export_my_data, __path_export_user_data, __path_anonymize_user,
+};
+pub use avatar_handler::{
+    upload_my_avatar, delete_my_avatar, get_user_avatar,
+    __path_upload_my_avatar, __path_delete_my_avatar, __path_get_user_avatar,
+};
+pub use me_handler::{get_me, __path_get_me};
+pub use perfil_miembro_handler::{
+    list_miembros, add_miembro, update_miembro_rol, remove_miembro, list_perfiles_disponibles,
+    __path_list_miembros, __path_add_miembro, __path_update_miembro_rol, __path_remove_miembro,
+    __path_list_perfiles_disponibles,
+};
+pub use credito_handler::{
+    get_my_credito, list_credito_alertas, get_credito_perfil, configurar_credito_perfil,
+    __path_get_my_credito, __path_list_credito_alertas, __path_get_credito_perfil,
+    __path_configurar_credito_perfil,
+};
+pub use perfil_fusion_handler::{
+    list_perfiles_duplicados, fusionar_perfiles, list_perfil_fusiones,
+    __path_list_perfiles_duplicados, __path_fusionar_perfiles, __path_list_perfil_fusiones,
+};
+pub use categoria_handler::{
+    get_arbol_categorias, list_categorias, create_categoria, update_categoria, delete_categoria,
+    __path_get_arbol_categorias, __path_list_categorias, __path_create_categoria,
+    __path_update_categoria, __path_delete_categoria,
+};
@@ -1,7 +1,7 @@
 use axum::{
-    extract::{Extension, Path, State},
+    extract::{Path, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
 use std::sync::Arc;
 use uuid::Uuid;
@@ -10,8 +10,10 @@ use crate::application::dto::{
     CreatePedidoDTO, PedidoResponseDTO, PedidosListResponseDTO,
     UpdateEstadoPedidoDTO, AsignarTransportistaDTO,
 };
+use crate::application::policies::{PerfilAccion, PerfilPolicy};
 use crate::application::services::PedidoService;
 use crate::domain::auth::AuthenticatedUser;
+use crate::presentation::extractors::{CurrentPerfil, CurrentSolicitante};
 use crate::shared::AppResult;
 
 /// Listar pedidos del usuario autenticado
@@ -22,16 +24,15 @@ use crate::shared::AppResult;
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "Lista de pedidos", body = PedidosListResponseDTO),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 409, description = "Perfil de cliente requerido")
     )
 )]
 pub async fn list_pedidos(
-    Extension(user): Extension<AuthenticatedUser>,
+    CurrentPerfil(perfil, _): CurrentPerfil,
     State(service): State<Arc<PedidoService>>,
 ) -> AppResult<Json<PedidosListResponseDTO>> {
-    // TODO: Obtener id_perfil desde el user.id (requiere query a perfiles_cliente)
-    // Por ahora usamos el user.id directamente
-    let pedidos = service.list_by_user(user.id).await?;
+    let pedidos = service.list_by_user(perfil.id_perfil).await?;
     Ok(Json(pedidos))
 }
 
@@ -40,22 +41,22 @@ pub async fn list_pedidos(
     get,
     path = "/api/pedidos/{id}",
     tag = "pedidos",
-    security(("bearer_auth" = [])),
+    security(("bearer_auth" = []), ("api_key" = [])),
     params(
         ("id" = Uuid, Path, description = "ID del pedido")
     ),
     responses(
         (status = 200, description = "Pedido encontrado", body = PedidoResponseDTO),
-        (status = 404, description = "Pedido no encontrado"),
+        (status = 404, description = "Pedido no encontrado o sin acceso"),
         (status = 401, description = "No autenticado")
     )
 )]
 pub async fn get_pedido(
     Path(id): Path<Uuid>,
-    Extension(_user): Extension<AuthenticatedUser>,
+    CurrentSolicitante(solicitante): CurrentSolicitante,
     State(service): State<Arc<PedidoService>>,
 ) -> AppResult<Json<PedidoResponseDTO>> {
-    let pedido = service.get_by_id(id).await?;
+    let pedido = service.get_by_id(id, &solicitante).await?;
     Ok(Json(pedido))
 }
 
@@ -69,15 +70,20 @@ pub async fn get_pedido(
     responses(
         (status = 201, description = "Pedido creado", body = PedidoResponseDTO),
         (status = 400, description = "Datos inválidos"),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 402, description = "El pedido supera el crédito disponible del perfil"),
+        (status = 403, description = "Sin permiso sobre el perfil u omisión del límite sin ser admin"),
+        (status = 409, description = "Perfil de cliente requerido")
     )
 )]
 pub async fn create_pedido(
     Extension(user): Extension<AuthenticatedUser>,
+    CurrentPerfil(perfil, rol): CurrentPerfil,
     State(service): State<Arc<PedidoService>>,
     Json(dto): Json<CreatePedidoDTO>,
 ) -> AppResult<(StatusCode, Json<PedidoResponseDTO>)> {
-    let pedido = service.create(user.id, dto).await?;
+    PerfilPolicy::autorizar(rol, PerfilAccion::GestionarPedidos)?;
+    let pedido = service.create(perfil.id_perfil, dto, user.impersonated_by).await?;
     Ok((StatusCode::CREATED, Json(pedido)))
 }
 
@@ -86,7 +92,7 @@ pub async fn create_pedido(
     patch,
     path = "/api/pedidos/{id}/estado",
     tag = "pedidos",
-    security(("bearer_auth" = [])),
+    security(("bearer_auth" = []), ("api_key" = [])),
     params(
         ("id" = Uuid, Path, description = "ID del pedido")
     ),
@@ -95,16 +101,17 @@ pub async fn create_pedido(
         (status = 200, description = "Estado actualizado", body = PedidoResponseDTO),
         (status = 400, description = "Transición de estado inválida"),
         (status = 404, description = "Pedido no encontrado"),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "Requiere ser el transportista asignado o admin")
     )
 )]
 pub async fn update_estado_pedido(
     Path(id): Path<Uuid>,
-    Extension(_user): Extension<AuthenticatedUser>,
+    CurrentSolicitante(solicitante): CurrentSolicitante,
     State(service): State<Arc<PedidoService>>,
     Json(dto): Json<UpdateEstadoPedidoDTO>,
 ) -> AppResult<Json<PedidoResponseDTO>> {
-    let pedido = service.update_estado(id, dto).await?;
+    let pedido = service.update_estado(id, &solicitante, dto).await?;
     Ok(Json(pedido))
 }
 
@@ -122,16 +129,17 @@ pub async fn update_estado_pedido(
         (status = 200, description = "Transportista asignado", body = PedidoResponseDTO),
         (status = 400, description = "No se puede asignar en este estado"),
         (status = 404, description = "Pedido no encontrado"),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "Requiere rol admin")
     )
 )]
 pub async fn assign_transportista(
     Path(id): Path<Uuid>,
-    Extension(_user): Extension<AuthenticatedUser>,
+    CurrentSolicitante(solicitante): CurrentSolicitante,
     State(service): State<Arc<PedidoService>>,
     Json(dto): Json<AsignarTransportistaDTO>,
 ) -> AppResult<Json<PedidoResponseDTO>> {
-    let pedido = service.assign_transportista(id, dto).await?;
+    let pedido = service.assign_transportista(id, &solicitante, dto).await?;
     Ok(Json(pedido))
 }
 
@@ -148,14 +156,15 @@ pub async fn assign_transportista(
         (status = 204, description = "Pedido cancelado"),
         (status = 400, description = "No se puede cancelar"),
         (status = 404, description = "Pedido no encontrado"),
-        (status = 401, description = "No autenticado")
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "Solo el cliente propietario o un admin puede cancelar")
     )
 )]
 pub async fn cancel_pedido(
     Path(id): Path<Uuid>,
-    Extension(_user): Extension<AuthenticatedUser>,
+    CurrentSolicitante(solicitante): CurrentSolicitante,
     State(service): State<Arc<PedidoService>>,
 ) -> AppResult<StatusCode> {
-    service.cancel(id).await?;
+    service.cancel(id, &solicitante).await?;
     Ok(StatusCode::NO_CONTENT)
 }
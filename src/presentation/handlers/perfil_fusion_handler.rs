@@ -0,0 +1,89 @@
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    DuplicadosResponseDTO, FusionResponseDTO, FusionarPerfilDTO, FusionesListResponseDTO,
+};
+use crate::application::services::PerfilFusionService;
+use crate::domain::auth::AuthenticatedUser;
+use crate::shared::error::AppResult;
+
+// ============================================================================
+// HANDLERS - ADMIN
+// ============================================================================
+
+/// Lista grupos de perfiles posiblemente duplicados (admin)
+///
+/// Agrupa por documento, teléfono o email normalizados; un perfil puede aparecer
+/// en varios grupos.
+#[utoipa::path(
+    get,
+    path = "/api/admin/perfiles/duplicados",
+    tag = "perfiles-admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Grupos de posibles duplicados", body = DuplicadosResponseDTO),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador")
+    )
+)]
+pub async fn list_perfiles_duplicados(
+    State(service): State<Arc<PerfilFusionService>>,
+) -> AppResult<Json<DuplicadosResponseDTO>> {
+    let duplicados = service.find_duplicados().await?;
+    Ok(Json(duplicados))
+}
+
+/// Fusiona un perfil duplicado en el perfil indicado (admin)
+///
+/// Irreversible. Direcciones, pedidos y miembros del origen pasan al destino y
+/// el origen se elimina, dejando un registro en `perfil_fusiones`.
+#[utoipa::path(
+    post,
+    path = "/api/admin/perfiles/{id}/fusionar",
+    tag = "perfiles-admin",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID del perfil que se conserva")
+    ),
+    request_body = FusionarPerfilDTO,
+    responses(
+        (status = 200, description = "Perfiles fusionados", body = FusionResponseDTO),
+        (status = 400, description = "Origen igual al destino o rol inválido"),
+        (status = 404, description = "Perfil no encontrado"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador o la operación se hace suplantando a un usuario")
+    )
+)]
+pub async fn fusionar_perfiles(
+    Extension(admin): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    State(service): State<Arc<PerfilFusionService>>,
+    Json(dto): Json<FusionarPerfilDTO>,
+) -> AppResult<Json<FusionResponseDTO>> {
+    let result = service.fusionar(id, admin.id, dto).await?;
+    Ok(Json(result))
+}
+
+/// Lista las fusiones de perfiles realizadas (admin)
+#[utoipa::path(
+    get,
+    path = "/api/admin/perfiles/fusiones",
+    tag = "perfiles-admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Fusiones, de la más reciente a la más antigua", body = FusionesListResponseDTO),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador")
+    )
+)]
+pub async fn list_perfil_fusiones(
+    State(service): State<Arc<PerfilFusionService>>,
+) -> AppResult<Json<FusionesListResponseDTO>> {
+    let fusiones = service.list_fusiones().await?;
+    Ok(Json(fusiones))
+}
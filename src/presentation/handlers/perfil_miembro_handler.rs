@@ -0,0 +1,156 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    AddMiembroDTO, MiembroResponseDTO, MiembrosListResponseDTO, PerfilesAccesiblesResponseDTO,
+    UpdateMiembroRolDTO,
+};
+use crate::application::policies::{PerfilAccion, PerfilPolicy};
+use crate::application::services::PerfilMiembroService;
+use crate::domain::auth::AuthenticatedUser;
+use crate::presentation::extractors::CurrentPerfil;
+use crate::shared::error::AppResult;
+
+// ============================================================================
+// HANDLERS - CUENTAS DE EMPRESA
+// ============================================================================
+
+/// Lista el titular y los miembros del perfil actual
+#[utoipa::path(
+    get,
+    path = "/api/perfil/miembros",
+    tag = "perfiles",
+    security(("bearer_auth" = [])),
+    params(
+        ("X-Perfil-Id" = Option<Uuid>, Header, description = "Perfil de empresa con el que se opera")
+    ),
+    responses(
+        (status = 200, description = "Miembros del perfil", body = MiembrosListResponseDTO),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "Sin acceso al perfil indicado"),
+        (status = 409, description = "Perfil requerido o ambiguo")
+    )
+)]
+pub async fn list_miembros(
+    CurrentPerfil(perfil, _): CurrentPerfil,
+    State(service): State<Arc<PerfilMiembroService>>,
+) -> AppResult<Json<MiembrosListResponseDTO>> {
+    let miembros = service.list_miembros(&perfil).await?;
+    Ok(Json(miembros))
+}
+
+/// Agrega un usuario registrado como miembro del perfil (solo owner)
+#[utoipa::path(
+    post,
+    path = "/api/perfil/miembros",
+    tag = "perfiles",
+    security(("bearer_auth" = [])),
+    params(
+        ("X-Perfil-Id" = Option<Uuid>, Header, description = "Perfil de empresa con el que se opera")
+    ),
+    request_body = AddMiembroDTO,
+    responses(
+        (status = 201, description = "Miembro agregado", body = MiembroResponseDTO),
+        (status = 400, description = "Rol inválido, perfil sin RUC o el usuario ya es miembro"),
+        (status = 404, description = "No existe un usuario con ese email"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "El rol en el perfil no permite gestionar miembros")
+    )
+)]
+pub async fn add_miembro(
+    Extension(user): Extension<AuthenticatedUser>,
+    CurrentPerfil(perfil, rol): CurrentPerfil,
+    State(service): State<Arc<PerfilMiembroService>>,
+    Json(dto): Json<AddMiembroDTO>,
+) -> AppResult<(StatusCode, Json<MiembroResponseDTO>)> {
+    PerfilPolicy::autorizar(rol, PerfilAccion::GestionarMiembros)?;
+    let miembro = service.add_miembro(&perfil, user.id, dto).await?;
+    Ok((StatusCode::CREATED, Json(miembro)))
+}
+
+/// Cambia el rol de un miembro del perfil (solo owner)
+#[utoipa::path(
+    patch,
+    path = "/api/perfil/miembros/{id}",
+    tag = "perfiles",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID del usuario miembro"),
+        ("X-Perfil-Id" = Option<Uuid>, Header, description = "Perfil de empresa con el que se opera")
+    ),
+    request_body = UpdateMiembroRolDTO,
+    responses(
+        (status = 200, description = "Rol actualizado", body = MiembroResponseDTO),
+        (status = 400, description = "Rol inválido o el usuario es el titular"),
+        (status = 404, description = "El usuario no es miembro del perfil"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "El rol en el perfil no permite gestionar miembros")
+    )
+)]
+pub async fn update_miembro_rol(
+    Path(id): Path<Uuid>,
+    CurrentPerfil(perfil, rol): CurrentPerfil,
+    State(service): State<Arc<PerfilMiembroService>>,
+    Json(dto): Json<UpdateMiembroRolDTO>,
+) -> AppResult<Json<MiembroResponseDTO>> {
+    PerfilPolicy::autorizar(rol, PerfilAccion::GestionarMiembros)?;
+    let miembro = service.update_rol(&perfil, id, dto).await?;
+    Ok(Json(miembro))
+}
+
+/// Quita un miembro del perfil
+///
+/// Un owner puede quitar a cualquier miembro; cualquier miembro puede quitarse a sí mismo.
+#[utoipa::path(
+    delete,
+    path = "/api/perfil/miembros/{id}",
+    tag = "perfiles",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID del usuario miembro"),
+        ("X-Perfil-Id" = Option<Uuid>, Header, description = "Perfil de empresa con el que se opera")
+    ),
+    responses(
+        (status = 204, description = "Miembro quitado"),
+        (status = 400, description = "No se puede quitar al titular"),
+        (status = 404, description = "El usuario no es miembro del perfil"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "El rol en el perfil no permite gestionar miembros")
+    )
+)]
+pub async fn remove_miembro(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    CurrentPerfil(perfil, rol): CurrentPerfil,
+    State(service): State<Arc<PerfilMiembroService>>,
+) -> AppResult<StatusCode> {
+    if id != user.id {
+        PerfilPolicy::autorizar(rol, PerfilAccion::GestionarMiembros)?;
+    }
+    service.remove_miembro(&perfil, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Lista los perfiles con los que puede operar el usuario (propio y de empresa)
+#[utoipa::path(
+    get,
+    path = "/api/perfil/disponibles",
+    tag = "perfiles",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Perfiles accesibles y el rol en cada uno", body = PerfilesAccesiblesResponseDTO),
+        (status = 401, description = "No autenticado")
+    )
+)]
+pub async fn list_perfiles_disponibles(
+    Extension(user): Extension<AuthenticatedUser>,
+    State(service): State<Arc<PerfilMiembroService>>,
+) -> AppResult<Json<PerfilesAccesiblesResponseDTO>> {
+    let perfiles = service.list_perfiles_accesibles(user.id).await?;
+    Ok(Json(perfiles))
+}
@@ -0,0 +1,100 @@
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{AnonymizationResponseDTO, PersonalDataExportDTO};
+use crate::application::services::PrivacyService;
+use crate::domain::auth::AuthenticatedUser;
+use crate::shared::error::AppResult;
+
+/// Respuesta JSON descargable como archivo
+fn json_attachment(id_usuario: Uuid, export: PersonalDataExportDTO) -> Response {
+    let disposition = format!("attachment; filename=\"datos-{}.json\"", id_usuario);
+    ([(header::CONTENT_DISPOSITION, disposition)], Json(export)).into_response()
+}
+
+// ============================================================================
+// HANDLERS - USUARIO AUTENTICADO
+// ============================================================================
+
+/// Exporta los datos personales del usuario autenticado
+#[utoipa::path(
+    get,
+    path = "/auth/me/data-export",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Archivo JSON con usuario, perfil, direcciones y pedidos", body = PersonalDataExportDTO),
+        (status = 401, description = "No autenticado")
+    )
+)]
+pub async fn export_my_data(
+    Extension(user): Extension<AuthenticatedUser>,
+    State(service): State<Arc<PrivacyService>>,
+) -> AppResult<Response> {
+    let actor = user.impersonated_by.unwrap_or(user.id);
+    let export = service.export_user_data(user.id, actor).await?;
+    Ok(json_attachment(user.id, export))
+}
+
+// ============================================================================
+// HANDLERS - ADMIN
+// ============================================================================
+
+/// Exporta los datos personales de un usuario (admin)
+#[utoipa::path(
+    get,
+    path = "/api/admin/users/{id}/data-export",
+    tag = "usuarios-admin",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID del usuario")
+    ),
+    responses(
+        (status = 200, description = "Archivo JSON con usuario, perfil, direcciones y pedidos", body = PersonalDataExportDTO),
+        (status = 404, description = "Usuario no encontrado"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador")
+    )
+)]
+pub async fn export_user_data(
+    Extension(admin): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    State(service): State<Arc<PrivacyService>>,
+) -> AppResult<Response> {
+    let export = service.export_user_data(id, admin.id).await?;
+    Ok(json_attachment(id, export))
+}
+
+/// Anonimiza los datos personales de un usuario (derecho al olvido, admin)
+///
+/// Irreversible. Pedidos y facturas se conservan para contabilidad.
+#[utoipa::path(
+    post,
+    path = "/api/admin/users/{id}/anonymize",
+    tag = "usuarios-admin",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID del usuario")
+    ),
+    responses(
+        (status = 200, description = "Usuario anonimizado", body = AnonymizationResponseDTO),
+        (status = 400, description = "No puede anonimizar su propia cuenta"),
+        (status = 404, description = "Usuario no encontrado"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador o la operación se hace suplantando a un usuario")
+    )
+)]
+pub async fn anonymize_user(
+    Extension(admin): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    State(service): State<Arc<PrivacyService>>,
+) -> AppResult<Json<AnonymizationResponseDTO>> {
+    let result = service.anonymize_user(id, admin.id).await?;
+    Ok(Json(result))
+}
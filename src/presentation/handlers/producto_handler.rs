@@ -1,7 +1,7 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
 use serde::Deserialize;
 use std::sync::Arc;
@@ -9,9 +9,13 @@ use uuid::Uuid;
 
 use crate::application::dto::{
     CreateProductoDTO, UpdateProductoDTO, UpdateStockDTO, UpdateEstadoProductoDTO,
-    ProductoResponseDTO, ProductosListResponseDTO,
+    ProductoResponseDTO, ProductosListResponseDTO, ProductosQueryDTO, ProductosBusquedaResponseDTO,
+    MovimientosStockQueryDTO, MovimientosStockResponseDTO, ConciliacionStockResponseDTO,
+    TransferenciaStockDTO, TransferenciaStockResponseDTO,
 };
 use crate::application::services::ProductoService;
+use crate::domain::auth::AuthenticatedPrincipal;
+use crate::domain::entities::ActorMovimiento;
 use crate::shared::error::AppResult;
 
 // ============================================================================
@@ -21,30 +25,42 @@ use crate::shared::error::AppResult;
 #[derive(Debug, Deserialize)]
 pub struct SearchQuery {
     pub q: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-pub struct CategoriaQuery {
-    pub categoria: String,
+    pub limit: Option<i64>,
 }
 
 // ============================================================================
 // HANDLERS PÚBLICOS
 // ============================================================================
 
-/// Lista todos los productos activos
+/// Catálogo de productos activos: filtros, orden y paginación
 #[utoipa::path(
     get,
     path = "/api/productos",
     tag = "productos",
+    params(
+        ("categoria" = Option<String>, Query, description = "Slugs de categoría separados por coma (incluye subcategorías)"),
+        ("precio_min" = Option<f64>, Query, description = "Precio mínimo (inclusive)"),
+        ("precio_max" = Option<f64>, Query, description = "Precio máximo (inclusive)"),
+        ("en_stock" = Option<bool>, Query, description = "Solo productos disponibles (en el almacén cercano si se pide)"),
+        ("sort" = Option<String>, Query, description = "precio | nombre | created_at (default: nombre)"),
+        ("order" = Option<String>, Query, description = "asc | desc (default: asc)"),
+        ("limit" = Option<i64>, Query, description = "Tamaño de página (default: 20, máximo: 100)"),
+        ("page" = Option<i64>, Query, description = "Número de página desde 1 (no combinar con cursor)"),
+        ("cursor" = Option<String>, Query, description = "Cursor devuelto en paginacion.next_cursor"),
+        ("disponibilidad" = Option<String>, Query, description = "total | cercano (default: total)"),
+        ("lat" = Option<f64>, Query, description = "Latitud del cliente (requerida con disponibilidad=cercano)"),
+        ("lng" = Option<f64>, Query, description = "Longitud del cliente (requerida con disponibilidad=cercano)")
+    ),
     responses(
-        (status = 200, description = "Lista de productos", body = ProductosListResponseDTO)
+        (status = 200, description = "Página del catálogo", body = ProductosListResponseDTO),
+        (status = 400, description = "Filtro, orden o cursor inválido")
     )
 )]
 pub async fn list_productos(
+    Query(query): Query<ProductosQueryDTO>,
     State(service): State<Arc<ProductoService>>,
 ) -> AppResult<Json<ProductosListResponseDTO>> {
-    let productos = service.list_productos().await?;
+    let productos = service.list_productos(query).await?;
     Ok(Json(productos))
 }
 
@@ -69,45 +85,63 @@ pub async fn get_producto(
     Ok(Json(producto))
 }
 
-/// Busca productos por término
+/// Busca productos por término, ordenados por relevancia
+///
+/// Texto completo en español sin distinguir acentos ni plurales, más prefijo de
+/// SKU. Sin coincidencias, recurre a similitud del nombre (`modo = similitud`).
 #[utoipa::path(
     get,
     path = "/api/productos/buscar",
     tag = "productos",
     params(
-        ("q" = String, Query, description = "Término de búsqueda")
+        ("q" = String, Query, description = "Término de búsqueda (admite \"frase\", -excluir y or)"),
+        ("limit" = Option<i64>, Query, description = "Máximo de resultados (default: 20, máximo: 100)")
     ),
     responses(
-        (status = 200, description = "Resultados de búsqueda", body = ProductosListResponseDTO),
+        (status = 200, description = "Resultados de búsqueda", body = ProductosBusquedaResponseDTO),
         (status = 400, description = "Término de búsqueda vacío")
     )
 )]
 pub async fn search_productos(
     Query(query): Query<SearchQuery>,
     State(service): State<Arc<ProductoService>>,
-) -> AppResult<Json<ProductosListResponseDTO>> {
+) -> AppResult<Json<ProductosBusquedaResponseDTO>> {
     let q = query.q.unwrap_or_default();
-    let productos = service.search_productos(&q).await?;
+    let productos = service.search_productos(&q, query.limit).await?;
     Ok(Json(productos))
 }
 
-/// Obtiene productos por categoría
+/// Obtiene productos de una categoría y sus subcategorías (paginado como el catálogo)
 #[utoipa::path(
     get,
     path = "/api/productos/categoria/{categoria}",
     tag = "productos",
     params(
-        ("categoria" = String, Path, description = "Nombre de la categoría")
+        ("categoria" = String, Path, description = "Slug de la categoría"),
+        ("precio_min" = Option<f64>, Query, description = "Precio mínimo (inclusive)"),
+        ("precio_max" = Option<f64>, Query, description = "Precio máximo (inclusive)"),
+        ("en_stock" = Option<bool>, Query, description = "Solo productos disponibles (en el almacén cercano si se pide)"),
+        ("sort" = Option<String>, Query, description = "precio | nombre | created_at (default: nombre)"),
+        ("order" = Option<String>, Query, description = "asc | desc (default: asc)"),
+        ("limit" = Option<i64>, Query, description = "Tamaño de página (default: 20, máximo: 100)"),
+        ("page" = Option<i64>, Query, description = "Número de página desde 1 (no combinar con cursor)"),
+        ("cursor" = Option<String>, Query, description = "Cursor devuelto en paginacion.next_cursor"),
+        ("disponibilidad" = Option<String>, Query, description = "total | cercano (default: total)"),
+        ("lat" = Option<f64>, Query, description = "Latitud del cliente (requerida con disponibilidad=cercano)"),
+        ("lng" = Option<f64>, Query, description = "Longitud del cliente (requerida con disponibilidad=cercano)")
     ),
     responses(
-        (status = 200, description = "Productos de la categoría", body = ProductosListResponseDTO)
+        (status = 200, description = "Productos de la categoría", body = ProductosListResponseDTO),
+        (status = 404, description = "Categoría no encontrada o inactiva"),
+        (status = 400, description = "Filtro, orden o cursor inválido")
     )
 )]
 pub async fn get_by_categoria(
     Path(categoria): Path<String>,
+    Query(query): Query<ProductosQueryDTO>,
     State(service): State<Arc<ProductoService>>,
 ) -> AppResult<Json<ProductosListResponseDTO>> {
-    let productos = service.get_by_categoria(&categoria).await?;
+    let productos = service.get_by_categoria(&categoria, query).await?;
     Ok(Json(productos))
 }
 
@@ -141,7 +175,7 @@ pub async fn get_by_sku(
     get,
     path = "/api/admin/productos",
     tag = "productos-admin",
-    security(("bearer_auth" = [])),
+    security(("bearer_auth" = []), ("api_key" = [])),
     responses(
         (status = 200, description = "Lista completa de productos", body = ProductosListResponseDTO),
         (status = 401, description = "No autenticado"),
@@ -160,7 +194,7 @@ pub async fn list_all_productos(
     post,
     path = "/api/admin/productos",
     tag = "productos-admin",
-    security(("bearer_auth" = [])),
+    security(("bearer_auth" = []), ("api_key" = [])),
     request_body = CreateProductoDTO,
     responses(
         (status = 201, description = "Producto creado", body = ProductoResponseDTO),
@@ -170,10 +204,12 @@ pub async fn list_all_productos(
     )
 )]
 pub async fn create_producto(
+    Extension(principal): Extension<AuthenticatedPrincipal>,
     State(service): State<Arc<ProductoService>>,
     Json(dto): Json<CreateProductoDTO>,
 ) -> AppResult<(StatusCode, Json<ProductoResponseDTO>)> {
-    let producto = service.create_producto(dto).await?;
+    // El kardex registra a quien realmente actuó: el admin si hay suplantación, o la API key
+    let producto = service.create_producto(ActorMovimiento::from(&principal), dto).await?;
     Ok((StatusCode::CREATED, Json(producto)))
 }
 
@@ -182,7 +218,7 @@ pub async fn create_producto(
     put,
     path = "/api/admin/productos/{id}",
     tag = "productos-admin",
-    security(("bearer_auth" = [])),
+    security(("bearer_auth" = []), ("api_key" = [])),
     params(
         ("id" = Uuid, Path, description = "ID del producto")
     ),
@@ -203,38 +239,116 @@ pub async fn update_producto(
     Ok(Json(producto))
 }
 
-/// Actualiza el stock de un producto - Admin
+/// Ajusta el stock de un producto y registra el movimiento en el kardex - Admin
 #[utoipa::path(
     patch,
     path = "/api/admin/productos/{id}/stock",
     tag = "productos-admin",
-    security(("bearer_auth" = [])),
+    security(("bearer_auth" = []), ("api_key" = [])),
     params(
         ("id" = Uuid, Path, description = "ID del producto")
     ),
     request_body = UpdateStockDTO,
     responses(
         (status = 200, description = "Stock actualizado", body = ProductoResponseDTO),
-        (status = 400, description = "Stock insuficiente o datos inválidos"),
+        (status = 400, description = "Stock insuficiente, motivo faltante, almacén inválido o pedido inexistente"),
         (status = 404, description = "Producto no encontrado"),
         (status = 401, description = "No autenticado")
     )
 )]
 pub async fn update_stock(
+    Extension(principal): Extension<AuthenticatedPrincipal>,
     Path(id): Path<Uuid>,
     State(service): State<Arc<ProductoService>>,
     Json(dto): Json<UpdateStockDTO>,
 ) -> AppResult<Json<ProductoResponseDTO>> {
-    let producto = service.update_stock(id, dto).await?;
+    let producto = service.update_stock(id, ActorMovimiento::from(&principal), dto).await?;
     Ok(Json(producto))
 }
 
+/// Mueve stock de un producto entre dos almacenes - Admin
+///
+/// Registra una salida y una entrada en el kardex con el mismo `id_transferencia`.
+#[utoipa::path(
+    post,
+    path = "/api/admin/productos/{id}/transferencias",
+    tag = "productos-admin",
+    security(("bearer_auth" = []), ("api_key" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID del producto")
+    ),
+    request_body = TransferenciaStockDTO,
+    responses(
+        (status = 201, description = "Transferencia registrada", body = TransferenciaStockResponseDTO),
+        (status = 400, description = "Stock insuficiente en el origen, almacén inválido o motivo faltante"),
+        (status = 404, description = "Producto no encontrado"),
+        (status = 401, description = "No autenticado")
+    )
+)]
+pub async fn transferir_stock(
+    Extension(principal): Extension<AuthenticatedPrincipal>,
+    Path(id): Path<Uuid>,
+    State(service): State<Arc<ProductoService>>,
+    Json(dto): Json<TransferenciaStockDTO>,
+) -> AppResult<(StatusCode, Json<TransferenciaStockResponseDTO>)> {
+    let transferencia = service.transferir_stock(id, ActorMovimiento::from(&principal), dto).await?;
+    Ok((StatusCode::CREATED, Json(transferencia)))
+}
+
+/// Kardex de un producto: movimientos de stock del más reciente al más antiguo - Admin
+#[utoipa::path(
+    get,
+    path = "/api/admin/productos/{id}/movimientos",
+    tag = "productos-admin",
+    security(("bearer_auth" = []), ("api_key" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID del producto"),
+        ("desde" = Option<String>, Query, description = "Movimientos desde (RFC 3339, inclusive)"),
+        ("hasta" = Option<String>, Query, description = "Movimientos hasta (RFC 3339, inclusive)"),
+        ("limit" = Option<i64>, Query, description = "Tamaño de página (default: 50, máximo: 200)"),
+        ("cursor" = Option<String>, Query, description = "Cursor `next_cursor` de la página anterior"),
+        ("id_almacen" = Option<Uuid>, Query, description = "Solo los movimientos de este almacén")
+    ),
+    responses(
+        (status = 200, description = "Movimientos del producto", body = MovimientosStockResponseDTO),
+        (status = 400, description = "Rango de fechas o cursor inválido"),
+        (status = 404, description = "Producto no encontrado"),
+        (status = 401, description = "No autenticado")
+    )
+)]
+pub async fn list_movimientos_stock(
+    Path(id): Path<Uuid>,
+    State(service): State<Arc<ProductoService>>,
+    Query(query): Query<MovimientosStockQueryDTO>,
+) -> AppResult<Json<MovimientosStockResponseDTO>> {
+    let movimientos = service.list_movimientos(id, query).await?;
+    Ok(Json(movimientos))
+}
+
+/// Verifica que el stock de cada producto coincida con la suma de su kardex - Admin
+#[utoipa::path(
+    get,
+    path = "/api/admin/productos/conciliacion-stock",
+    tag = "productos-admin",
+    security(("bearer_auth" = []), ("api_key" = [])),
+    responses(
+        (status = 200, description = "Resultado de la conciliación", body = ConciliacionStockResponseDTO),
+        (status = 401, description = "No autenticado")
+    )
+)]
+pub async fn conciliar_stock(
+    State(service): State<Arc<ProductoService>>,
+) -> AppResult<Json<ConciliacionStockResponseDTO>> {
+    let conciliacion = service.conciliar_stock().await?;
+    Ok(Json(conciliacion))
+}
+
 /// Cambia el estado de un producto (activar/desactivar) - Admin
 #[utoipa::path(
     patch,
     path = "/api/admin/productos/{id}/estado",
     tag = "productos-admin",
-    security(("bearer_auth" = [])),
+    security(("bearer_auth" = []), ("api_key" = [])),
     params(
         ("id" = Uuid, Path, description = "ID del producto")
     ),
@@ -259,12 +373,12 @@ pub async fn update_estado_producto(
     delete,
     path = "/api/admin/productos/{id}",
     tag = "productos-admin",
-    security(("bearer_auth" = [])),
+    security(("bearer_auth" = []), ("api_key" = [])),
     params(
         ("id" = Uuid, Path, description = "ID del producto")
     ),
     responses(
-        (status = 204, description = "Producto eliminado"),
+        (status = 204, description = "Producto eliminado, o desactivado si tiene movimientos de stock"),
         (status = 404, description = "Producto no encontrado"),
         (status = 401, description = "No autenticado")
     )
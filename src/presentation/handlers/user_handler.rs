@@ -1,14 +1,18 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Extension, Json,
 };
+use futures::StreamExt;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::application::dto::{
     CreateUserDTO, UpdateUserDTO, UpdateUserRoleDTO, UpdateUserStatusDTO,
-    UserResponseDTO, UsersListResponseDTO,
+    UserResponseDTO, UsersListResponseDTO, UsersQueryDTO,
+    UserImportQueryDTO, UserImportReportDTO,
 };
 use crate::application::services::UserService;
 use crate::domain::auth::AuthenticatedUser;
@@ -18,14 +22,26 @@ use crate::shared::error::AppResult;
 // HANDLERS - ADMIN
 // ============================================================================
 
-/// Lista todos los usuarios (admin)
+/// Directorio de usuarios con filtros, orden y paginación por cursor (admin)
 #[utoipa::path(
     get,
     path = "/api/admin/users",
     tag = "usuarios-admin",
     security(("bearer_auth" = [])),
+    params(
+        ("rol" = Option<String>, Query, description = "Filtrar por rol (cliente, transportista, admin)"),
+        ("activo" = Option<bool>, Query, description = "Filtrar por estado"),
+        ("created_desde" = Option<String>, Query, description = "Creados desde (RFC 3339, inclusive)"),
+        ("created_hasta" = Option<String>, Query, description = "Creados hasta (RFC 3339, inclusive)"),
+        ("q" = Option<String>, Query, description = "Subcadena de email, nombre o apellido"),
+        ("sort" = Option<String>, Query, description = "email | nombre | apellido | rol | activo | created_at | updated_at (default: created_at)"),
+        ("order" = Option<String>, Query, description = "asc | desc (default: desc)"),
+        ("limit" = Option<i64>, Query, description = "Tamaño de página (default: 20, máximo: 100)"),
+        ("cursor" = Option<String>, Query, description = "Cursor `next_cursor` de la página anterior")
+    ),
     responses(
-        (status = 200, description = "Lista de usuarios", body = UsersListResponseDTO),
+        (status = 200, description = "Página de usuarios y conteo por rol", body = UsersListResponseDTO),
+        (status = 400, description = "Filtro, orden o cursor inválido"),
         (status = 401, description = "No autenticado"),
         (status = 403, description = "No es administrador")
     )
@@ -33,11 +49,75 @@ use crate::shared::error::AppResult;
 pub async fn list_users(
     Extension(_user): Extension<AuthenticatedUser>,
     State(service): State<Arc<UserService>>,
+    Query(query): Query<UsersQueryDTO>,
 ) -> AppResult<Json<UsersListResponseDTO>> {
-    let users = service.list_users().await?;
+    let users = service.list_users(query).await?;
     Ok(Json(users))
 }
 
+/// Importa usuarios desde CSV con reporte por fila (admin)
+#[utoipa::path(
+    post,
+    path = "/api/admin/users/import",
+    tag = "usuarios-admin",
+    security(("bearer_auth" = [])),
+    params(
+        ("mode" = Option<String>, Query, description = "dry_run (default): solo valida | commit: crea las filas válidas")
+    ),
+    request_body(
+        content = String,
+        content_type = "text/csv",
+        description = "Cabecera `email,nombre,apellido,rol` (solo `email` es obligatoria; rol por defecto: cliente)"
+    ),
+    responses(
+        (status = 200, description = "Reporte de la importación", body = UserImportReportDTO),
+        (status = 400, description = "CSV ilegible, sin columna email, demasiadas filas o mode inválido"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador")
+    )
+)]
+pub async fn import_users(
+    Extension(_user): Extension<AuthenticatedUser>,
+    State(service): State<Arc<UserService>>,
+    Query(query): Query<UserImportQueryDTO>,
+    body: String,
+) -> AppResult<Json<UserImportReportDTO>> {
+    let report = service.import_users(query, &body).await?;
+    Ok(Json(report))
+}
+
+/// Exporta todos los usuarios como CSV (admin)
+#[utoipa::path(
+    get,
+    path = "/api/admin/users/export",
+    tag = "usuarios-admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "CSV con todos los usuarios (activos e inactivos)", content_type = "text/csv", body = String),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador")
+    )
+)]
+pub async fn export_users(
+    Extension(_user): Extension<AuthenticatedUser>,
+    State(service): State<Arc<UserService>>,
+) -> Response {
+    let body = service.export_users().map(|chunk| {
+        chunk.map_err(|e| {
+            tracing::error!("Error exportando usuarios: {:?}", e);
+            std::io::Error::other("Error al exportar usuarios")
+        })
+    });
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (header::CONTENT_DISPOSITION, "attachment; filename=\"usuarios.csv\""),
+        ],
+        Body::from_stream(body),
+    ).into_response()
+}
+
 /// Obtiene un usuario por ID (admin)
 #[utoipa::path(
     get,
@@ -169,6 +249,33 @@ pub async fn update_user_status(
     Ok(Json(user))
 }
 
+/// Revoca todas las sesiones de un usuario (logout forzado - solo admin)
+///
+/// Los tokens emitidos hasta este momento dejan de ser válidos.
+#[utoipa::path(
+    post,
+    path = "/api/admin/users/{id}/revoke-sessions",
+    tag = "usuarios-admin",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "ID del usuario")
+    ),
+    responses(
+        (status = 204, description = "Sesiones revocadas"),
+        (status = 404, description = "Usuario no encontrado"),
+        (status = 401, description = "No autenticado"),
+        (status = 403, description = "No es administrador")
+    )
+)]
+pub async fn revoke_user_sessions(
+    Extension(admin): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    State(service): State<Arc<UserService>>,
+) -> AppResult<StatusCode> {
+    service.revoke_user_sessions(id, admin.id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Elimina un usuario (soft delete - solo admin)
 #[utoipa::path(
     delete,
@@ -1,15 +1,68 @@
 use axum::{
-    extract::Request,
+    extract::{Request, State},
     http::{header, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
     Json,
 };
-use jsonwebtoken::{decode, DecodingKey, Validation, Algorithm};
+use jsonwebtoken::{decode, decode_header, errors::ErrorKind, Validation};
 use serde_json::json;
-use std::env;
+use std::sync::Arc;
+use uuid::Uuid;
 
-use crate::domain::auth::{Claims, AuthenticatedUser};
+use crate::application::services::{ApiKeyService, ImpersonationService, SessionService, SessionStatus};
+use crate::config::AppConfig;
+use crate::domain::auth::{AuthenticatedPrincipal, Claims, AuthenticatedUser};
+use crate::infrastructure::auth::KeyProvider;
+
+/// Estado compartido por los middlewares de autenticación
+#[derive(Clone)]
+pub struct AuthState {
+    keys: Arc<KeyProvider>,
+    issuer: String,
+    sessions: Arc<SessionService>,
+    impersonation: Arc<ImpersonationService>,
+    /// Solo las rutas que lo habilitan aceptan el header `X-API-Key`
+    api_keys: Option<Arc<ApiKeyService>>,
+}
+
+impl AuthState {
+    pub fn new(
+        keys: Arc<KeyProvider>,
+        issuer: String,
+        sessions: Arc<SessionService>,
+        impersonation: Arc<ImpersonationService>,
+    ) -> Self {
+        Self { keys, issuer, sessions, impersonation, api_keys: None }
+    }
+
+    pub fn from_config(
+        config: &AppConfig,
+        sessions: Arc<SessionService>,
+        impersonation: Arc<ImpersonationService>,
+    ) -> Self {
+        Self::new(
+            Arc::new(KeyProvider::from_config(config)),
+            config.jwt_issuer.clone(),
+            sessions,
+            impersonation,
+        )
+    }
+
+    /// Variante que además acepta API keys de servicio
+    pub fn accepting_api_keys(&self, api_keys: Arc<ApiKeyService>) -> Self {
+        Self {
+            api_keys: Some(api_keys),
+            ..self.clone()
+        }
+    }
+}
+
+/// Header con la API key de servicio
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Header con el UUID del usuario a suplantar (solo admins)
+const ACT_AS_HEADER: &str = "x-act-as";
 
 /// Extrae el token del header Authorization
 fn extract_token(request: &Request) -> Option<String> {
@@ -26,25 +79,63 @@ fn extract_token(request: &Request) -> Option<String> {
         })
 }
 
-/// Valida el JWT usando el secret de Supabase
-fn validate_jwt(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
-    let secret = env::var("SUPABASE_JWT_SECRET")
-        .expect("SUPABASE_JWT_SECRET must be set");
+/// Extrae la API key del header `X-API-Key`
+fn extract_api_key(request: &Request) -> Option<String> {
+    request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+}
+
+/// Valida el JWT de Supabase (HS256 con secret, RS256/ES256 con JWKS)
+async fn validate_jwt(auth: &AuthState, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    let header = decode_header(token)?;
+
+    let key = auth.keys.decoding_key(&header).await.map_err(|e| {
+        tracing::warn!("No se pudo resolver la clave del token: {}", e);
+        jsonwebtoken::errors::Error::from(ErrorKind::InvalidKeyFormat)
+    })?;
 
-    let mut validation = Validation::new(Algorithm::HS256);
+    // El algoritmo viene del header, pero la clave ya quedó restringida a su familia
+    let mut validation = Validation::new(header.alg);
     validation.set_audience(&["authenticated"]);
-    
-    let token_data = decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(secret.as_bytes()),
-        &validation,
-    )?;
+    validation.set_issuer(&[&auth.issuer]);
+
+    let token_data = decode::<Claims>(token, &key, &validation)?;
 
     Ok(token_data.claims)
 }
 
 /// Middleware que requiere autenticación
-pub async fn require_auth(mut request: Request, next: Next) -> Response {
+pub async fn require_auth(
+    State(auth): State<AuthState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    // API key de servicio (solo en rutas que las aceptan)
+    if let (Some(api_keys), Some(raw_key)) = (&auth.api_keys, extract_api_key(&request)) {
+        return match api_keys.authenticate(&raw_key).await {
+            Ok(Some(principal)) => {
+                tracing::debug!("Request autenticado con API key {} ({})", principal.id, principal.nombre);
+                request
+                    .extensions_mut()
+                    .insert(AuthenticatedPrincipal::ApiKey(principal));
+                next.run(request).await
+            }
+            Ok(None) => (
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": {
+                        "code": "API_KEY_INVALID",
+                        "message": "Invalid, expired or revoked API key"
+                    }
+                })),
+            ).into_response(),
+            Err(e) => e.into_response(),
+        };
+    }
+
     // Extraer token
     let token = match extract_token(&request) {
         Some(t) => t,
@@ -62,11 +153,11 @@ pub async fn require_auth(mut request: Request, next: Next) -> Response {
     };
 
     // Validar token
-    let claims = match validate_jwt(&token) {
+    let claims = match validate_jwt(&auth, &token).await {
         Ok(c) => c,
         Err(e) => {
             let (code, message) = match e.kind() {
-                jsonwebtoken::errors::ErrorKind::ExpiredSignature => {
+                ErrorKind::ExpiredSignature => {
                     ("TOKEN_EXPIRED", "Token has expired")
                 }
                 _ => ("TOKEN_INVALID", "Invalid token"),
@@ -97,19 +188,108 @@ pub async fn require_auth(mut request: Request, next: Next) -> Response {
         ).into_response();
     }
 
+    // Aprovisionar el usuario si hace falta, verificar que la cuenta siga activa
+    // y que la sesión no haya sido revocada
+    match auth.sessions.check_session(&claims).await {
+        Ok(SessionStatus::Active) => {}
+        Ok(SessionStatus::UserInactive) => {
+            return (
+                StatusCode::FORBIDDEN,
+                Json(json!({
+                    "error": {
+                        "code": "USER_INACTIVE",
+                        "message": "User account is disabled"
+                    }
+                })),
+            ).into_response();
+        }
+        Ok(SessionStatus::Revoked) => {
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": {
+                        "code": "SESSION_REVOKED",
+                        "message": "Session has been revoked, please sign in again"
+                    }
+                })),
+            ).into_response();
+        }
+        Err(e) => return e.into_response(),
+    }
+
+    let mut user: AuthenticatedUser = claims.into();
+
+    // Suplantación (X-Act-As): el request se ejecuta como el usuario objetivo
+    let act_as = match request.headers().get(ACT_AS_HEADER) {
+        None => None,
+        Some(value) => match value.to_str().ok().and_then(|v| v.trim().parse::<Uuid>().ok()) {
+            Some(target_id) => Some(target_id),
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": {
+                            "code": "ACT_AS_INVALID",
+                            "message": "X-Act-As must be a user UUID"
+                        }
+                    })),
+                ).into_response();
+            }
+        },
+    };
+
+    if let Some(target_id) = act_as {
+        let target = match auth.impersonation.resolve_target(user.id, target_id).await {
+            Ok(target) => target,
+            Err(e) => return e.into_response(),
+        };
+
+        user = AuthenticatedUser {
+            id: target.id,
+            email: target.email,
+            role: user.role,
+            impersonated_by: Some(user.id),
+        };
+    }
+
     // Insertar usuario autenticado en las extensiones del request
-    let user: AuthenticatedUser = claims.into();
-    request.extensions_mut().insert(user);
+    request
+        .extensions_mut()
+        .insert(AuthenticatedPrincipal::User(user.clone()));
+    request.extensions_mut().insert(user.clone());
 
-    next.run(request).await
+    let Some(actor_id) = user.impersonated_by else {
+        return next.run(request).await;
+    };
+
+    // Todo request suplantado queda en la bitácora con ambas identidades
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+    let response = next.run(request).await;
+    auth.impersonation
+        .record_request(actor_id, user.id, &method, &path, response.status().as_u16())
+        .await;
+
+    response
 }
 
 /// Middleware opcional - no falla si no hay token
-pub async fn optional_auth(mut request: Request, next: Next) -> Response {
+pub async fn optional_auth(
+    State(auth): State<AuthState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
     if let Some(token) = extract_token(&request) {
-        if let Ok(claims) = validate_jwt(&token) {
-            if !claims.is_expired() {
+        if let Ok(claims) = validate_jwt(&auth, &token).await {
+            let active = matches!(
+                auth.sessions.check_session(&claims).await,
+                Ok(SessionStatus::Active)
+            );
+            if !claims.is_expired() && active {
                 let user: AuthenticatedUser = claims.into();
+                request
+                    .extensions_mut()
+                    .insert(AuthenticatedPrincipal::User(user.clone()));
                 request.extensions_mut().insert(user);
             }
         }
@@ -0,0 +1,39 @@
+use axum::{
+    extract::Request,
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+
+use crate::domain::auth::AuthenticatedUser;
+
+/// Middleware que rechaza la operación si el request es una suplantación (`X-Act-As`)
+///
+/// Se aplica a operaciones destructivas (borrados definitivos, cambios de rol),
+/// que soporte no debe poder ejecutar en nombre de otro usuario:
+///
+/// ```ignore
+/// delete(delete_direccion_permanente).route_layer(middleware::from_fn(forbid_impersonation))
+/// ```
+pub async fn forbid_impersonation(request: Request, next: Next) -> Response {
+    let impersonated = request
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .is_some_and(AuthenticatedUser::is_impersonated);
+
+    if impersonated {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": {
+                    "code": "IMPERSONATION_FORBIDDEN",
+                    "message": "Operación no permitida mientras se suplanta a un usuario"
+                }
+            })),
+        ).into_response();
+    }
+
+    next.run(request).await
+}
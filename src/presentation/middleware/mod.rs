@@ -1,3 +1,9 @@
 pub mod auth_middleware;
+pub mod impersonation_middleware;
+pub mod rate_limit_middleware;
+pub mod role_middleware;
 
-pub use auth_middleware::require_auth;
+pub use auth_middleware::{require_auth, AuthState};
+pub use impersonation_middleware::forbid_impersonation;
+pub use rate_limit_middleware::{rate_limit, rate_limit_auth_failures, RateLimiter};
+pub use role_middleware::{require_role, RoleGuard};
@@ -0,0 +1,265 @@
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::config::RateLimitRule;
+use crate::domain::auth::AuthenticatedPrincipal;
+use crate::shared::AppError;
+
+/// Tamaño a partir del cual se purgan los buckets que ya se rellenaron por completo
+const RATE_LIMIT_MAX_BUCKETS: usize = 10_000;
+
+/// Estado de un bucket: tokens disponibles y último momento en que se recargó
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Rate limiter token bucket para un grupo de rutas
+///
+/// Cada cliente (usuario autenticado, API key o IP) tiene su propio bucket con
+/// capacidad `burst` que se recarga a `per_minute / 60` tokens por segundo.
+/// Los buckets viven en memoria del proceso: con varias instancias el límite es por instancia.
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    trust_proxy: bool,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(rule: RateLimitRule, trust_proxy: bool) -> Arc<Self> {
+        Arc::new(Self {
+            capacity: rule.burst.max(1) as f64,
+            refill_per_sec: rule.per_minute as f64 / 60.0,
+            trust_proxy,
+            buckets: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn enabled(&self) -> bool {
+        self.refill_per_sec > 0.0
+    }
+
+    /// Consume un token del cliente; si no hay, devuelve los segundos a esperar
+    fn check(&self, key: String) -> Result<(), u64> {
+        self.check_at(key, Instant::now())
+    }
+
+    fn check_at(&self, key: String, now: Instant) -> Result<(), u64> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.len() >= RATE_LIMIT_MAX_BUCKETS {
+            buckets.retain(|_, bucket| self.refilled(bucket, now) < self.capacity);
+        }
+
+        let bucket = buckets.entry(key).or_insert(Bucket {
+            tokens: self.capacity,
+            updated_at: now,
+        });
+        bucket.tokens = self.refilled(bucket, now);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(self.retry_after(bucket.tokens))
+        }
+    }
+
+    /// Segundos a esperar si el cliente agotó su bucket, sin consumir un token
+    fn exhausted_at(&self, key: &str, now: Instant) -> Option<u64> {
+        let buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let tokens = self.refilled(buckets.get(key)?, now);
+        (tokens < 1.0).then(|| self.retry_after(tokens))
+    }
+
+    fn retry_after(&self, tokens: f64) -> u64 {
+        ((1.0 - tokens) / self.refill_per_sec).ceil().max(1.0) as u64
+    }
+
+    fn refilled(&self, bucket: &Bucket, now: Instant) -> f64 {
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity)
+    }
+
+    /// Identifica al cliente: usuario o API key si el request está autenticado, si no la IP
+    fn client_key(&self, request: &Request) -> String {
+        match request.extensions().get::<AuthenticatedPrincipal>() {
+            // En una suplantación cuenta contra el admin que la realiza
+            Some(AuthenticatedPrincipal::User(user)) => {
+                format!("user:{}", user.impersonated_by.unwrap_or(user.id))
+            }
+            Some(AuthenticatedPrincipal::ApiKey(api_key)) => format!("api_key:{}", api_key.id),
+            None => format!("ip:{}", self.client_ip(request)),
+        }
+    }
+
+    fn client_ip(&self, request: &Request) -> String {
+        if self.trust_proxy {
+            let forwarded = request
+                .headers()
+                .get("x-forwarded-for")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split(',').next())
+                .map(str::trim)
+                .filter(|v| !v.is_empty());
+            if let Some(ip) = forwarded {
+                return ip.to_string();
+            }
+        }
+
+        request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+/// Middleware que aplica el `RateLimiter` del grupo de rutas
+///
+/// En rutas protegidas debe ejecutarse después de `require_auth` (agregarse como
+/// `route_layer` antes que él) para limitar por usuario en lugar de por IP:
+///
+/// ```ignore
+/// .route_layer(middleware::from_fn_with_state(limiter, rate_limit))
+/// .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth))
+/// ```
+pub async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !limiter.enabled() {
+        return next.run(request).await;
+    }
+
+    let key = limiter.client_key(&request);
+    if let Err(retry_after) = limiter.check(key) {
+        return AppError::TooManyRequests(retry_after).into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Middleware que limita por IP los intentos sin credenciales válidas
+///
+/// Se aplica por fuera de `require_auth`: solo las respuestas 401 consumen un
+/// token, así que el tráfico autenticado no lo agota. Con el bucket vacío la IP
+/// recibe 429 antes de volver a validar credenciales.
+pub async fn rate_limit_auth_failures(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !limiter.enabled() {
+        return next.run(request).await;
+    }
+
+    let key = format!("ip:{}", limiter.client_ip(&request));
+    if let Some(retry_after) = limiter.exhausted_at(&key, Instant::now()) {
+        return AppError::TooManyRequests(retry_after).into_response();
+    }
+
+    let response = next.run(request).await;
+    if response.status() == StatusCode::UNAUTHORIZED {
+        // El 401 ya se respondió; el 429 aplica a los siguientes intentos
+        let _ = limiter.check(key);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn limiter(per_minute: u32, burst: u32) -> Arc<RateLimiter> {
+        RateLimiter::new(RateLimitRule { per_minute, burst }, false)
+    }
+
+    #[test]
+    fn permite_el_burst_y_luego_rechaza() {
+        let limiter = limiter(60, 3);
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check_at("ip:1".into(), now).is_ok());
+        }
+        assert_eq!(limiter.check_at("ip:1".into(), now), Err(1));
+    }
+
+    #[test]
+    fn recarga_segun_la_tasa_por_minuto() {
+        // 6 req/min: un token cada 10 segundos
+        let limiter = limiter(6, 1);
+        let now = Instant::now();
+
+        assert!(limiter.check_at("ip:1".into(), now).is_ok());
+        assert_eq!(limiter.check_at("ip:1".into(), now + Duration::from_secs(4)), Err(6));
+        assert!(limiter.check_at("ip:1".into(), now + Duration::from_secs(10)).is_ok());
+    }
+
+    #[test]
+    fn la_recarga_no_supera_el_burst() {
+        let limiter = limiter(60, 2);
+        let now = Instant::now();
+        let later = now + Duration::from_secs(600);
+
+        assert!(limiter.check_at("ip:1".into(), now).is_ok());
+        assert!(limiter.check_at("ip:1".into(), later).is_ok());
+        assert!(limiter.check_at("ip:1".into(), later).is_ok());
+        assert!(limiter.check_at("ip:1".into(), later).is_err());
+    }
+
+    #[test]
+    fn cada_cliente_tiene_su_bucket() {
+        let limiter = limiter(60, 1);
+        let now = Instant::now();
+
+        assert!(limiter.check_at("user:a".into(), now).is_ok());
+        assert!(limiter.check_at("user:a".into(), now).is_err());
+        assert!(limiter.check_at("user:b".into(), now).is_ok());
+    }
+
+    #[test]
+    fn exhausted_no_consume_tokens() {
+        let limiter = limiter(60, 1);
+        let now = Instant::now();
+
+        assert_eq!(limiter.exhausted_at("ip:1", now), None);
+        assert!(limiter.check_at("ip:1".into(), now).is_ok());
+        assert_eq!(limiter.exhausted_at("ip:1", now), Some(1));
+        assert_eq!(limiter.exhausted_at("ip:1", now), Some(1));
+        assert_eq!(limiter.exhausted_at("ip:1", now + Duration::from_secs(1)), None);
+    }
+
+    #[test]
+    fn per_minute_cero_desactiva_el_limite() {
+        assert!(!limiter(0, 10).enabled());
+        assert!(limiter(1, 10).enabled());
+    }
+
+    #[test]
+    fn purga_los_buckets_llenos_al_alcanzar_el_maximo() {
+        let limiter = limiter(60, 1);
+        let now = Instant::now();
+
+        for i in 0..RATE_LIMIT_MAX_BUCKETS {
+            limiter.check_at(format!("ip:{}", i), now).unwrap();
+        }
+        let later = now + Duration::from_secs(2);
+        limiter.check_at("ip:nuevo".into(), later).unwrap();
+
+        assert_eq!(limiter.buckets.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,132 @@
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::domain::auth::AuthenticatedPrincipal;
+use crate::domain::repositories::UserRepository;
+use crate::shared::AppError;
+
+/// Estado del middleware de roles: dónde buscar el rol y qué roles se aceptan
+#[derive(Clone)]
+pub struct RoleGuard {
+    user_repo: Arc<dyn UserRepository>,
+    roles: &'static [&'static str],
+    /// Scope que debe tener una API key para pasar (sin scope, las API keys se rechazan)
+    scope: Option<&'static str>,
+}
+
+impl RoleGuard {
+    pub fn new(user_repo: Arc<dyn UserRepository>, roles: &'static [&'static str]) -> Self {
+        Self { user_repo, roles, scope: None }
+    }
+
+    /// Permite además API keys de servicio que tengan el scope indicado
+    pub fn with_scope(mut self, scope: &'static str) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+}
+
+/// Respuesta 403 estructurada
+fn forbidden(code: &str, message: String, guard: &RoleGuard, current_role: Option<&str>) -> Response {
+    (
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": {
+                "code": code,
+                "message": message,
+                "required_roles": guard.roles,
+                "current_role": current_role
+            }
+        })),
+    ).into_response()
+}
+
+/// Middleware que exige uno de los roles configurados en `RoleGuard`
+///
+/// El rol se resuelve desde `public.users` (no desde el JWT). Debe ejecutarse
+/// después de `require_auth`, es decir, agregarse como `route_layer` antes que él:
+///
+/// ```ignore
+/// .route_layer(middleware::from_fn_with_state(RoleGuard::new(repo, &[ROL_ADMIN]), require_role))
+/// .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth))
+/// ```
+pub async fn require_role(
+    State(guard): State<RoleGuard>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let user_id = match request.extensions().get::<AuthenticatedPrincipal>() {
+        Some(AuthenticatedPrincipal::User(user)) => user.id,
+        // Las API keys no tienen rol: se autorizan por scope
+        Some(AuthenticatedPrincipal::ApiKey(api_key)) => {
+            if !guard.scope.is_some_and(|scope| api_key.has_scope(scope)) {
+                return (
+                    StatusCode::FORBIDDEN,
+                    Json(json!({
+                        "error": {
+                            "code": "INSUFFICIENT_SCOPE",
+                            "message": "La API key no tiene permisos para esta operación",
+                            "required_scope": guard.scope,
+                            "current_scopes": api_key.scopes
+                        }
+                    })),
+                ).into_response();
+            }
+            return next.run(request).await;
+        }
+        None => {
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": {
+                        "code": "TOKEN_MISSING",
+                        "message": "Authorization header is required"
+                    }
+                })),
+            ).into_response();
+        }
+    };
+
+    let user = match guard.user_repo.find_by_id(user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            return forbidden(
+                "USER_NOT_REGISTERED",
+                "El usuario no está registrado en el sistema".to_string(),
+                &guard,
+                None,
+            );
+        }
+        Err(e) => return AppError::Database(e).into_response(),
+    };
+
+    if !user.activo {
+        return forbidden(
+            "USER_INACTIVE",
+            "La cuenta del usuario está suspendida".to_string(),
+            &guard,
+            Some(&user.rol),
+        );
+    }
+
+    if !guard.roles.contains(&user.rol.as_str()) {
+        return forbidden(
+            "INSUFFICIENT_ROLE",
+            format!("Se requiere uno de los roles: {}", guard.roles.join(", ")),
+            &guard,
+            Some(&user.rol),
+        );
+    }
+
+    // Dejar disponible el usuario de public.users para los handlers
+    request.extensions_mut().insert(user);
+
+    next.run(request).await
+}
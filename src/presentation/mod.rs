@@ -1,3 +1,5 @@
+pub mod cli;
+pub mod extractors;
 pub mod handlers;
 pub mod middleware;
 pub mod routes;
@@ -1,34 +1,52 @@
 use axum::{
+    extract::DefaultBodyLimit,
     middleware,
-    routing::{get, patch, delete, put},
-    Router,
+    routing::{get, post, patch, delete, put},
+    Extension, Router,
 };
 use sqlx::PgPool;
 use std::sync::Arc;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
-use utoipa::openapi::security::{SecurityScheme, Http, HttpAuthScheme};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme, Http, HttpAuthScheme};
 use tower_http::trace::TraceLayer;
 
 use crate::application::dto::{
     CreateUserDTO, UpdateUserDTO, UserResponseDTO, UpdateUserRoleDTO, UpdateUserStatusDTO, UsersListResponseDTO,
+    UserImportEstado, UserImportRowDTO, UserImportReportDTO,
     CreateProductoDTO, UpdateProductoDTO, UpdateStockDTO, UpdateEstadoProductoDTO,
-    ProductoResponseDTO, ProductosListResponseDTO,
+    ProductoResponseDTO, ProductosListResponseDTO, AlmacenCercanoDTO,
+    PaginacionDTO, CategoriaFacetaDTO,
+    ModoBusqueda, ProductoBusquedaResponseDTO, ProductosBusquedaResponseDTO,
+    MovimientoStockResponseDTO, MovimientosStockResponseDTO, DescuadreStockDTO, ConciliacionStockResponseDTO,
+    InventarioAlmacenDTO, TransferenciaStockDTO, TransferenciaStockResponseDTO,
     CreatePedidoDTO, PedidoResponseDTO, PedidosListResponseDTO,
     UpdateEstadoPedidoDTO, AsignarTransportistaDTO,
     CreatePerfilClienteDTO, UpdatePerfilClienteDTO, 
     PerfilClienteResponseDTO, PerfilesClienteListResponseDTO,
     CreateDireccionDTO, UpdateDireccionDTO, CreateAlmacenDTO,
     DireccionResponseDTO, DireccionesListResponseDTO,
+    CreateApiKeyDTO, ApiKeyResponseDTO, ApiKeyCreatedResponseDTO, ApiKeysListResponseDTO,
+    PersonalDataExportDTO, AnonymizationResponseDTO, MeResponseDTO,
+    AddMiembroDTO, UpdateMiembroRolDTO, MiembroResponseDTO, MiembrosListResponseDTO,
+    PerfilAccesibleDTO, PerfilesAccesiblesResponseDTO,
+    ConfigurarCreditoDTO, EstadoCreditoResponseDTO, CreditoAlertasResponseDTO,
+    FusionarPerfilDTO, PerfilDuplicadoDTO, DuplicadosGrupoDTO, DuplicadosResponseDTO,
+    PerfilFusionResponseDTO, FusionResponseDTO, FusionesListResponseDTO,
+    CreateCategoriaDTO, UpdateCategoriaDTO, CategoriaResponseDTO, CategoriasListResponseDTO,
+    CategoriaArbolDTO, CategoriasArbolResponseDTO,
 };
-use crate::application::services::{UserService, PedidoService, PerfilClienteService, ProductoService, DireccionService};
-use crate::domain::repositories::{UserRepository, PedidoRepository, PerfilClienteRepository, ProductoRepository, DireccionRepository};
-use crate::infrastructure::repositories::{UserRepositoryImpl, PedidoRepositoryImpl, PerfilClienteRepositoryImpl, ProductoRepositoryImpl, DireccionRepositoryImpl};
-use crate::config::create_cors_layer;
+use crate::application::services::{UserService, SessionService, ApiKeyService, ImpersonationService, PrivacyService, AvatarService, AVATAR_MAX_BYTES, MeService, PedidoService, PerfilClienteService, PerfilMiembroService, CreditoService, PerfilFusionService, ProductoService, CategoriaService, DireccionService};
+use crate::domain::repositories::{UserRepository, SessionRevocationRepository, ApiKeyRepository, AuditLogRepository, DataPrivacyRepository, PedidoRepository, PerfilClienteRepository, PerfilMiembroRepository, CreditoRepository, PerfilFusionRepository, ProductoRepository, CategoriaRepository, DireccionRepository, TransportistaRepository};
+use crate::infrastructure::repositories::{UserRepositoryImpl, SessionRevocationRepositoryImpl, ApiKeyRepositoryImpl, AuditLogRepositoryImpl, DataPrivacyRepositoryImpl, PedidoRepositoryImpl, PerfilClienteRepositoryImpl, PerfilMiembroRepositoryImpl, CreditoRepositoryImpl, PerfilFusionRepositoryImpl, ProductoRepositoryImpl, CategoriaRepositoryImpl, DireccionRepositoryImpl, TransportistaRepositoryImpl};
+use crate::config::{create_cors_layer, AppConfig};
+use crate::infrastructure::auth::{DevTokenIssuer, DEV_ENVIRONMENT};
+use crate::infrastructure::storage::LocalBlobStorage;
+use crate::domain::storage::BlobStorage;
 use crate::presentation::handlers::{
-    get_current_user, CurrentUserResponse, __path_get_current_user,
-    list_users, get_user, create_user, update_user, update_user_role, update_user_status, delete_user,
-    __path_list_users, __path_get_user, __path_create_user, __path_update_user, __path_update_user_role, __path_update_user_status, __path_delete_user,
+    get_current_user, CurrentUserResponse, __path_get_current_user, issue_dev_token,
+    list_users, import_users, export_users, get_user, create_user, update_user, update_user_role, update_user_status, delete_user, revoke_user_sessions,
+    __path_list_users, __path_import_users, __path_export_users, __path_get_user, __path_create_user, __path_update_user, __path_update_user_role, __path_update_user_status, __path_delete_user, __path_revoke_user_sessions,
     list_pedidos, get_pedido, create_pedido, 
     update_estado_pedido, assign_transportista, cancel_pedido,
     __path_list_pedidos, __path_get_pedido, __path_create_pedido,
@@ -39,11 +57,13 @@ use crate::presentation::handlers::{
     __path_list_perfiles, __path_get_perfil_by_id, __path_update_perfil_by_id, __path_delete_perfil_by_id,
     list_productos, get_producto, search_productos, get_by_categoria, get_by_sku,
     list_all_productos, create_producto, update_producto, update_stock,
+    list_movimientos_stock, conciliar_stock, transferir_stock,
     update_estado_producto, delete_producto,
     __path_list_productos, __path_get_producto, __path_search_productos,
     __path_get_by_categoria, __path_get_by_sku,
     __path_list_all_productos, __path_create_producto, __path_update_producto,
-    __path_update_stock, __path_update_estado_producto, __path_delete_producto,
+    __path_update_stock, __path_list_movimientos_stock, __path_conciliar_stock, __path_transferir_stock,
+    __path_update_estado_producto, __path_delete_producto,
     list_my_direcciones, get_direccion, get_predeterminada, create_direccion,
     update_direccion, set_predeterminada, deactivate_direccion, activate_direccion,
     delete_direccion_permanente, list_almacenes,
@@ -53,8 +73,27 @@ use crate::presentation::handlers::{
     __path_delete_direccion_permanente, __path_list_almacenes,
     __path_list_all_almacenes, __path_create_almacen, __path_deactivate_almacen, __path_activate_almacen,
     __path_delete_almacen_permanente,
+    list_api_keys, create_api_key, revoke_api_key,
+    __path_list_api_keys, __path_create_api_key, __path_revoke_api_key,
+    export_my_data, export_user_data, anonymize_user,
+    __path_export_my_data, __path_export_user_data, __path_anonymize_user,
+    upload_my_avatar, delete_my_avatar, get_user_avatar,
+    __path_upload_my_avatar, __path_delete_my_avatar, __path_get_user_avatar,
+    get_me, __path_get_me,
+    list_miembros, add_miembro, update_miembro_rol, remove_miembro, list_perfiles_disponibles,
+    __path_list_miembros, __path_add_miembro, __path_update_miembro_rol, __path_remove_miembro,
+    __path_list_perfiles_disponibles,
+    get_my_credito, list_credito_alertas, get_credito_perfil, configurar_credito_perfil,
+    __path_get_my_credito, __path_list_credito_alertas, __path_get_credito_perfil,
+    __path_configurar_credito_perfil,
+    list_perfiles_duplicados, fusionar_perfiles, list_perfil_fusiones,
+    __path_list_perfiles_duplicados, __path_fusionar_perfiles, __path_list_perfil_fusiones,
+    get_arbol_categorias, list_categorias, create_categoria, update_categoria, delete_categoria,
+    __path_get_arbol_categorias, __path_list_categorias, __path_create_categoria,
+    __path_update_categoria, __path_delete_categoria,
 };
-use crate::presentation::middleware::require_auth;
+use crate::domain::auth::{ROL_ADMIN, ROL_TRANSPORTISTA, SCOPE_PEDIDOS_ESTADO, SCOPE_PRODUCTOS_WRITE};
+use crate::presentation::middleware::{forbid_impersonation, rate_limit, rate_limit_auth_failures, require_auth, require_role, AuthState, RateLimiter, RoleGuard};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -75,36 +114,64 @@ use crate::presentation::middleware::require_auth;
         (name = "perfiles-admin", description = "Gestión de perfiles (admin)"),
         (name = "productos", description = "Catálogo de productos (público)"),
         (name = "productos-admin", description = "Gestión de productos (admin)"),
+        (name = "categorias", description = "Árbol de categorías de productos (público)"),
+        (name = "categorias-admin", description = "Gestión de categorías (admin)"),
         (name = "pedidos", description = "Gestión de pedidos"),
         (name = "direcciones", description = "Gestión de direcciones del cliente"),
         (name = "almacenes", description = "Almacenes (lectura pública)"),
         (name = "almacenes-admin", description = "Gestión de almacenes (admin)"),
+        (name = "api-keys-admin", description = "API keys de servicio (admin)"),
     ),
     components(
         schemas(
             CreateUserDTO, UpdateUserDTO, UserResponseDTO, UpdateUserRoleDTO, UpdateUserStatusDTO, UsersListResponseDTO,
+            UserImportEstado, UserImportRowDTO, UserImportReportDTO,
             CreateProductoDTO, UpdateProductoDTO, UpdateStockDTO, UpdateEstadoProductoDTO,
-            ProductoResponseDTO, ProductosListResponseDTO,
+            ProductoResponseDTO, ProductosListResponseDTO, AlmacenCercanoDTO,
+            PaginacionDTO, CategoriaFacetaDTO,
+            ModoBusqueda, ProductoBusquedaResponseDTO, ProductosBusquedaResponseDTO,
+            MovimientoStockResponseDTO, MovimientosStockResponseDTO, DescuadreStockDTO, ConciliacionStockResponseDTO,
+            InventarioAlmacenDTO, TransferenciaStockDTO, TransferenciaStockResponseDTO,
             CreatePedidoDTO, PedidoResponseDTO, PedidosListResponseDTO,
             UpdateEstadoPedidoDTO, AsignarTransportistaDTO,
             CreatePerfilClienteDTO, UpdatePerfilClienteDTO,
             PerfilClienteResponseDTO, PerfilesClienteListResponseDTO,
             CreateDireccionDTO, UpdateDireccionDTO, CreateAlmacenDTO,
             DireccionResponseDTO, DireccionesListResponseDTO,
+            CreateApiKeyDTO, ApiKeyResponseDTO, ApiKeyCreatedResponseDTO, ApiKeysListResponseDTO,
+            PersonalDataExportDTO, AnonymizationResponseDTO,
+            MeResponseDTO,
+            AddMiembroDTO, UpdateMiembroRolDTO, MiembroResponseDTO, MiembrosListResponseDTO,
+            PerfilAccesibleDTO, PerfilesAccesiblesResponseDTO,
+            ConfigurarCreditoDTO, EstadoCreditoResponseDTO, CreditoAlertasResponseDTO,
+            FusionarPerfilDTO, PerfilDuplicadoDTO, DuplicadosGrupoDTO, DuplicadosResponseDTO,
+            PerfilFusionResponseDTO, FusionResponseDTO, FusionesListResponseDTO,
+            CreateCategoriaDTO, UpdateCategoriaDTO, CategoriaResponseDTO, CategoriasListResponseDTO,
+            CategoriaArbolDTO, CategoriasArbolResponseDTO,
             CurrentUserResponse,
         )
     ),
     paths(
         health_check,
         get_current_user,
+        get_me,
+        export_my_data,
+        upload_my_avatar,
+        delete_my_avatar,
+        get_user_avatar,
         // Usuarios admin
         list_users,
+        import_users,
+        export_users,
         get_user,
         create_user,
         update_user,
         update_user_role,
         update_user_status,
         delete_user,
+        revoke_user_sessions,
+        export_user_data,
+        anonymize_user,
         // Pedidos
         list_pedidos,
         get_pedido,
@@ -121,6 +188,21 @@ use crate::presentation::middleware::require_auth;
         get_perfil_by_id,
         update_perfil_by_id,
         delete_perfil_by_id,
+        // Miembros de perfiles de empresa
+        list_miembros,
+        add_miembro,
+        update_miembro_rol,
+        remove_miembro,
+        list_perfiles_disponibles,
+        // Crédito de perfiles
+        get_my_credito,
+        list_credito_alertas,
+        get_credito_perfil,
+        configurar_credito_perfil,
+        // Duplicados y fusión de perfiles
+        list_perfiles_duplicados,
+        fusionar_perfiles,
+        list_perfil_fusiones,
         // Productos públicos
         list_productos,
         get_producto,
@@ -132,8 +214,18 @@ use crate::presentation::middleware::require_auth;
         create_producto,
         update_producto,
         update_stock,
+        transferir_stock,
+        list_movimientos_stock,
+        conciliar_stock,
         update_estado_producto,
         delete_producto,
+        // Categorías públicas
+        get_arbol_categorias,
+        // Categorías admin
+        list_categorias,
+        create_categoria,
+        update_categoria,
+        delete_categoria,
         // Direcciones cliente
         list_my_direcciones,
         get_direccion,
@@ -152,6 +244,10 @@ use crate::presentation::middleware::require_auth;
         deactivate_almacen,
         activate_almacen,
         delete_almacen_permanente,
+        // API keys admin
+        list_api_keys,
+        create_api_key,
+        revoke_api_key,
     ),
     modifiers(&SecurityAddon)
 )]
@@ -166,11 +262,15 @@ impl utoipa::Modify for SecurityAddon {
                 "bearer_auth",
                 SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
             );
+            components.add_security_scheme(
+                "api_key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))),
+            );
         }
     }
 }
 
-pub fn create_routes(pool: PgPool) -> Router {
+pub fn create_routes(pool: PgPool, config: &AppConfig) -> Router {
     // Obtener entorno (default: "development")
     let environment = std::env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
     
@@ -179,54 +279,235 @@ pub fn create_routes(pool: PgPool) -> Router {
 
     // Crear repositorio y service de usuarios (Dependency Injection)
     let user_repo: Arc<dyn UserRepository> = Arc::new(UserRepositoryImpl::new(pool.clone()));
-    let user_service = Arc::new(UserService::new(user_repo));
+    let revocation_repo: Arc<dyn SessionRevocationRepository> = Arc::new(SessionRevocationRepositoryImpl::new(pool.clone()));
+    let session_service = Arc::new(SessionService::new(user_repo.clone(), revocation_repo));
+    let user_service = Arc::new(UserService::new(user_repo.clone(), session_service.clone()));
+
+    // Bitácora de auditoría y suplantación de usuarios (X-Act-As)
+    let audit_repo: Arc<dyn AuditLogRepository> = Arc::new(AuditLogRepositoryImpl::new(pool.clone()));
+    let impersonation_service = Arc::new(ImpersonationService::new(user_repo.clone(), audit_repo.clone()));
+
+    // Validación de JWT (secret HS256 y/o JWKS con caché) y estado de sesión
+    let auth_state = AuthState::from_config(config, session_service.clone(), impersonation_service);
+
+    // API keys de servicio: solo las aceptan las rutas que usan `auth_o_api_key`
+    let api_key_repo: Arc<dyn ApiKeyRepository> = Arc::new(ApiKeyRepositoryImpl::new(pool.clone()));
+    let api_key_service = Arc::new(ApiKeyService::new(api_key_repo));
+    let auth_o_api_key = auth_state.accepting_api_keys(api_key_service.clone());
+
+    // Guards de rol (el rol se resuelve desde public.users)
+    let solo_admin = RoleGuard::new(user_repo.clone(), &[ROL_ADMIN]);
+    let transportista_o_admin = RoleGuard::new(user_repo.clone(), &[ROL_TRANSPORTISTA, ROL_ADMIN]);
+
+    // Rate limiting por grupo de rutas (por usuario si está autenticado, si no por IP)
+    let limits = &config.rate_limits;
+    let public_limiter = RateLimiter::new(limits.public, limits.trust_proxy);
+    let authenticated_limiter = RateLimiter::new(limits.authenticated, limits.trust_proxy);
+    let admin_limiter = RateLimiter::new(limits.admin, limits.trust_proxy);
+    // Intentos sin credenciales válidas: por IP y por fuera de require_auth
+    let auth_failures_limiter = RateLimiter::new(limits.auth_failures, limits.trust_proxy);
 
     // Crear repositorio y service de pedidos (Dependency Injection)
     let pedido_repo: Arc<dyn PedidoRepository> = Arc::new(PedidoRepositoryImpl::new(pool.clone()));
-    let pedido_service = Arc::new(PedidoService::new(pedido_repo));
+    let pedido_service = Arc::new(PedidoService::new(pedido_repo.clone()));
+
+    // Repositorio de transportistas (para la política de acceso a pedidos)
+    let transportista_repo: Arc<dyn TransportistaRepository> = Arc::new(TransportistaRepositoryImpl::new(pool.clone()));
 
     // Crear repositorio y service de perfiles de cliente (Dependency Injection)
     let perfil_repo: Arc<dyn PerfilClienteRepository> = Arc::new(PerfilClienteRepositoryImpl::new(pool.clone()));
     let perfil_service = Arc::new(PerfilClienteService::new(perfil_repo.clone()));
 
-    // Crear repositorio y service de productos (Dependency Injection)
-    let producto_repo: Arc<dyn ProductoRepository> = Arc::new(ProductoRepositoryImpl::new(pool.clone()));
-    let producto_service = Arc::new(ProductoService::new(producto_repo));
+    // Miembros de perfiles de empresa (el extractor CurrentPerfil también los usa)
+    let miembro_repo: Arc<dyn PerfilMiembroRepository> = Arc::new(PerfilMiembroRepositoryImpl::new(pool.clone()));
+    let perfil_miembro_service = Arc::new(PerfilMiembroService::new(
+        perfil_repo.clone(),
+        miembro_repo.clone(),
+        user_repo.clone(),
+    ));
+
+    // Crédito de clientes postpago (la verificación al crear pedidos está en PedidoRepository)
+    let credito_repo: Arc<dyn CreditoRepository> = Arc::new(CreditoRepositoryImpl::new(pool.clone()));
+    let credito_service = Arc::new(CreditoService::new(credito_repo, audit_repo.clone()));
+
+    // Detección y fusión de perfiles duplicados
+    let fusion_repo: Arc<dyn PerfilFusionRepository> = Arc::new(PerfilFusionRepositoryImpl::new(pool.clone()));
+    let fusion_service = Arc::new(PerfilFusionService::new(fusion_repo));
 
     // Crear repositorio y service de direcciones (Dependency Injection)
     let direccion_repo: Arc<dyn DireccionRepository> = Arc::new(DireccionRepositoryImpl::new(pool.clone()));
-    let direccion_service = Arc::new(DireccionService::new(direccion_repo));
+    let direccion_service = Arc::new(DireccionService::new(direccion_repo.clone()));
 
-    // Rutas admin de usuarios (protegidas)
+    // Crear repositorios y services de productos y categorías (Dependency Injection).
+    // El stock de los productos se lleva por almacén (direcciones de tipo almacen).
+    let categoria_repo: Arc<dyn CategoriaRepository> = Arc::new(CategoriaRepositoryImpl::new(pool.clone()));
+    let categoria_service = Arc::new(CategoriaService::new(categoria_repo.clone()));
+    let producto_repo: Arc<dyn ProductoRepository> = Arc::new(ProductoRepositoryImpl::new(pool.clone()));
+    let producto_service = Arc::new(ProductoService::new(producto_repo, categoria_repo, direccion_repo.clone()));
+
+    // Fotos de perfil (almacenamiento local; la interfaz BlobStorage admite otros backends)
+    let blob_storage: Arc<dyn BlobStorage> = Arc::new(LocalBlobStorage::new(&config.storage_local_dir));
+    let avatar_service = Arc::new(AvatarService::new(user_repo.clone(), blob_storage));
+
+    // Exportación de datos personales y anonimización (derecho al olvido)
+    let privacy_repo: Arc<dyn DataPrivacyRepository> = Arc::new(DataPrivacyRepositoryImpl::new(pool.clone()));
+    let privacy_service = Arc::new(PrivacyService::new(
+        user_repo.clone(),
+        perfil_repo.clone(),
+        direccion_repo,
+        pedido_repo,
+        privacy_repo,
+        audit_repo,
+        session_service,
+        avatar_service.clone(),
+    ));
+
+    // Vista agregada del usuario autenticado (compone los services de cada módulo)
+    let me_service = Arc::new(MeService::new(
+        user_service.clone(),
+        perfil_service.clone(),
+        direccion_service.clone(),
+        pedido_service.clone(),
+        perfil_miembro_service.clone(),
+    ));
+
+    // Rutas admin de usuarios (protegidas, solo admin)
     let admin_users_routes = Router::new()
         .route("/api/admin/users", get(list_users).post(create_user))
+        .route("/api/admin/users/import", post(import_users))
+        .route("/api/admin/users/export", get(export_users))
         .route("/api/admin/users/{id}", get(get_user).put(update_user).delete(delete_user))
-        .route("/api/admin/users/{id}/role", patch(update_user_role))
+        .route(
+            "/api/admin/users/{id}/role",
+            patch(update_user_role).route_layer(middleware::from_fn(forbid_impersonation)),
+        )
         .route("/api/admin/users/{id}/status", patch(update_user_status))
+        .route("/api/admin/users/{id}/revoke-sessions", post(revoke_user_sessions))
         .with_state(user_service)
-        .route_layer(middleware::from_fn(require_auth));
+        .route_layer(middleware::from_fn_with_state(solo_admin.clone(), require_role))
+        .route_layer(middleware::from_fn_with_state(admin_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
+
+    // Rutas de avatares (protegidas)
+    let avatar_routes = Router::new()
+        .route(
+            "/auth/me/avatar",
+            put(upload_my_avatar).delete(delete_my_avatar)
+                // Margen para las cabeceras del multipart
+                .layer(DefaultBodyLimit::max(AVATAR_MAX_BYTES + 64 * 1024)),
+        )
+        .route("/api/users/{id}/avatar", get(get_user_avatar))
+        .with_state(avatar_service)
+        .route_layer(middleware::from_fn_with_state(authenticated_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
+
+    // Rutas admin de privacidad de usuarios (protegidas, solo admin)
+    let admin_privacy_routes = Router::new()
+        .route("/api/admin/users/{id}/data-export", get(export_user_data))
+        .route(
+            "/api/admin/users/{id}/anonymize",
+            post(anonymize_user).route_layer(middleware::from_fn(forbid_impersonation)),
+        )
+        .with_state(privacy_service.clone())
+        .route_layer(middleware::from_fn_with_state(solo_admin.clone(), require_role))
+        .route_layer(middleware::from_fn_with_state(admin_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
 
     // Rutas de pedidos (protegidas)
     let pedidos_routes = Router::new()
         .route("/api/pedidos", get(list_pedidos).post(create_pedido))
         .route("/api/pedidos/{id}", get(get_pedido).delete(cancel_pedido))
-        .route("/api/pedidos/{id}/estado", patch(update_estado_pedido))
-        .route("/api/pedidos/{id}/transportista", patch(assign_transportista))
+        .route(
+            "/api/pedidos/{id}/estado",
+            patch(update_estado_pedido)
+                .route_layer(middleware::from_fn_with_state(
+                    transportista_o_admin.with_scope(SCOPE_PEDIDOS_ESTADO),
+                    require_role,
+                )),
+        )
+        .route(
+            "/api/pedidos/{id}/transportista",
+            patch(assign_transportista)
+                .route_layer(middleware::from_fn_with_state(solo_admin.clone(), require_role)),
+        )
         .with_state(pedido_service)
-        .route_layer(middleware::from_fn(require_auth));
+        .route_layer(middleware::from_fn_with_state(authenticated_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_o_api_key.clone(), require_auth));
 
     // Rutas de perfil de cliente (protegidas)
     let perfil_routes = Router::new()
-        .route("/api/perfil", get(get_my_perfil).post(create_perfil).put(update_my_perfil).delete(delete_my_perfil))
+        .route(
+            "/api/perfil",
+            get(get_my_perfil).post(create_perfil).put(update_my_perfil)
+                .merge(delete(delete_my_perfil).route_layer(middleware::from_fn(forbid_impersonation))),
+        )
         .with_state(perfil_service.clone())
-        .route_layer(middleware::from_fn(require_auth));
+        .route_layer(middleware::from_fn_with_state(authenticated_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
+
+    // Rutas de miembros de perfiles de empresa (protegidas)
+    let perfil_miembros_routes = Router::new()
+        .route(
+            "/api/perfil/miembros",
+            get(list_miembros)
+                .merge(post(add_miembro).route_layer(middleware::from_fn(forbid_impersonation))),
+        )
+        .route(
+            "/api/perfil/miembros/{id}",
+            patch(update_miembro_rol)
+                .delete(remove_miembro)
+                .route_layer(middleware::from_fn(forbid_impersonation)),
+        )
+        .route("/api/perfil/disponibles", get(list_perfiles_disponibles))
+        .with_state(perfil_miembro_service)
+        .route_layer(middleware::from_fn_with_state(authenticated_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
+
+    // Crédito del perfil actual (protegida)
+    let credito_routes = Router::new()
+        .route("/api/perfil/credito", get(get_my_credito))
+        .with_state(credito_service.clone())
+        .route_layer(middleware::from_fn_with_state(authenticated_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
 
-    // Rutas admin de perfiles (protegidas - TODO: agregar middleware de rol admin)
+    // Rutas admin de crédito (protegidas, solo admin)
+    let admin_credito_routes = Router::new()
+        .route("/api/admin/perfiles/credito", get(list_credito_alertas))
+        .route(
+            "/api/admin/perfiles/{id}/credito",
+            get(get_credito_perfil)
+                .merge(put(configurar_credito_perfil).route_layer(middleware::from_fn(forbid_impersonation))),
+        )
+        .with_state(credito_service)
+        .route_layer(middleware::from_fn_with_state(solo_admin.clone(), require_role))
+        .route_layer(middleware::from_fn_with_state(admin_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
+
+    // Rutas admin de perfiles duplicados (protegidas, solo admin)
+    let admin_fusion_routes = Router::new()
+        .route("/api/admin/perfiles/duplicados", get(list_perfiles_duplicados))
+        .route("/api/admin/perfiles/fusiones", get(list_perfil_fusiones))
+        .route(
+            "/api/admin/perfiles/{id}/fusionar",
+            post(fusionar_perfiles).route_layer(middleware::from_fn(forbid_impersonation)),
+        )
+        .with_state(fusion_service)
+        .route_layer(middleware::from_fn_with_state(solo_admin.clone(), require_role))
+        .route_layer(middleware::from_fn_with_state(admin_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
+
+    // Rutas admin de perfiles (protegidas, solo admin)
     let admin_perfil_routes = Router::new()
         .route("/api/admin/perfiles", get(list_perfiles))
-        .route("/api/admin/perfiles/{id}", get(get_perfil_by_id).put(update_perfil_by_id).delete(delete_perfil_by_id))
+        .route(
+            "/api/admin/perfiles/{id}",
+            get(get_perfil_by_id).put(update_perfil_by_id)
+                .merge(delete(delete_perfil_by_id).route_layer(middleware::from_fn(forbid_impersonation))),
+        )
         .with_state(perfil_service)
-        .route_layer(middleware::from_fn(require_auth));
+        .route_layer(middleware::from_fn_with_state(solo_admin.clone(), require_role))
+        .route_layer(middleware::from_fn_with_state(admin_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
 
     // Rutas públicas de productos (catálogo)
     let productos_public_routes = Router::new()
@@ -235,16 +516,45 @@ pub fn create_routes(pool: PgPool) -> Router {
         .route("/api/productos/categoria/{categoria}", get(get_by_categoria))
         .route("/api/productos/sku/{sku}", get(get_by_sku))
         .route("/api/productos/{id}", get(get_producto))
-        .with_state(producto_service.clone());
+        .with_state(producto_service.clone())
+        .route_layer(middleware::from_fn_with_state(public_limiter.clone(), rate_limit));
 
-    // Rutas admin de productos (protegidas)
+    // Rutas admin de productos (protegidas, solo admin)
     let admin_productos_routes = Router::new()
         .route("/api/admin/productos", get(list_all_productos).post(create_producto))
-        .route("/api/admin/productos/{id}", put(update_producto).delete(delete_producto))
+        .route(
+            "/api/admin/productos/{id}",
+            put(update_producto)
+                .merge(delete(delete_producto).route_layer(middleware::from_fn(forbid_impersonation))),
+        )
+        .route("/api/admin/productos/conciliacion-stock", get(conciliar_stock))
         .route("/api/admin/productos/{id}/stock", patch(update_stock))
+        .route("/api/admin/productos/{id}/transferencias", post(transferir_stock))
+        .route("/api/admin/productos/{id}/movimientos", get(list_movimientos_stock))
         .route("/api/admin/productos/{id}/estado", patch(update_estado_producto))
         .with_state(producto_service)
-        .route_layer(middleware::from_fn(require_auth));
+        .route_layer(middleware::from_fn_with_state(solo_admin.clone().with_scope(SCOPE_PRODUCTOS_WRITE), require_role))
+        .route_layer(middleware::from_fn_with_state(admin_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_o_api_key.clone(), require_auth));
+
+    // Rutas públicas de categorías
+    let categorias_public_routes = Router::new()
+        .route("/api/categorias", get(get_arbol_categorias))
+        .with_state(categoria_service.clone())
+        .route_layer(middleware::from_fn_with_state(public_limiter.clone(), rate_limit));
+
+    // Rutas admin de categorías (mismos permisos que la gestión de productos)
+    let admin_categorias_routes = Router::new()
+        .route("/api/admin/categorias", get(list_categorias).post(create_categoria))
+        .route(
+            "/api/admin/categorias/{id}",
+            put(update_categoria)
+                .merge(delete(delete_categoria).route_layer(middleware::from_fn(forbid_impersonation))),
+        )
+        .with_state(categoria_service)
+        .route_layer(middleware::from_fn_with_state(solo_admin.clone().with_scope(SCOPE_PRODUCTOS_WRITE), require_role))
+        .route_layer(middleware::from_fn_with_state(admin_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_o_api_key, require_auth));
 
     // Rutas de direcciones del cliente (protegidas)
     let direcciones_routes = Router::new()
@@ -253,47 +563,111 @@ pub fn create_routes(pool: PgPool) -> Router {
         .route("/api/direcciones/{id}", get(get_direccion).put(update_direccion).delete(deactivate_direccion))
         .route("/api/direcciones/{id}/predeterminada", patch(set_predeterminada))
         .route("/api/direcciones/{id}/activar", patch(activate_direccion))
-        .route("/api/direcciones/{id}/permanente", delete(delete_direccion_permanente))
-        .with_state((direccion_service.clone(), perfil_repo.clone()))
-        .route_layer(middleware::from_fn(require_auth));
+        .route(
+            "/api/direcciones/{id}/permanente",
+            delete(delete_direccion_permanente).route_layer(middleware::from_fn(forbid_impersonation)),
+        )
+        .with_state(direccion_service.clone())
+        .route_layer(middleware::from_fn_with_state(authenticated_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
 
     // Rutas públicas de almacenes
     let almacenes_public_routes = Router::new()
         .route("/api/almacenes", get(list_almacenes))
-        .with_state(direccion_service.clone());
+        .with_state(direccion_service.clone())
+        .route_layer(middleware::from_fn_with_state(public_limiter.clone(), rate_limit));
 
-    // Rutas admin de almacenes (protegidas)
+    // Rutas admin de almacenes (protegidas, solo admin)
     let admin_almacenes_routes = Router::new()
         .route("/api/admin/almacenes", get(list_all_almacenes).post(create_almacen))
         .route("/api/admin/almacenes/{id}", delete(deactivate_almacen))
         .route("/api/admin/almacenes/{id}/activar", patch(activate_almacen))
-        .route("/api/admin/almacenes/{id}/permanente", delete(delete_almacen_permanente))
+        .route(
+            "/api/admin/almacenes/{id}/permanente",
+            delete(delete_almacen_permanente).route_layer(middleware::from_fn(forbid_impersonation)),
+        )
         .with_state(direccion_service)
-        .route_layer(middleware::from_fn(require_auth));
+        .route_layer(middleware::from_fn_with_state(solo_admin.clone(), require_role))
+        .route_layer(middleware::from_fn_with_state(admin_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
+
+    // Rutas admin de API keys (protegidas, solo admin con JWT)
+    let admin_api_keys_routes = Router::new()
+        .route("/api/admin/api-keys", get(list_api_keys).post(create_api_key))
+        .route("/api/admin/api-keys/{id}", delete(revoke_api_key))
+        .with_state(api_key_service)
+        .route_layer(middleware::from_fn_with_state(solo_admin.clone(), require_role))
+        .route_layer(middleware::from_fn_with_state(admin_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
 
     // Rutas de auth (protegidas)
     let auth_routes = Router::new()
         .route("/auth/me", get(get_current_user))
-        .route_layer(middleware::from_fn(require_auth));
+        .route("/auth/me/data-export", get(export_my_data))
+        .with_state(privacy_service)
+        .route_layer(middleware::from_fn_with_state(authenticated_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
+
+    // Vista agregada del usuario (protegida)
+    let me_routes = Router::new()
+        .route("/api/me", get(get_me))
+        .with_state(me_service)
+        .route_layer(middleware::from_fn_with_state(authenticated_limiter.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(auth_state.clone(), require_auth));
 
     // Rutas públicas
     let public_routes = Router::new()
         .route("/health", get(health_check));
 
+    // Emisor de tokens locales (solo ENVIRONMENT=development y DEV_TOKENS_ENABLED=true)
+    let dev_routes = if config.dev_tokens_enabled && config.environment == DEV_ENVIRONMENT {
+        match DevTokenIssuer::from_config(config, user_repo.clone()) {
+            Ok(issuer) => {
+                tracing::warn!("POST /dev/token habilitado (ENVIRONMENT={})", DEV_ENVIRONMENT);
+                Router::new()
+                    .route("/dev/token", post(issue_dev_token))
+                    .with_state(Arc::new(issuer))
+            }
+            Err(e) => {
+                tracing::warn!("POST /dev/token deshabilitado: {}", e);
+                Router::new()
+            }
+        }
+    } else {
+        Router::new()
+    };
+
     // Combinar todas las rutas
     Router::new()
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
         .merge(public_routes)
+        .merge(dev_routes)
         .merge(auth_routes)
+        .merge(me_routes)
         .merge(admin_users_routes)
+        .merge(admin_privacy_routes)
+        .merge(avatar_routes)
         .merge(pedidos_routes)
         .merge(perfil_routes)
+        .merge(perfil_miembros_routes)
+        .merge(credito_routes)
+        .merge(admin_credito_routes)
+        .merge(admin_fusion_routes)
         .merge(admin_perfil_routes)
         .merge(productos_public_routes)
         .merge(admin_productos_routes)
+        .merge(categorias_public_routes)
+        .merge(admin_categorias_routes)
         .merge(direcciones_routes)
         .merge(almacenes_public_routes)
         .merge(admin_almacenes_routes)
+        .merge(admin_api_keys_routes)
+        // Repositorios para los extractores CurrentPerfil y CurrentSolicitante
+        .layer(Extension(perfil_repo))
+        .layer(Extension(miembro_repo))
+        .layer(Extension(user_repo))
+        .layer(Extension(transportista_repo))
+        .layer(middleware::from_fn_with_state(auth_failures_limiter, rate_limit_auth_failures))
         .layer(cors)
         .layer(TraceLayer::new_for_http())
         .with_state(pool)
@@ -0,0 +1,90 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::shared::error::{AppError, AppResult};
+
+/// Cursor opaco de paginación: `T` serializado como JSON en base64url
+///
+/// El cliente lo recibe en `next_cursor` y lo devuelve sin modificar; lo que no
+/// se decodifica como `T` se rechaza con 400 "Cursor inválido".
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor<T>(pub T);
+
+impl<T: Serialize + DeserializeOwned> Cursor<T> {
+    pub fn encode(&self) -> AppResult<String> {
+        let json = serde_json::to_vec(&self.0)
+            .map_err(|e| AppError::Internal(format!("No se pudo generar el cursor: {}", e)))?;
+        Ok(URL_SAFE_NO_PAD.encode(json))
+    }
+
+    pub fn decode(raw: &str) -> AppResult<T> {
+        Self::decode_valid(raw, |_| true)
+    }
+
+    /// Decodifica y además exige `es_valido` (ej: valores que luego se castean en SQL)
+    pub fn decode_valid(raw: &str, es_valido: impl FnOnce(&T) -> bool) -> AppResult<T> {
+        let invalid = || AppError::BadRequest("Cursor inválido".to_string());
+
+        let bytes = URL_SAFE_NO_PAD.decode(raw).map_err(|_| invalid())?;
+        let value: T = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+
+        if es_valido(&value) { Ok(value) } else { Err(invalid()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Posicion {
+        value: String,
+        id: Uuid,
+    }
+
+    fn posicion() -> Posicion {
+        Posicion { value: "Ñandú & co".to_string(), id: Uuid::new_v4() }
+    }
+
+    fn es_invalido<T: std::fmt::Debug>(result: AppResult<T>) -> bool {
+        matches!(result, Err(AppError::BadRequest(msg)) if msg == "Cursor inválido")
+    }
+
+    #[test]
+    fn ida_y_vuelta() {
+        let posicion = posicion();
+        let raw = Cursor(posicion.clone()).encode().unwrap();
+
+        assert_eq!(Cursor::<Posicion>::decode(&raw).unwrap(), posicion);
+    }
+
+    #[test]
+    fn el_cursor_es_seguro_en_una_url() {
+        let raw = Cursor(posicion()).encode().unwrap();
+        assert!(raw.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    }
+
+    #[test]
+    fn rechaza_base64_invalido() {
+        assert!(es_invalido(Cursor::<Posicion>::decode("no es base64!")));
+    }
+
+    #[test]
+    fn rechaza_json_de_otra_forma() {
+        let raw = Cursor(serde_json::json!({ "value": "x" })).encode().unwrap();
+        assert!(es_invalido(Cursor::<Posicion>::decode(&raw)));
+
+        let raw = URL_SAFE_NO_PAD.encode("{no es json");
+        assert!(es_invalido(Cursor::<Posicion>::decode(&raw)));
+    }
+
+    #[test]
+    fn decode_valid_aplica_la_validacion() {
+        let raw = Cursor(posicion()).encode().unwrap();
+
+        assert!(Cursor::<Posicion>::decode_valid(&raw, |p| p.value.starts_with('Ñ')).is_ok());
+        assert!(es_invalido(Cursor::<Posicion>::decode_valid(&raw, |p| p.value.is_empty())));
+    }
+}
@@ -1,8 +1,10 @@
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use serde::Serialize;
 use serde_json::json;
 
@@ -11,10 +13,16 @@ use serde_json::json;
 pub enum AppError {
     NotFound(String),
     BadRequest(String),
+    /// Valor inválido en un campo concreto del request (400 con `field` en el cuerpo)
+    InvalidField { field: &'static str, message: String },
     Unauthorized(String),
     Forbidden(String),
     Internal(String),
     Database(sqlx::Error),
+    /// El pedido dejaría al perfil sobre su límite de crédito (402 con límite y disponible)
+    CreditLimitExceeded { limite: Decimal, disponible: Decimal },
+    /// Límite de requests excedido; indica los segundos a esperar (`Retry-After`)
+    TooManyRequests(u64),
 }
 
 #[derive(Serialize)]
@@ -30,9 +38,23 @@ struct ErrorBody {
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let retry_after = match &self {
+            AppError::TooManyRequests(secs) => Some(*secs),
+            _ => None,
+        };
+        let field = match &self {
+            AppError::InvalidField { field, .. } => Some(*field),
+            _ => None,
+        };
+        let credito = match &self {
+            AppError::CreditLimitExceeded { limite, disponible } => Some((*limite, *disponible)),
+            _ => None,
+        };
+
         let (status, code, message) = match self {
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, "NOT_FOUND", msg),
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, "BAD_REQUEST", msg),
+            AppError::InvalidField { message, .. } => (StatusCode::BAD_REQUEST, "INVALID_FIELD", message),
             AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, "UNAUTHORIZED", msg),
             AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, "FORBIDDEN", msg),
             AppError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR", msg),
@@ -44,16 +66,36 @@ impl IntoResponse for AppError {
                     "Error de base de datos".to_string(),
                 )
             }
+            AppError::CreditLimitExceeded { disponible, .. } => (
+                StatusCode::PAYMENT_REQUIRED,
+                "CREDIT_LIMIT_EXCEEDED",
+                format!("El pedido supera el crédito disponible ({:.2})", disponible),
+            ),
+            AppError::TooManyRequests(secs) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "RATE_LIMITED",
+                format!("Demasiadas solicitudes, reintente en {} s", secs),
+            ),
         };
 
-        let body = json!({
+        let mut body = json!({
             "error": {
                 "code": code,
                 "message": message
             }
         });
+        if let Some(field) = field {
+            body["error"]["field"] = json!(field);
+        }
+        if let Some((limite, disponible)) = credito {
+            body["error"]["limite_credito"] = json!(limite.to_f64());
+            body["error"]["disponible"] = json!(disponible.to_f64());
+        }
 
-        (status, Json(body)).into_response()
+        match retry_after {
+            Some(secs) => (status, [(header::RETRY_AFTER, secs.to_string())], Json(body)).into_response(),
+            None => (status, Json(body)).into_response(),
+        }
     }
 }
 
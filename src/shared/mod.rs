@@ -1,3 +1,5 @@
+pub mod cursor;
 pub mod error;
 
+pub use cursor::Cursor;
 pub use error::{AppError, AppResult};